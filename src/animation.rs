@@ -0,0 +1,189 @@
+use super::GraphMaker;
+use std::fmt::Write;
+
+/// Holds the frames of an animation to be saved as an animated GIF
+///
+/// Each frame holds the commands of one or more graphs. The axes are cleared before drawing each
+/// frame and the commands of the [crate::Plot] (e.g., ranges and labels) are then drawn on top
+/// of the frame. See [crate::Plot::save_animation].
+///
+/// # Example
+///
+/// ```
+/// use plotpy::{Animation, Curve, GraphMaker, Plot};
+///
+/// let mut animation = Animation::new();
+/// animation.set_fps(2.0).set_loop_count(0);
+/// let mut curve = Curve::new();
+/// for k in 0..3 {
+///     curve.draw(&[0.0, 1.0], &[0.0, k as f64]);
+///     animation.add_frame(&[&curve]);
+///     curve.clear_buffer();
+/// }
+/// let mut plot = Plot::new();
+/// plot.set_range(0.0, 1.0, 0.0, 2.0);
+/// let script = plot.render_animation(&animation, "/tmp/plotpy/doc_tests/doc_animation.gif");
+/// assert!(script.contains("FuncAnimation"));
+/// assert_eq!(animation.num_frames(), 3);
+/// ```
+///
+/// [See Matplotlib's documentation](https://matplotlib.org/stable/api/animation_api.html)
+pub struct Animation {
+    frames: Vec<String>, // Commands of each frame
+    fps: f64,            // Frames per second
+    loop_count: usize,   // Number of loops (0 means forever)
+}
+
+impl Animation {
+    /// Creates a new Animation object
+    pub fn new() -> Self {
+        Animation {
+            frames: Vec::new(),
+            fps: 5.0,
+            loop_count: 0,
+        }
+    }
+
+    /// Adds a new frame with the commands of the given graphs (copied)
+    ///
+    /// The graphs may be cleared (e.g., with `clear_buffer`) and re-drawn for the next frame.
+    pub fn add_frame(&mut self, graphs: &[&dyn GraphMaker]) -> &mut Self {
+        let mut frame = String::new();
+        for graph in graphs {
+            frame.push_str(graph.get_buffer());
+        }
+        self.frames.push(frame);
+        self
+    }
+
+    /// Returns the number of frames
+    pub fn num_frames(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Sets the number of frames per second (default = 5)
+    pub fn set_fps(&mut self, fps: f64) -> &mut Self {
+        self.fps = fps;
+        self
+    }
+
+    /// Sets the number of times the GIF loops (default = 0, i.e., loops forever)
+    pub fn set_loop_count(&mut self, loop_count: usize) -> &mut Self {
+        self.loop_count = loop_count;
+        self
+    }
+
+    /// Returns the commands defining the animation (anim) with the common commands drawn after each frame
+    pub(crate) fn commands(&self, common: &str) -> String {
+        let mut buffer = String::from("import matplotlib.animation as ani\n");
+        for (i, frame) in self.frames.iter().enumerate() {
+            write!(&mut buffer, "def frame_{}():\n", i).unwrap();
+            write_indented(&mut buffer, frame);
+        }
+        buffer.push_str("def draw_common():\n");
+        write_indented(&mut buffer, common);
+        buffer.push_str("FRAMES=[");
+        for i in 0..self.frames.len() {
+            write!(&mut buffer, "frame_{},", i).unwrap();
+        }
+        write!(
+            &mut buffer,
+            "]\n\
+             def update(i):\n\
+             \x20   for a in plt.gcf().axes: a.clear()\n\
+             \x20   FRAMES[i]()\n\
+             \x20   draw_common()\n\
+             anim=ani.FuncAnimation(plt.gcf(),update,frames=len(FRAMES))\n\
+             writer=ani.PillowWriter(fps={})\n",
+            self.fps
+        )
+        .unwrap();
+        buffer
+    }
+
+    /// Returns the commands to re-save the GIF file (fn) with the number of loops (if not looping forever)
+    ///
+    /// The file is read into memory first because Pillow reads the frames lazily.
+    pub(crate) fn loop_commands(&self) -> String {
+        if self.loop_count == 0 {
+            return String::new();
+        }
+        format!(
+            "import io\n\
+             from PIL import Image\n\
+             with open(fn,'rb') as f:\n\
+             \x20   gif=Image.open(io.BytesIO(f.read()))\n\
+             gif.save(fn,save_all=True,loop={})\n",
+            self.loop_count
+        )
+    }
+}
+
+/// Writes the commands indented by four spaces (as the body of a function)
+fn write_indented(buffer: &mut String, commands: &str) {
+    if commands.trim().is_empty() {
+        buffer.push_str("    pass\n");
+        return;
+    }
+    for line in commands.lines() {
+        write!(buffer, "    {}\n", line).unwrap();
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::Animation;
+    use crate::{Curve, GraphMaker};
+
+    #[test]
+    fn new_works() {
+        let animation = Animation::new();
+        assert_eq!(animation.frames.len(), 0);
+        assert_eq!(animation.fps, 5.0);
+        assert_eq!(animation.loop_count, 0);
+    }
+
+    #[test]
+    fn commands_work() {
+        let mut curve = Curve::new();
+        let mut animation = Animation::new();
+        animation.set_fps(10.0).set_loop_count(2);
+        curve.draw(&[0.0, 1.0], &[0.0, 1.0]);
+        animation.add_frame(&[&curve]);
+        curve.clear_buffer();
+        animation.add_frame(&[&curve]);
+        assert_eq!(animation.num_frames(), 2);
+        let b: &str = "import matplotlib.animation as ani\n\
+                       def frame_0():\n\
+                       \x20   x=np.array([0,1,],dtype=float)\n\
+                       \x20   y=np.array([0,1,],dtype=float)\n\
+                       \x20   plt.plot(x,y)\n\
+                       def frame_1():\n\
+                       \x20   pass\n\
+                       def draw_common():\n\
+                       \x20   plt.grid()\n\
+                       FRAMES=[frame_0,frame_1,]\n\
+                       def update(i):\n\
+                       \x20   for a in plt.gcf().axes: a.clear()\n\
+                       \x20   FRAMES[i]()\n\
+                       \x20   draw_common()\n\
+                       anim=ani.FuncAnimation(plt.gcf(),update,frames=len(FRAMES))\n\
+                       writer=ani.PillowWriter(fps=10)\n";
+        assert_eq!(animation.commands("plt.grid()\n"), b);
+    }
+
+    #[test]
+    fn loop_commands_work() {
+        let mut animation = Animation::new();
+        assert_eq!(animation.loop_commands(), "");
+        animation.set_loop_count(3);
+        let b: &str = "import io\n\
+                       from PIL import Image\n\
+                       with open(fn,'rb') as f:\n\
+                       \x20   gif=Image.open(io.BytesIO(f.read()))\n\
+                       gif.save(fn,save_all=True,loop=3)\n";
+        assert_eq!(animation.loop_commands(), b);
+    }
+}
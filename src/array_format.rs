@@ -0,0 +1,106 @@
+/// Maximum number of digits after the decimal point of the generated numbers
+const MAX_PRECISION: usize = 17;
+
+/// Holds the options to write the numbers of the generated Python lists and arrays
+///
+/// The graphs (and the Plot) write their lists and arrays with their own ArrayFormat;
+/// e.g., see [crate::Curve::set_array_format].
+///
+/// # Examples
+///
+/// ```
+/// use plotpy::{ArrayFormat, Curve, GraphMaker};
+///
+/// let mut format = ArrayFormat::new();
+/// format.set_trailing_comma(false);
+/// let mut curve = Curve::new();
+/// curve.set_array_format(&format).draw(&[1.0, 2.0], &[3.0, 4.0]);
+/// assert!(curve.get_buffer().starts_with("x=np.array([1,2],dtype=float)"));
+///
+/// format.set_trailing_comma(true).set_precision(Some(3));
+/// let mut curve = Curve::new();
+/// curve.set_array_format(&format).draw(&[1.0, 2.0], &[3.0, 4.0]);
+/// assert!(curve.get_buffer().starts_with("x=np.array([1.000,2.000,],dtype=float)"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct ArrayFormat {
+    /// Indicates whether the entries are followed by a comma (default = true)
+    trailing_comma: bool,
+
+    /// Number of digits after the decimal point (default = None, i.e., the shortest representation)
+    precision: Option<usize>,
+}
+
+impl ArrayFormat {
+    /// Allocates a new instance
+    pub fn new() -> Self {
+        ArrayFormat {
+            trailing_comma: true,
+            precision: None,
+        }
+    }
+
+    /// Sets whether the entries of the generated lists and arrays are followed by a comma (default = true)
+    ///
+    /// By default, every entry is followed by a comma; e.g., `[1,2,3,]`, which is valid Python.
+    /// Setting `false` generates `[1,2,3]` instead, which may be required by stricter parsers
+    /// that reuse the generated scripts.
+    pub fn set_trailing_comma(&mut self, flag: bool) -> &mut Self {
+        self.trailing_comma = flag;
+        self
+    }
+
+    /// Sets the number of digits after the decimal point of the generated numbers (default = None)
+    ///
+    /// By default (`None`), numbers are written with the shortest representation that
+    /// round-trips exactly; e.g., `0.1` and `1`. A small precision reduces the size of the
+    /// Python script when plotting large datasets. The precision is clamped to 17.
+    pub fn set_precision(&mut self, precision: Option<usize>) -> &mut Self {
+        self.precision = precision.map(|digits| usize::min(digits, MAX_PRECISION));
+        self
+    }
+
+    /// Returns whether the entries are followed by a comma
+    pub(crate) fn trailing_comma(&self) -> bool {
+        self.trailing_comma
+    }
+
+    /// Returns the number of digits after the decimal point, if set
+    pub(crate) fn precision(&self) -> Option<usize> {
+        self.precision
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::ArrayFormat;
+
+    #[test]
+    fn new_works() {
+        let format = ArrayFormat::new();
+        assert_eq!(format.trailing_comma, true);
+        assert_eq!(format.precision, None);
+    }
+
+    #[test]
+    fn set_trailing_comma_works() {
+        let mut format = ArrayFormat::new();
+        format.set_trailing_comma(false);
+        assert_eq!(format.trailing_comma(), false);
+        let clone = format.clone();
+        assert_eq!(clone.trailing_comma(), false);
+    }
+
+    #[test]
+    fn set_precision_works() {
+        let mut format = ArrayFormat::new();
+        format.set_precision(Some(3));
+        assert_eq!(format.precision(), Some(3));
+        format.set_precision(Some(100));
+        assert_eq!(format.precision(), Some(17));
+        format.set_precision(None);
+        assert_eq!(format.precision(), None);
+    }
+}
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 /// Implements the sign function
 ///
 /// ```text
@@ -68,6 +70,38 @@ pub fn linspace(start: f64, stop: f64, count: usize) -> Vec<f64> {
     res
 }
 
+/// Converts optional values into numbers with NaN holes (e.g., to express gaps in the data)
+///
+/// Lines drawn by [crate::Curve::draw] are interrupted at NaN values; thus, `None` becomes a gap.
+///
+/// # Examples
+///
+/// ```
+/// use plotpy::option_to_nan;
+///
+/// let y = option_to_nan(&[Some(1.0), None, Some(3.0)]);
+/// assert_eq!(y[0], 1.0);
+/// assert!(y[1].is_nan());
+/// ```
+pub fn option_to_nan(values: &[Option<f64>]) -> Vec<f64> {
+    values.iter().map(|v| v.unwrap_or(f64::NAN)).collect()
+}
+
+/// Converts durations (e.g., time stamps of a time series) into seconds
+///
+/// # Examples
+///
+/// ```
+/// use plotpy::durations_to_seconds;
+/// use std::time::Duration;
+///
+/// let t = durations_to_seconds(&[Duration::from_millis(1500), Duration::from_secs(2)]);
+/// assert_eq!(t, &[1.5, 2.0]);
+/// ```
+pub fn durations_to_seconds(durations: &[Duration]) -> Vec<f64> {
+    durations.iter().map(|d| d.as_secs_f64()).collect()
+}
+
 /// Generates 2d points (meshgrid)
 ///
 /// # Input
@@ -164,7 +198,8 @@ where
 
 #[cfg(test)]
 mod tests {
-    use super::{generate2d, generate3d, linspace, sign, suq_cos, suq_sin};
+    use super::{durations_to_seconds, generate2d, generate3d, linspace, option_to_nan, sign, suq_cos, suq_sin};
+    use std::time::Duration;
 
     fn approx_eq(a: f64, b: f64, tol: f64) {
         let diff = f64::abs(a - b);
@@ -212,6 +247,28 @@ mod tests {
         approx_eq(suq_cos(-PI / 4.0, 2.0), 0.5, 1e-14);
     }
 
+    #[test]
+    fn durations_to_seconds_works() {
+        assert_eq!(durations_to_seconds(&[]).len(), 0);
+        let t = durations_to_seconds(&[
+            Duration::ZERO,
+            Duration::from_micros(250),
+            Duration::new(3, 500_000_000),
+        ]);
+        assert_eq!(t, &[0.0, 0.00025, 3.5]);
+    }
+
+    #[test]
+    fn option_to_nan_works() {
+        let empty: &[Option<f64>] = &[];
+        assert_eq!(option_to_nan(empty).len(), 0);
+        let y = option_to_nan(&[Some(1.0), None, Some(3.0)]);
+        assert_eq!(y.len(), 3);
+        assert_eq!(y[0], 1.0);
+        assert!(y[1].is_nan());
+        assert_eq!(y[2], 3.0);
+    }
+
     #[test]
     fn linspace_works() {
         let x = linspace(0.0, 1.0, 11);
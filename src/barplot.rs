@@ -1,4 +1,4 @@
-use super::{vector_to_array, generate_list_quoted, AsVector, GraphMaker};
+use super::{generate_list_quoted, vector_to_array, ArrayFormat, AsF64, AsVector, GraphMaker};
 use std::fmt::Write;
 
 /// Generates a Barplot plot
@@ -118,6 +118,7 @@ pub struct Barplot {
     horizontal: bool,          // Horizontal barplot
     x_errors: Vec<f64>,        // Shows x-error icons on horizontal bars
     extra: String,             // Extra commands (comma separated)
+    array_format: ArrayFormat, // Format of the numbers in the generated arrays
     buffer: String,            // buffer
 }
 
@@ -133,6 +134,7 @@ impl Barplot {
             horizontal: false,
             x_errors: Vec::new(),
             extra: String::new(),
+            array_format: ArrayFormat::new(),
             buffer: String::new(),
         }
     }
@@ -145,19 +147,19 @@ impl Barplot {
     pub fn draw<'a, T, U>(&mut self, x: &'a T, y: &'a T)
     where
         T: AsVector<'a, U>,
-        U: 'a + std::fmt::Display,
+        U: 'a + std::fmt::Display + AsF64,
     {
-        vector_to_array(&mut self.buffer, "x", x);
-        vector_to_array(&mut self.buffer, "y", y);
+        vector_to_array(&mut self.buffer, &self.array_format, "x", x);
+        vector_to_array(&mut self.buffer, &self.array_format, "y", y);
         let opt = self.options();
         if self.colors.len() > 0 {
-            generate_list_quoted(&mut self.buffer, "colors", self.colors.as_slice());
+            generate_list_quoted(&mut self.buffer, &self.array_format, "colors", self.colors.as_slice());
         }
         if self.bottom.len() > 0 {
-            vector_to_array(&mut self.buffer, "bottom", &self.bottom);
+            vector_to_array(&mut self.buffer, &self.array_format, "bottom", &self.bottom);
         }
         if self.x_errors.len() > 0 {
-            vector_to_array(&mut self.buffer, "xerr", &self.x_errors);
+            vector_to_array(&mut self.buffer, &self.array_format, "xerr", &self.x_errors);
         }
         if self.horizontal {
             write!(&mut self.buffer, "p=plt.barh(x,y{})\n", &opt).unwrap();
@@ -179,19 +181,19 @@ impl Barplot {
     where
         S: std::fmt::Display,
         T: AsVector<'a, U>,
-        U: 'a + std::fmt::Display,
+        U: 'a + std::fmt::Display + AsF64,
     {
-        generate_list_quoted(&mut self.buffer, "x", x);
-        vector_to_array(&mut self.buffer, "y", y);
+        generate_list_quoted(&mut self.buffer, &self.array_format, "x", x);
+        vector_to_array(&mut self.buffer, &self.array_format, "y", y);
         let opt = self.options();
         if self.colors.len() > 0 {
-            generate_list_quoted(&mut self.buffer, "colors", self.colors.as_slice());
+            generate_list_quoted(&mut self.buffer, &self.array_format, "colors", self.colors.as_slice());
         }
         if self.bottom.len() > 0 {
-            vector_to_array(&mut self.buffer, "bottom", &self.bottom);
+            vector_to_array(&mut self.buffer, &self.array_format, "bottom", &self.bottom);
         }
         if self.x_errors.len() > 0 {
-            vector_to_array(&mut self.buffer, "xerr", &self.x_errors);
+            vector_to_array(&mut self.buffer, &self.array_format, "xerr", &self.x_errors);
         }
         if self.horizontal {
             write!(&mut self.buffer, "p=plt.barh(x,y{})\n", &opt).unwrap();
@@ -269,6 +271,12 @@ impl Barplot {
         self
     }
 
+    /// Sets the format of the numbers in the generated lists and arrays
+    pub fn set_array_format(&mut self, format: &ArrayFormat) -> &mut Self {
+        self.array_format = format.clone();
+        self
+    }
+
     /// Returns options for barplot
     fn options(&self) -> String {
         let mut opt = String::new();
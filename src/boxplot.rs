@@ -1,4 +1,4 @@
-use super::{generate_list, generate_nested_list, matrix_to_array, AsMatrix, GraphMaker};
+use super::{generate_list, generate_nested_list, matrix_to_array, ArrayFormat, AsMatrix, GraphMaker};
 use std::fmt::Write;
 
 /// Draw a box and whisker plot
@@ -157,6 +157,7 @@ pub struct Boxplot {
     boxprops: String,   // The properties of the box
     whiskerprops: String, // The properties of the whisker
     extra: String,      // Extra commands (comma separated)
+    array_format: ArrayFormat, // Format of the numbers in the generated arrays
     buffer: String,     // Buffer
 }
 
@@ -175,6 +176,7 @@ impl Boxplot {
             boxprops: String::new(),
             whiskerprops: String::new(),
             extra: String::new(),
+            array_format: ArrayFormat::new(),
             buffer: String::new(),
         }
     }
@@ -193,9 +195,9 @@ impl Boxplot {
     where
         T: std::fmt::Display,
     {
-        generate_nested_list(&mut self.buffer, "x", data);
+        generate_nested_list(&mut self.buffer, &self.array_format, "x", data);
         if self.positions.len() > 0 {
-            generate_list(&mut self.buffer, "positions", self.positions.as_slice());
+            generate_list(&mut self.buffer, &self.array_format, "positions", self.positions.as_slice());
         }
         let opt = self.options();
         write!(&mut self.buffer, "p=plt.boxplot(x{})\n", &opt).unwrap();
@@ -216,9 +218,9 @@ impl Boxplot {
         T: AsMatrix<'a, U>,
         U: 'a + std::fmt::Display,
     {
-        matrix_to_array(&mut self.buffer, "x", data);
+        matrix_to_array(&mut self.buffer, &self.array_format, "x", data);
         if self.positions.len() > 0 {
-            generate_list(&mut self.buffer, "positions", self.positions.as_slice());
+            generate_list(&mut self.buffer, &self.array_format, "positions", self.positions.as_slice());
         }
         let opt = self.options();
         write!(&mut self.buffer, "p=plt.boxplot(x{})\n", &opt).unwrap();
@@ -304,6 +306,12 @@ impl Boxplot {
         self
     }
 
+    /// Sets the format of the numbers in the generated lists and arrays
+    pub fn set_array_format(&mut self, format: &ArrayFormat) -> &mut Self {
+        self.array_format = format.clone();
+        self
+    }
+
     /// Returns options (optional parameters) for boxplot
     fn options(&self) -> String {
         let mut opt = String::new();
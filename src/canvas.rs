@@ -1,4 +1,4 @@
-use super::{GraphMaker, StrError};
+use super::{Color, GraphMaker, StrError};
 use crate::AsMatrix;
 use std::fmt::Write;
 
@@ -125,12 +125,13 @@ pub enum PolyCode {
 /// See also integration tests in the [tests directory](https://github.com/cpmech/plotpy/tree/main/tests)
 pub struct Canvas {
     // features
-    edge_color: String,  // Edge color (shared)
-    face_color: String,  // Face color (shared)
-    line_width: f64,     // Line width of edge (shared)
-    line_style: String,  // Style of lines (shared)
-    arrow_scale: f64,    // Arrow scale
-    arrow_style: String, // Arrow style
+    edge_color: String,   // Edge color (shared)
+    face_color: String,   // Face color (shared)
+    line_width: f64,      // Line width of edge (shared)
+    line_style: String,   // Style of lines (shared)
+    arrow_scale: f64,     // Arrow scale
+    arrow_style: String,  // Arrow style
+    arrow_curvature: f64, // Curvature of annotation arrows (connectionstyle arc3)
 
     // text
     text_color: String,            // Text color
@@ -157,20 +158,21 @@ impl Canvas {
     pub fn new() -> Self {
         Canvas {
             // features
-            edge_color: "#427ce5".to_string(),
+            edge_color: "'#427ce5'".to_string(),
             face_color: String::new(),
             line_width: 0.0,
             line_style: String::new(),
             arrow_scale: 0.0,
             arrow_style: String::new(),
+            arrow_curvature: 0.0,
             // text
-            text_color: "#343434".to_string(),
+            text_color: "'#343434'".to_string(),
             text_align_horizontal: "center".to_string(),
             text_align_vertical: "center".to_string(),
             text_fontsize: 10.0,
             text_rotation: 0.0,
             // alternative text
-            alt_text_color: "#a81414".to_string(),
+            alt_text_color: "'#a81414'".to_string(),
             alt_text_align_horizontal: String::new(),
             alt_text_align_vertical: String::new(),
             alt_text_fontsize: 8.0,
@@ -216,6 +218,32 @@ impl Canvas {
         .unwrap();
     }
 
+    /// Draws an arrow connecting two points with a text label at the starting point (2D only)
+    ///
+    /// # Input
+    ///
+    /// * `xi,yi` -- coordinates of the starting point (where the text is placed)
+    /// * `xf,yf` -- coordinates of the ending point (pointed by the arrow)
+    /// * `text` -- the label
+    ///
+    /// # Notes
+    ///
+    /// * The arrow style defaults to "->"; see [Canvas::set_arrow_style()]
+    /// * Use [Canvas::set_arrow_curvature()] to draw a curved arrow
+    pub fn draw_annotation_arrow<T>(&mut self, xi: T, yi: T, xf: T, yf: T, text: &str)
+    where
+        T: std::fmt::Display,
+    {
+        let opt_arrow = self.options_annotation_arrow();
+        let opt_text = self.options_text();
+        write!(
+            &mut self.buffer,
+            "plt.annotate(r'{}',xy=({},{}),xytext=({},{}),arrowprops=dict({}){})\n",
+            text, xf, yf, xi, yi, &opt_arrow, &opt_text
+        )
+        .unwrap();
+    }
+
     /// Draws circle (2D only)
     pub fn draw_circle<T>(&mut self, xc: T, yc: T, r: T)
     where
@@ -614,14 +642,24 @@ impl Canvas {
     }
 
     /// Sets the edge color (shared among features)
-    pub fn set_edge_color(&mut self, color: &str) -> &mut Self {
-        self.edge_color = String::from(color);
+    ///
+    /// The color may be given by name (e.g., "red" or "#ff0000") or as a [Color]
+    pub fn set_edge_color<C>(&mut self, color: C) -> &mut Self
+    where
+        C: Into<Color>,
+    {
+        self.edge_color = color.into().to_python();
         self
     }
 
     /// Sets the face color (shared among features)
-    pub fn set_face_color(&mut self, color: &str) -> &mut Self {
-        self.face_color = String::from(color);
+    ///
+    /// The color may be given by name (e.g., "red" or "#ff0000") or as a [Color]
+    pub fn set_face_color<C>(&mut self, color: C) -> &mut Self
+    where
+        C: Into<Color>,
+    {
+        self.face_color = color.into().to_python();
         self
     }
 
@@ -672,9 +710,23 @@ impl Canvas {
         self
     }
 
+    /// Sets the curvature of annotation arrows
+    ///
+    /// The curvature corresponds to `rad` in `connectionstyle='arc3,rad=...'`. A value of
+    /// zero (default) yields a straight arrow. See [Canvas::draw_annotation_arrow()]
+    pub fn set_arrow_curvature(&mut self, rad: f64) -> &mut Self {
+        self.arrow_curvature = rad;
+        self
+    }
+
     /// Sets the text color
-    pub fn set_text_color(&mut self, color: &str) -> &mut Self {
-        self.text_color = String::from(color);
+    ///
+    /// The color may be given by name (e.g., "red" or "#ff0000") or as a [Color]
+    pub fn set_text_color<C>(&mut self, color: C) -> &mut Self
+    where
+        C: Into<Color>,
+    {
+        self.text_color = color.into().to_python();
         self
     }
 
@@ -707,8 +759,13 @@ impl Canvas {
     }
 
     /// Sets the alternative text color
-    pub fn set_alt_text_color(&mut self, color: &str) -> &mut Self {
-        self.alt_text_color = String::from(color);
+    ///
+    /// The color may be given by name (e.g., "red" or "#ff0000") or as a [Color]
+    pub fn set_alt_text_color<C>(&mut self, color: C) -> &mut Self
+    where
+        C: Into<Color>,
+    {
+        self.alt_text_color = color.into().to_python();
         self
     }
 
@@ -750,10 +807,10 @@ impl Canvas {
     fn options_shared(&self) -> String {
         let mut opt = String::new();
         if self.edge_color != "" {
-            write!(&mut opt, ",edgecolor='{}'", self.edge_color).unwrap();
+            write!(&mut opt, ",edgecolor={}", self.edge_color).unwrap();
         }
         if self.face_color != "" {
-            write!(&mut opt, ",facecolor='{}'", self.face_color).unwrap();
+            write!(&mut opt, ",facecolor={}", self.face_color).unwrap();
         }
         if self.line_width > 0.0 {
             write!(&mut opt, ",linewidth={}", self.line_width).unwrap();
@@ -779,11 +836,38 @@ impl Canvas {
         opt
     }
 
+    /// Returns the properties of annotation arrows
+    fn options_annotation_arrow(&self) -> String {
+        let mut opt = String::new();
+        if self.arrow_style != "" {
+            write!(&mut opt, "arrowstyle='{}'", self.arrow_style).unwrap();
+        } else {
+            write!(&mut opt, "arrowstyle='->'").unwrap();
+        }
+        write!(&mut opt, ",shrinkA=0,shrinkB=0").unwrap();
+        if self.arrow_scale > 0.0 {
+            write!(&mut opt, ",mutation_scale={}", self.arrow_scale).unwrap();
+        }
+        if self.arrow_curvature != 0.0 {
+            write!(&mut opt, ",connectionstyle='arc3,rad={}'", self.arrow_curvature).unwrap();
+        }
+        if self.edge_color != "" {
+            write!(&mut opt, ",color={}", self.edge_color).unwrap();
+        }
+        if self.line_width > 0.0 {
+            write!(&mut opt, ",linewidth={}", self.line_width).unwrap();
+        }
+        if self.line_style != "" {
+            write!(&mut opt, ",linestyle='{}'", self.line_style).unwrap();
+        }
+        opt
+    }
+
     /// Returns options for text
     fn options_text(&self) -> String {
         let mut opt = String::new();
         if self.text_color != "" {
-            write!(&mut opt, ",color='{}'", self.text_color).unwrap();
+            write!(&mut opt, ",color={}", self.text_color).unwrap();
         }
         if self.text_align_horizontal != "" {
             write!(&mut opt, ",ha='{}'", self.text_align_horizontal).unwrap();
@@ -804,7 +888,7 @@ impl Canvas {
     fn options_alt_text(&self) -> String {
         let mut opt = String::new();
         if self.alt_text_color != "" {
-            write!(&mut opt, ",color='{}'", self.alt_text_color).unwrap();
+            write!(&mut opt, ",color={}", self.alt_text_color).unwrap();
         }
         if self.alt_text_align_horizontal != "" {
             write!(&mut opt, ",ha='{}'", self.alt_text_align_horizontal).unwrap();
@@ -825,7 +909,7 @@ impl Canvas {
     fn options_line_3d(&self) -> String {
         let mut opt = String::new();
         if self.edge_color != "" {
-            write!(&mut opt, ",color='{}'", self.edge_color).unwrap();
+            write!(&mut opt, ",color={}", self.edge_color).unwrap();
         }
         if self.line_width > 0.0 {
             write!(&mut opt, ",linewidth={}", self.line_width).unwrap();
@@ -924,7 +1008,7 @@ impl GraphMaker for Canvas {
 #[cfg(test)]
 mod tests {
     use super::Canvas;
-    use crate::{GraphMaker, PolyCode};
+    use crate::{Color, GraphMaker, PolyCode};
 
     #[test]
     fn derive_works() {
@@ -938,12 +1022,13 @@ mod tests {
     #[test]
     fn new_works() {
         let canvas = Canvas::new();
-        assert_eq!(canvas.edge_color.len(), 7);
+        assert_eq!(canvas.edge_color, "'#427ce5'");
         assert_eq!(canvas.face_color.len(), 0);
         assert_eq!(canvas.line_width, 0.0);
         assert_eq!(canvas.line_style.len(), 0);
         assert_eq!(canvas.arrow_scale, 0.0);
         assert_eq!(canvas.arrow_style.len(), 0);
+        assert_eq!(canvas.arrow_curvature, 0.0);
         assert_eq!(canvas.buffer.len(), 0);
     }
 
@@ -965,6 +1050,8 @@ mod tests {
              ,linestyle='--'\
              ,clip_on=False"
         );
+        canvas.set_edge_color(Color::Rgb(1.0, 0.0, 0.0)).set_face_color("");
+        assert!(canvas.options_shared().starts_with(",edgecolor=(1,0,0),linewidth=2.5"));
     }
 
     #[test]
@@ -1096,6 +1183,25 @@ mod tests {
         assert_eq!(canvas.buffer, b);
     }
 
+    #[test]
+    fn annotation_arrow_works() {
+        let mut canvas = Canvas::new();
+        canvas.draw_annotation_arrow(0.0, 0.0, 1.0, 1.0, "peak");
+        let b: &str =
+            "plt.annotate(r'peak',xy=(1,1),xytext=(0,0),arrowprops=dict(arrowstyle='->',shrinkA=0,shrinkB=0,color='#427ce5'),color='#343434',ha='center',va='center',fontsize=10)\n";
+        assert_eq!(canvas.buffer, b);
+        canvas.clear_buffer();
+        canvas
+            .set_arrow_style("-|>")
+            .set_arrow_scale(20.0)
+            .set_arrow_curvature(-0.3)
+            .set_line_width(2.0)
+            .draw_annotation_arrow(0.0, 0.0, 1.0, 1.0, "peak");
+        let b: &str =
+            "plt.annotate(r'peak',xy=(1,1),xytext=(0,0),arrowprops=dict(arrowstyle='-|>',shrinkA=0,shrinkB=0,mutation_scale=20,connectionstyle='arc3,rad=-0.3',color='#427ce5',linewidth=2),color='#343434',ha='center',va='center',fontsize=10)\n";
+        assert_eq!(canvas.buffer, b);
+    }
+
     #[test]
     fn circle_works() {
         let mut canvas = Canvas::new();
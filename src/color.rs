@@ -0,0 +1,95 @@
+use std::fmt::Write;
+
+/// Holds a color given by name (or hex code) or by its RGB(A) components
+///
+/// # Examples
+///
+/// ```
+/// use plotpy::{Color, Curve};
+///
+/// let mut curve = Curve::new();
+/// curve
+///     .set_line_color("#1862ab")
+///     .set_marker_color(Color::Rgba(1.0, 0.0, 0.0, 0.5))
+///     .set_marker_line_color(Color::Rgb(0.2, 0.2, 0.2));
+/// ```
+///
+/// See [Matplotlib's documentation](https://matplotlib.org/stable/users/explain/colors/colors.html)
+#[derive(Clone, Debug, PartialEq)]
+pub enum Color {
+    /// Named color such as "red" or hex code such as "#ff0000"
+    Named(String),
+
+    /// Red, green, and blue components in [0, 1]
+    Rgb(f64, f64, f64),
+
+    /// Red, green, blue, and alpha components in [0, 1]
+    Rgba(f64, f64, f64, f64),
+}
+
+impl Color {
+    /// Returns the Python literal representing this color (a quoted string or a tuple)
+    ///
+    /// Returns an empty string if the named color is empty (i.e., the color is unset).
+    pub(crate) fn to_python(&self) -> String {
+        let mut res = String::new();
+        match self {
+            Color::Named(name) => {
+                if name != "" {
+                    write!(&mut res, "'{}'", name).unwrap();
+                }
+            }
+            Color::Rgb(r, g, b) => write!(&mut res, "({},{},{})", r, g, b).unwrap(),
+            Color::Rgba(r, g, b, a) => write!(&mut res, "({},{},{},{})", r, g, b, a).unwrap(),
+        }
+        res
+    }
+}
+
+impl From<&str> for Color {
+    fn from(name: &str) -> Self {
+        Color::Named(name.to_string())
+    }
+}
+
+impl From<&String> for Color {
+    fn from(name: &String) -> Self {
+        Color::Named(name.clone())
+    }
+}
+
+impl From<String> for Color {
+    fn from(name: String) -> Self {
+        Color::Named(name)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::Color;
+
+    #[test]
+    fn to_python_works() {
+        assert_eq!(Color::Named(String::new()).to_python(), "");
+        assert_eq!(Color::Named("red".to_string()).to_python(), "'red'");
+        assert_eq!(Color::Rgb(1.0, 0.5, 0.0).to_python(), "(1,0.5,0)");
+        assert_eq!(Color::Rgba(1.0, 0.5, 0.0, 0.25).to_python(), "(1,0.5,0,0.25)");
+    }
+
+    #[test]
+    fn from_works() {
+        let name = String::from("#f00");
+        assert_eq!(Color::from("#f00"), Color::Named("#f00".to_string()));
+        assert_eq!(Color::from(&name), Color::Named("#f00".to_string()));
+        assert_eq!(Color::from(name), Color::Named("#f00".to_string()));
+    }
+
+    #[test]
+    fn derive_works() {
+        let color = Color::Rgb(1.0, 0.0, 0.0);
+        let clone = color.clone();
+        assert_eq!(format!("{:?}", clone), "Rgb(1.0, 0.0, 0.0)");
+    }
+}
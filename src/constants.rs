@@ -22,7 +22,7 @@
 /// * `set_equal_axes` -- Configures the aspect of axes with a same scaling from data to plot units for x, y and z.
 ///   For example a circle will show as a circle in the screen and not an ellipse. This function also handles
 ///   the 3D case which is a little tricky with Matplotlib. In this case (3D), the version of Matplotlib
-///   must be greater than 3.3.0.
+///   must be greater than 3.3.0. Only the current axes (i.e., the active subplot) is modified.
 pub const PYTHON_HEADER: &str = "### file generated by the 'plotpy' Rust crate
 
 import numpy as np
@@ -83,13 +83,11 @@ def axis_to_data(coords):
 
 # Configures the aspect of axes with a same scaling from data to plot units for x, y and z.
 def set_equal_axes():
-    global THREE_D
-    if len(THREE_D) == 0:
-        ax = plt.gca()
+    ax = plt.gca()
+    if ax.name != '3d':
         ax.axes.set_aspect('equal')
         return
     try:
-        ax = ax3d()
         ax.set_box_aspect([1,1,1])
         limits = np.array([ax.get_xlim3d(), ax.get_ylim3d(), ax.get_zlim3d()])
         origin = np.mean(limits, axis=1)
@@ -146,6 +144,6 @@ mod tests {
 
     #[test]
     fn constants_are_correct() {
-        assert_eq!(PYTHON_HEADER.len(), 2770);
+        assert_eq!(PYTHON_HEADER.len(), 2725);
     }
 }
@@ -1,4 +1,7 @@
-use super::{matrix_to_array, vector_to_array, generate_list_quoted, AsMatrix, GraphMaker};
+use super::{
+    generate_list_quoted, linspace, matrix_to_array, to_f64, vector_to_array, ArrayFormat, AsF64, AsMatrix, GraphMaker,
+    StrError,
+};
 use std::fmt::Write;
 
 /// Generates a contour plot
@@ -46,27 +49,30 @@ use std::fmt::Write;
 ///
 /// ![integ_contour.svg](https://raw.githubusercontent.com/cpmech/plotpy/main/figures/integ_contour.svg)
 pub struct Contour {
-    colors: Vec<String>,         // Colors to be used instead of colormap
-    levels: Vec<f64>,            // Pre-defined levels
-    colormap_name: String,       // Colormap name
-    no_lines: bool,              // Skip drawing a lines contour
-    no_labels: bool,             // Skip adding labels to the lines contour
-    no_inline_labels: bool,      // Do not draw labels inline
-    no_colorbar: bool,           // Skip drawing a colorbar
-    colorbar_label: String,      // Colorbar label
-    number_format_cb: String,    // Number format for the labels in lines contour
-    line_color: String,          // Line color for the lines contour
-    line_style: String,          // Line style for the lines contour
-    line_width: f64,             // Line width for the lines contour
-    fontsize_labels: f64,        // Font size for labels
-    with_selected: bool,         // Draw a line contour with a selected level
-    selected_level: f64,         // Selected level (e.g., 0.0)
-    selected_line_color: String, // Color to mark the selected level
-    selected_line_style: String, // Line style for the selected level
-    selected_line_width: f64,    // Line width for the selected level
-    extra_filled: String,        // Extra commands (comma separated) for the filled contour
-    extra_line: String,          // Extra commands (comma separated) for the line contour
-    buffer: String,              // buffer
+    colors: Vec<String>,            // Colors to be used instead of colormap
+    levels: Vec<f64>,               // Pre-defined levels
+    colormap_name: String,          // Colormap name
+    no_lines: bool,                 // Skip drawing a lines contour
+    no_labels: bool,                // Skip adding labels to the lines contour
+    no_inline_labels: bool,         // Do not draw labels inline
+    no_colorbar: bool,              // Skip drawing a colorbar
+    colorbar_label: String,         // Colorbar label
+    colorbar_centered: bool,        // Places the colorbar labels at the center of bands
+    number_format_cb: String,       // Number format for the labels in lines contour
+    line_color: String,             // Line color for the lines contour
+    line_style: String,             // Line style for the lines contour
+    line_width: f64,                // Line width for the lines contour
+    fontsize_labels: f64,           // Font size for labels
+    label_positions: Vec<[f64; 2]>, // Positions of the labels (manual placement)
+    with_selected: bool,            // Draw a line contour with a selected level
+    selected_level: f64,            // Selected level (e.g., 0.0)
+    selected_line_color: String,    // Color to mark the selected level
+    selected_line_style: String,    // Line style for the selected level
+    selected_line_width: f64,       // Line width for the selected level
+    extra_filled: String,           // Extra commands (comma separated) for the filled contour
+    extra_line: String,             // Extra commands (comma separated) for the line contour
+    array_format: ArrayFormat,      // Format of the numbers in the generated arrays
+    buffer: String,                 // buffer
 }
 
 impl Contour {
@@ -81,11 +87,13 @@ impl Contour {
             no_inline_labels: false,
             no_colorbar: false,
             colorbar_label: String::new(),
+            colorbar_centered: false,
             number_format_cb: String::new(),
             line_color: "black".to_string(),
             line_style: String::new(),
             line_width: 0.0,
             fontsize_labels: 0.0,
+            label_positions: Vec::new(),
             with_selected: false,
             selected_level: 0.0,
             selected_line_color: "yellow".to_string(),
@@ -93,6 +101,7 @@ impl Contour {
             selected_line_width: 2.0,
             extra_filled: String::new(),
             extra_line: String::new(),
+            array_format: ArrayFormat::new(),
             buffer: String::new(),
         }
     }
@@ -121,16 +130,16 @@ impl Contour {
     pub fn draw<'a, T, U>(&mut self, x: &'a T, y: &'a T, z: &'a T)
     where
         T: AsMatrix<'a, U>,
-        U: 'a + std::fmt::Display,
+        U: 'a + std::fmt::Display + AsF64,
     {
-        matrix_to_array(&mut self.buffer, "x", x);
-        matrix_to_array(&mut self.buffer, "y", y);
-        matrix_to_array(&mut self.buffer, "z", z);
+        matrix_to_array(&mut self.buffer, &self.array_format, "x", x);
+        matrix_to_array(&mut self.buffer, &self.array_format, "y", y);
+        matrix_to_array(&mut self.buffer, &self.array_format, "z", z);
         if self.colors.len() > 0 {
-            generate_list_quoted(&mut self.buffer, "colors", &self.colors);
+            generate_list_quoted(&mut self.buffer, &self.array_format, "colors", &self.colors);
         }
         if self.levels.len() > 0 {
-            vector_to_array(&mut self.buffer, "levels", &self.levels);
+            vector_to_array(&mut self.buffer, &self.array_format, "levels", &self.levels);
         }
         let opt = self.options_filled();
         write!(&mut self.buffer, "cf=plt.contourf(x,y,z{})\n", &opt).unwrap();
@@ -148,6 +157,21 @@ impl Contour {
             if self.colorbar_label != "" {
                 write!(&mut self.buffer, "cb.ax.set_ylabel(r'{}')\n", self.colorbar_label).unwrap();
             }
+            if self.colorbar_centered {
+                let fmt = if self.number_format_cb != "" {
+                    self.number_format_cb.as_str()
+                } else {
+                    "%g"
+                };
+                write!(
+                    &mut self.buffer,
+                    "lv=cf.levels\n\
+                     cb.set_ticks(0.5*(lv[:-1]+lv[1:]))\n\
+                     cb.set_ticklabels([('{}'%a)+'-'+('{}'%b) for a,b in zip(lv[:-1],lv[1:])])\n",
+                    fmt, fmt
+                )
+                .unwrap();
+            }
         }
         if self.with_selected {
             let opt_selected = self.options_selected();
@@ -155,6 +179,26 @@ impl Contour {
         }
     }
 
+    /// Draws a fancy contour with `n` logarithmically spaced levels between the min and max of the data
+    ///
+    /// The levels are computed from `z` and replace the pre-defined levels (see [Contour::set_levels()]).
+    /// See [Contour::draw()] for the other options.
+    ///
+    /// # Notes
+    ///
+    /// * Returns an error if `n = 0`, if a z value is zero or negative, or if there are no finite z values.
+    /// * Non-finite z values (e.g., NaN for masked data) are ignored when computing the levels.
+    /// * The type `U` of the input matrices must be a number.
+    pub fn draw_with_log_levels<'a, T, U>(&mut self, x: &'a T, y: &'a T, z: &'a T, n: usize) -> Result<(), StrError>
+    where
+        T: AsMatrix<'a, U>,
+        U: 'a + std::fmt::Display + AsF64,
+    {
+        self.levels = calc_log_levels(z, n)?;
+        self.draw(x, y, z);
+        Ok(())
+    }
+
     /// Sets the colors to be used instead of a pre-defined colormap
     ///
     /// Will use `colormap_index` instead if its empty.
@@ -219,12 +263,30 @@ impl Contour {
         self
     }
 
+    /// Sets the positions (x,y) of the labels of the lines contour (manual placement)
+    ///
+    /// Each label is placed on the contour line nearest to the given position.
+    /// An empty slice restores the automatic placement.
+    pub fn set_label_positions(&mut self, positions: &[[f64; 2]]) -> &mut Self {
+        self.label_positions = positions.to_vec();
+        self
+    }
+
     /// Sets option to skip drawing a colorbar
     pub fn set_no_colorbar(&mut self, flag: bool) -> &mut Self {
         self.no_colorbar = flag;
         self
     }
 
+    /// Sets option to place the colorbar labels at the center of the filled bands
+    ///
+    /// Each label then shows the range of the band (e.g., `0.25-0.5`). Otherwise (default),
+    /// the labels are placed at the boundaries of bands, i.e., at the levels.
+    pub fn set_colorbar_centered_labels(&mut self, flag: bool) -> &mut Self {
+        self.colorbar_centered = flag;
+        self
+    }
+
     /// Sets the colorbar label
     pub fn set_colorbar_label(&mut self, label: &str) -> &mut Self {
         self.colorbar_label = String::from(label);
@@ -324,6 +386,12 @@ impl Contour {
         self
     }
 
+    /// Sets the format of the numbers in the generated lists and arrays
+    pub fn set_array_format(&mut self, format: &ArrayFormat) -> &mut Self {
+        self.array_format = format.clone();
+        self
+    }
+
     /// Returns options for filled contour
     fn options_filled(&self) -> String {
         let mut opt = String::new();
@@ -375,6 +443,13 @@ impl Contour {
         if self.fontsize_labels > 0.0 {
             write!(&mut opt, ",fontsize={}", self.fontsize_labels).unwrap();
         }
+        if self.label_positions.len() > 0 {
+            write!(&mut opt, ",manual=[").unwrap();
+            for p in &self.label_positions {
+                write!(&mut opt, "({},{}),", p[0], p[1]).unwrap();
+            }
+            write!(&mut opt, "]").unwrap();
+        }
         opt
     }
 
@@ -384,6 +459,9 @@ impl Contour {
         if self.number_format_cb != "" {
             write!(&mut opt, ",format='{}'", self.number_format_cb).unwrap();
         }
+        if self.levels.len() > 0 && !self.colorbar_centered {
+            write!(&mut opt, ",ticks=levels").unwrap();
+        }
         opt
     }
 
@@ -413,11 +491,48 @@ impl GraphMaker for Contour {
     }
 }
 
+/// Calculates n log-spaced levels between the min and max of the finite z values (which must be positive)
+fn calc_log_levels<'a, T, U>(z: &'a T, n: usize) -> Result<Vec<f64>, StrError>
+where
+    T: AsMatrix<'a, U>,
+    U: 'a + std::fmt::Display + AsF64,
+{
+    if n == 0 {
+        return Err("the number of log levels must be at least 1");
+    }
+    let mut min = f64::MAX;
+    let mut max = f64::MIN;
+    let (m, n_col) = z.size();
+    for i in 0..m {
+        for j in 0..n_col {
+            let v = to_f64(z.at(i, j));
+            if !v.is_finite() {
+                continue;
+            }
+            if v <= 0.0 {
+                return Err("z values must be positive to compute log levels");
+            }
+            min = f64::min(min, v);
+            max = f64::max(max, v);
+        }
+    }
+    if min > max {
+        return Err("z must have at least one finite value to compute log levels");
+    }
+    if min == max || n == 1 {
+        return Ok(vec![min]);
+    }
+    Ok(linspace(f64::log10(min), f64::log10(max), n)
+        .iter()
+        .map(|e| f64::powf(10.0, *e))
+        .collect())
+}
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
 #[cfg(test)]
 mod tests {
-    use super::Contour;
+    use super::{calc_log_levels, Contour};
     use crate::GraphMaker;
 
     #[test]
@@ -431,6 +546,7 @@ mod tests {
         assert_eq!(contour.no_inline_labels, false);
         assert_eq!(contour.no_colorbar, false);
         assert_eq!(contour.colorbar_label.len(), 0);
+        assert_eq!(contour.colorbar_centered, false);
         assert_eq!(contour.number_format_cb.len(), 0);
         assert_eq!(contour.line_color, "black".to_string());
         assert_eq!(contour.line_style.len(), 0);
@@ -500,6 +616,11 @@ mod tests {
             ",inline=False\
              ,fontsize=5"
         );
+        contour.set_label_positions(&[[1.0, 2.5], [-0.5, 0.0]]);
+        let opt = contour.options_label();
+        assert_eq!(opt, ",inline=False,fontsize=5,manual=[(1,2.5),(-0.5,0),]");
+        contour.set_label_positions(&[]);
+        assert!(!contour.options_label().contains("manual"));
     }
 
     #[test]
@@ -508,6 +629,12 @@ mod tests {
         contour.set_number_format_cb("%.4f");
         let opt = contour.options_colorbar();
         assert_eq!(opt, ",format='%.4f'");
+        contour.set_levels(&[0.0, 1.0]);
+        let opt = contour.options_colorbar();
+        assert_eq!(opt, ",format='%.4f',ticks=levels");
+        contour.set_colorbar_centered_labels(true);
+        let opt = contour.options_colorbar();
+        assert_eq!(opt, ",format='%.4f'");
     }
 
     #[test]
@@ -548,11 +675,78 @@ mod tests {
                        cf=plt.contourf(x,y,z,colors=colors,levels=levels)\n\
                        cl=plt.contour(x,y,z,colors=['black'],levels=levels)\n\
                        plt.clabel(cl,inline=True)\n\
-                       cb=plt.colorbar(cf)\n\
+                       cb=plt.colorbar(cf,ticks=levels)\n\
                        cb.ax.set_ylabel(r'temperature')\n\
                        plt.contour(x,y,z,colors=['yellow'],levels=[0],linestyles=['-'],linewidths=[2])\n";
         assert_eq!(contour.buffer, b);
         contour.clear_buffer();
         assert_eq!(contour.buffer, "");
     }
+
+    #[test]
+    fn calc_log_levels_works() {
+        let z = vec![vec![1.0, f64::NAN, 10.0], vec![10.0, 50.0, 100.0]];
+        assert_eq!(calc_log_levels(&z, 3).unwrap(), &[1.0, 10.0, 100.0]);
+        assert_eq!(calc_log_levels(&z, 1).unwrap(), &[1.0]);
+        let z = vec![vec![2.0, 2.0]];
+        assert_eq!(calc_log_levels(&z, 3).unwrap(), &[2.0]);
+        assert_eq!(
+            calc_log_levels(&z, 0).err(),
+            Some("the number of log levels must be at least 1")
+        );
+        let z = vec![vec![-1.0, 0.0, 1.0]];
+        assert_eq!(
+            calc_log_levels(&z, 3).err(),
+            Some("z values must be positive to compute log levels")
+        );
+        let z = vec![vec![1.0, 0.0]];
+        assert_eq!(
+            calc_log_levels(&z, 3).err(),
+            Some("z values must be positive to compute log levels")
+        );
+        let z = vec![vec![f64::NAN]];
+        assert_eq!(
+            calc_log_levels(&z, 3).err(),
+            Some("z must have at least one finite value to compute log levels")
+        );
+    }
+
+    #[test]
+    fn draw_with_log_levels_works() {
+        let mut contour = Contour::new();
+        contour.set_no_lines(true).set_no_colorbar(true);
+        let x = vec![vec![0.0, 1.0]];
+        let y = vec![vec![0.0, 0.0]];
+        let z = vec![vec![1.0, 1000.0]];
+        contour.draw_with_log_levels(&x, &y, &z, 2).unwrap();
+        let b: &str = "x=np.array([[0,1,],],dtype=float)\n\
+                       y=np.array([[0,0,],],dtype=float)\n\
+                       z=np.array([[1,1000,],],dtype=float)\n\
+                       levels=np.array([1,1000,],dtype=float)\n\
+                       cf=plt.contourf(x,y,z,cmap=plt.get_cmap('bwr'),levels=levels)\n";
+        assert_eq!(contour.buffer, b);
+        contour.clear_buffer();
+        let z = vec![vec![0.0, 1000.0]];
+        assert_eq!(
+            contour.draw_with_log_levels(&x, &y, &z, 2).err(),
+            Some("z values must be positive to compute log levels")
+        );
+        assert_eq!(contour.buffer, "");
+    }
+
+    #[test]
+    fn draw_with_centered_labels_works() {
+        let mut contour = Contour::new();
+        contour.set_no_lines(true).set_colorbar_centered_labels(true);
+        let x = vec![vec![0.0, 1.0]];
+        let y = vec![vec![0.0, 0.0]];
+        let z = vec![vec![0.0, 1.0]];
+        contour.draw(&x, &y, &z);
+        assert!(contour.buffer.ends_with(
+            "cb=plt.colorbar(cf)\n\
+             lv=cf.levels\n\
+             cb.set_ticks(0.5*(lv[:-1]+lv[1:]))\n\
+             cb.set_ticklabels([('%g'%a)+'-'+('%g'%b) for a,b in zip(lv[:-1],lv[1:])])\n"
+        ));
+    }
 }
@@ -1,20 +1,143 @@
-use super::{AsMatrix, AsVector};
+use super::{ArrayFormat, AsMatrix, AsVector, StrError};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
 use std::fmt::Write;
+use std::hash::{Hash, Hasher};
+
+/// Estimated number of digits after the decimal point (when the precision is not set) to reserve capacity
+const ESTIMATED_DIGITS: usize = 8;
+
+/// Minimum length of constant or evenly spaced vectors written in compact form instead of listing all entries
+const MIN_LEN_COMPACT: usize = 16;
+
+/// Relative tolerance to detect evenly spaced vectors
+const TOL_EVENLY_SPACED: f64 = 1e-12;
+
+/// Number of entries formatted in memory before being written by `write_vector`
+const STREAM_CHUNK: usize = 4096;
+
+/// Removes the comma at the end of the buffer if the trailing comma option is disabled
+fn trim_trailing_comma(buf: &mut String, fmt: &ArrayFormat) {
+    if !fmt.trailing_comma() && buf.ends_with(',') {
+        buf.pop();
+    }
+}
+
+mod sealed {
+    /// Prevents the implementation of AsF64 outside this crate
+    pub trait Sealed {}
+}
+
+/// Converts the primitive number types to f64 (without formatting and parsing)
+///
+/// This trait is implemented for all primitive integer and float types (and references to them)
+/// and cannot be implemented outside this crate. It is the bound of the values of the drawn arrays.
+///
+/// # Examples
+///
+/// ```
+/// use plotpy::AsF64;
+///
+/// assert_eq!(3_usize.as_f64(), 3.0);
+/// assert_eq!((-2_i64).as_f64(), -2.0);
+/// ```
+pub trait AsF64: sealed::Sealed {
+    /// Returns the value as f64 (possibly with loss of precision, as with `as`)
+    fn as_f64(&self) -> f64;
+}
+
+macro_rules! impl_as_f64 {
+    ($($t:ty),*) => {
+        $(impl sealed::Sealed for $t {}
+        impl AsF64 for $t {
+            fn as_f64(&self) -> f64 {
+                *self as f64
+            }
+        })*
+    };
+}
+
+impl_as_f64!(f64, f32, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl<T: AsF64 + ?Sized> sealed::Sealed for &T {}
+
+impl<T: AsF64 + ?Sized> AsF64 for &T {
+    fn as_f64(&self) -> f64 {
+        (**self).as_f64()
+    }
+}
+
+/// Converts a number to f64
+pub(crate) fn to_f64<T>(val: T) -> f64
+where
+    T: AsF64,
+{
+    val.as_f64()
+}
+
+/// Writes a number followed by a comma, using NumPy constants for NaN and infinity
+fn write_number<T>(buf: &mut String, fmt: &ArrayFormat, val: T)
+where
+    T: std::fmt::Display,
+{
+    let start = buf.len();
+    match fmt.precision() {
+        Some(digits) => write!(buf, "{:.*}", digits, val).unwrap(),
+        None => write!(buf, "{}", val).unwrap(),
+    };
+    let constant = match &buf[start..] {
+        "NaN" => Some("np.nan"),
+        "inf" => Some("np.inf"),
+        "-inf" => Some("-np.inf"),
+        _ => None,
+    };
+    if let Some(c) = constant {
+        buf.truncate(start);
+        buf.push_str(c);
+    }
+    buf.push(',');
+}
+
+/// Reserves capacity in the buffer for the given number of numbers (estimated size)
+fn reserve_numbers(buf: &mut String, fmt: &ArrayFormat, count: usize) {
+    let digits = fmt.precision().unwrap_or(ESTIMATED_DIGITS);
+    buf.reserve(count * (digits + 8));
+}
 
 /// Generates a Python list
-pub(crate) fn generate_list<T>(buf: &mut String, name: &str, data: &[T])
+pub(crate) fn generate_list<T>(buf: &mut String, fmt: &ArrayFormat, name: &str, data: &[T])
 where
     T: std::fmt::Display,
 {
+    reserve_numbers(buf, fmt, data.len());
     write!(buf, "{}=[", name).unwrap();
     for val in data.into_iter() {
-        write!(buf, "{},", val).unwrap();
+        write_number(buf, fmt, val);
     }
+    trim_trailing_comma(buf, fmt);
     write!(buf, "]\n").unwrap();
 }
 
+/// Returns a Python list of numbers (without a variable name), e.g., to be used as a keyword argument
+///
+/// The list has no trailing comma; e.g., `[1,2,3]`.
+pub(crate) fn array2num<T>(fmt: &ArrayFormat, values: &[T]) -> String
+where
+    T: std::fmt::Display,
+{
+    let mut buf = String::from("[");
+    for val in values.into_iter() {
+        write_number(&mut buf, fmt, val);
+    }
+    if buf.ends_with(',') {
+        buf.pop();
+    }
+    buf.push(']');
+    buf
+}
+
 /// Generates a Python list with quoted entries
-pub(crate) fn generate_list_quoted<T>(buf: &mut String, name: &str, data: &[T])
+pub(crate) fn generate_list_quoted<T>(buf: &mut String, fmt: &ArrayFormat, name: &str, data: &[T])
 where
     T: std::fmt::Display,
 {
@@ -22,54 +145,239 @@ where
     for val in data.into_iter() {
         write!(buf, "'{}',", val).unwrap();
     }
+    trim_trailing_comma(buf, fmt);
+    write!(buf, "]\n").unwrap();
+}
+
+/// Generates a Python list with entries that are already Python literals (e.g., colors)
+pub(crate) fn generate_list_literal(buf: &mut String, fmt: &ArrayFormat, name: &str, data: &[String]) {
+    write!(buf, "{}=[", name).unwrap();
+    for val in data.into_iter() {
+        write!(buf, "{},", val).unwrap();
+    }
+    trim_trailing_comma(buf, fmt);
     write!(buf, "]\n").unwrap();
 }
 
 /// Converts vector to a 1D NumPy array
-pub(crate) fn vector_to_array<'a, T, U>(buf: &mut String, name: &str, vector: &'a T)
+///
+/// Long constant vectors are written as `np.full(n,value)` and long evenly spaced vectors are
+/// written as `np.linspace(start,stop,n)` to reduce the size of the script.
+pub(crate) fn vector_to_array<'a, T, U>(buf: &mut String, fmt: &ArrayFormat, name: &str, vector: &'a T)
 where
     T: AsVector<'a, U>,
-    U: 'a + std::fmt::Display,
+    U: 'a + std::fmt::Display + AsF64,
 {
-    write!(buf, "{}=np.array([", name).unwrap();
+    if let Some(compact) = compact_array(fmt, name, vector) {
+        buf.push_str(&compact);
+        return;
+    }
     let m = vector.vec_size();
+    reserve_numbers(buf, fmt, m);
+    write!(buf, "{}=np.array([", name).unwrap();
     for i in 0..m {
-        write!(buf, "{},", vector.vec_at(i)).unwrap();
+        write_number(buf, fmt, vector.vec_at(i));
     }
+    trim_trailing_comma(buf, fmt);
     write!(buf, "],dtype=float)\n").unwrap();
 }
 
+/// Holds the arrays already written to a buffer to avoid writing identical arrays again
+///
+/// Each distinct array is written once as `da{index}` and then assigned to the requested name.
+/// The arrays are found by a hash of their contents; collisions are resolved by comparing the values.
+/// The cache must be cleared whenever the buffer is cleared.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ArrayCache {
+    arrays: HashMap<u64, Vec<(Vec<u64>, String)>>, // hash => (bits of the values, name)
+    count: usize,                                  // number of written arrays
+}
+
+impl ArrayCache {
+    /// Writes `name=da{index}`, writing before the array `da{index}` if it has not been written yet
+    pub(crate) fn write<'a, T, U>(&mut self, buf: &mut String, fmt: &ArrayFormat, name: &str, vector: &'a T)
+    where
+        T: AsVector<'a, U>,
+        U: 'a + std::fmt::Display + AsF64,
+    {
+        let bits: Vec<u64> = (0..vector.vec_size())
+            .map(|i| to_f64(vector.vec_at(i)).to_bits())
+            .collect();
+        let mut hasher = DefaultHasher::new();
+        bits.hash(&mut hasher);
+        let entries = self.arrays.entry(hasher.finish()).or_default();
+        let uid = match entries.iter().find(|(values, _)| *values == bits) {
+            Some((_, uid)) => uid.clone(),
+            None => {
+                let uid = format!("da{}", self.count);
+                self.count += 1;
+                vector_to_array(buf, fmt, &uid, vector);
+                entries.push((bits, uid.clone()));
+                uid
+            }
+        };
+        write!(buf, "{}={}\n", name, uid).unwrap();
+    }
+
+    /// Forgets all written arrays
+    pub(crate) fn clear(&mut self) {
+        self.arrays.clear();
+        self.count = 0;
+    }
+}
+
+/// Converts three (parallel) vectors of coordinates to 1D NumPy arrays
+///
+/// The arrays are written in the order x, y, z using [vector_to_array].
+pub(crate) fn vectors3_to_arrays<'a, T, U>(
+    buf: &mut String,
+    fmt: &ArrayFormat,
+    name_x: &str,
+    name_y: &str,
+    name_z: &str,
+    x: &'a T,
+    y: &'a T,
+    z: &'a T,
+) where
+    T: AsVector<'a, U>,
+    U: 'a + std::fmt::Display + AsF64,
+{
+    vector_to_array(buf, fmt, name_x, x);
+    vector_to_array(buf, fmt, name_y, y);
+    vector_to_array(buf, fmt, name_z, z);
+}
+
+/// Writes a vector as a 1D NumPy array directly to a writer (e.g., a file)
+///
+/// The generated text is the same as the one generated by the graphs; however, the entries
+/// are formatted in small chunks; thus, very large arrays are never assembled in memory.
+///
+/// **Note:** The graphs do not use this function; they always write their arrays to their own
+/// buffers. This function is useful to write the data of custom Python scripts without storing
+/// the whole array as a string.
+///
+/// # Examples
+///
+/// ```
+/// use plotpy::{write_vector, ArrayFormat};
+///
+/// let mut out: Vec<u8> = Vec::new();
+/// write_vector(&mut out, &ArrayFormat::new(), "x", &[1.0, 2.5]).unwrap();
+/// assert_eq!(String::from_utf8(out).unwrap(), "x=np.array([1,2.5,],dtype=float)\n");
+/// ```
+pub fn write_vector<'a, W, T, U>(out: &mut W, fmt: &ArrayFormat, name: &str, vector: &'a T) -> Result<(), StrError>
+where
+    W: std::io::Write,
+    T: AsVector<'a, U>,
+    U: 'a + std::fmt::Display + AsF64,
+{
+    if let Some(compact) = compact_array(fmt, name, vector) {
+        return out.write_all(compact.as_bytes()).map_err(|_| "cannot write array");
+    }
+    let m = vector.vec_size();
+    let mut chunk = String::new();
+    reserve_numbers(&mut chunk, fmt, usize::min(m, STREAM_CHUNK));
+    write!(&mut chunk, "{}=np.array([", name).unwrap();
+    for i in 0..m {
+        write_number(&mut chunk, fmt, vector.vec_at(i));
+        if (i + 1) % STREAM_CHUNK == 0 && i + 1 < m {
+            out.write_all(chunk.as_bytes()).map_err(|_| "cannot write array")?;
+            chunk.clear();
+        }
+    }
+    trim_trailing_comma(&mut chunk, fmt);
+    write!(&mut chunk, "],dtype=float)\n").unwrap();
+    out.write_all(chunk.as_bytes()).map_err(|_| "cannot write array")
+}
+
+/// Returns the compact form of long constant (np.full) or evenly spaced (np.linspace) vectors
+fn compact_array<'a, T, U>(fmt: &ArrayFormat, name: &str, vector: &'a T) -> Option<String>
+where
+    T: AsVector<'a, U>,
+    U: 'a + std::fmt::Display + AsF64,
+{
+    let m = vector.vec_size();
+    if m < MIN_LEN_COMPACT {
+        return None;
+    }
+    let mut first = String::new();
+    let mut current = String::new();
+    write_number(&mut first, fmt, vector.vec_at(0));
+    let constant = (1..m).all(|i| {
+        current.clear();
+        write_number(&mut current, fmt, vector.vec_at(i));
+        current == first
+    });
+    let mut buf = String::new();
+    if constant {
+        first.pop(); // comma
+        write!(&mut buf, "{}=np.full({},{},dtype=float)\n", name, m, first).unwrap();
+        return Some(buf);
+    }
+    if evenly_spaced(vector) {
+        write!(&mut buf, "{}=np.linspace(", name).unwrap();
+        write_number(&mut buf, fmt, vector.vec_at(0));
+        write_number(&mut buf, fmt, vector.vec_at(m - 1));
+        write!(&mut buf, "{})\n", m).unwrap();
+        return Some(buf);
+    }
+    None
+}
+
+/// Returns whether the (non-constant) vector is an arithmetic sequence (within a tolerance)
+fn evenly_spaced<'a, T, U>(vector: &'a T) -> bool
+where
+    T: AsVector<'a, U>,
+    U: 'a + std::fmt::Display + AsF64,
+{
+    let m = vector.vec_size();
+    let first = to_f64(vector.vec_at(0));
+    let last = to_f64(vector.vec_at(m - 1));
+    if !first.is_finite() || !last.is_finite() || first == last {
+        return false;
+    }
+    let step = (last - first) / ((m - 1) as f64);
+    let tol = TOL_EVENLY_SPACED * f64::max(first.abs(), last.abs());
+    (1..m - 1).all(|i| f64::abs(to_f64(vector.vec_at(i)) - (first + (i as f64) * step)) <= tol)
+}
+
 /// Generates a nested Python list
-pub(crate) fn generate_nested_list<T>(buf: &mut String, name: &str, data: &Vec<Vec<T>>)
+pub(crate) fn generate_nested_list<T>(buf: &mut String, fmt: &ArrayFormat, name: &str, data: &Vec<Vec<T>>)
 where
     T: std::fmt::Display,
 {
+    reserve_numbers(buf, fmt, data.iter().map(|row| row.len()).sum());
     write!(buf, "{}=[", name).unwrap();
     for row in data.into_iter() {
         write!(buf, "[").unwrap();
         for val in row.into_iter() {
-            write!(buf, "{},", val).unwrap();
+            write_number(buf, fmt, val);
         }
+        trim_trailing_comma(buf, fmt);
         write!(buf, "],").unwrap();
     }
+    trim_trailing_comma(buf, fmt);
     write!(buf, "]\n").unwrap();
 }
 
 /// Converts a matrix to a 2D NumPy array
-pub(crate) fn matrix_to_array<'a, T, U>(buf: &mut String, name: &str, matrix: &'a T)
+pub(crate) fn matrix_to_array<'a, T, U>(buf: &mut String, fmt: &ArrayFormat, name: &str, matrix: &'a T)
 where
     T: AsMatrix<'a, U>,
     U: 'a + std::fmt::Display,
 {
-    write!(buf, "{}=np.array([", name).unwrap();
     let (m, n) = matrix.size();
+    reserve_numbers(buf, fmt, m * n);
+    write!(buf, "{}=np.array([", name).unwrap();
     for i in 0..m {
         write!(buf, "[").unwrap();
         for j in 0..n {
-            write!(buf, "{},", matrix.at(i, j)).unwrap();
+            write_number(buf, fmt, matrix.at(i, j));
         }
+        trim_trailing_comma(buf, fmt);
         write!(buf, "],").unwrap();
     }
+    trim_trailing_comma(buf, fmt);
     write!(buf, "],dtype=float)\n").unwrap();
 }
 
@@ -77,17 +385,37 @@ where
 
 #[cfg(test)]
 mod tests {
-    use super::{generate_list, generate_list_quoted, generate_nested_list, matrix_to_array, vector_to_array};
+    use super::{
+        array2num, generate_list, generate_list_literal, generate_list_quoted, generate_nested_list, matrix_to_array,
+        to_f64, vector_to_array, vectors3_to_arrays, write_vector, ArrayCache, STREAM_CHUNK,
+    };
+    use crate::ArrayFormat;
+    use std::collections::hash_map::DefaultHasher;
+    use std::fs::{self, File};
+    use std::hash::{Hash, Hasher};
+    use std::io::{BufWriter, Write};
+
+    #[test]
+    fn array2num_works() {
+        let fmt = ArrayFormat::new();
+        let empty: &[f64] = &[];
+        assert_eq!(array2num(&fmt, &[1.0, 2.0]), "[1,2]");
+        assert_eq!(array2num(&fmt, &[0.5, -3.25]), "[0.5,-3.25]");
+        assert_eq!(array2num(&fmt, &[1, 2, 3]), "[1,2,3]");
+        assert_eq!(array2num::<usize>(&fmt, &[10, 20]), "[10,20]");
+        assert_eq!(array2num(&fmt, empty), "[]");
+    }
 
     #[test]
     fn generate_list_works() {
+        let fmt = ArrayFormat::new();
         let mut buf = String::new();
         let x: Vec<f64> = vec![0.1, 0.2, 0.3];
         let y: [f64; 3] = [1.0, 2.0, 3.0];
         let z: &[f64] = &[10.0, 20.0, 30.0];
-        generate_list(&mut buf, "x", &x);
-        generate_list(&mut buf, "y", &y);
-        generate_list(&mut buf, "z", z);
+        generate_list(&mut buf, &fmt, "x", &x);
+        generate_list(&mut buf, &fmt, "y", &y);
+        generate_list(&mut buf, &fmt, "z", z);
         assert_eq!(
             buf,
             "x=[0.1,0.2,0.3,]\n\
@@ -98,13 +426,14 @@ mod tests {
 
     #[test]
     fn generate_list_quoted_works() {
+        let fmt = ArrayFormat::new();
         let mut buf = String::new();
         let x: Vec<&str> = vec!["red", "green", "blue"];
         let y: [String; 3] = ["cyan".to_string(), "magenta".to_string(), "white".to_string()];
         let z: &[&str] = &["#f00", "#0f0", "#00f"];
-        generate_list_quoted(&mut buf, "x", &x);
-        generate_list_quoted(&mut buf, "y", &y);
-        generate_list_quoted(&mut buf, "z", z);
+        generate_list_quoted(&mut buf, &fmt, "x", &x);
+        generate_list_quoted(&mut buf, &fmt, "y", &y);
+        generate_list_quoted(&mut buf, &fmt, "z", z);
         assert_eq!(
             buf,
             "x=['red','green','blue',]\n\
@@ -113,15 +442,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn generate_list_literal_works() {
+        let mut fmt = ArrayFormat::new();
+        let mut buf = String::new();
+        let x: Vec<String> = vec!["'red'".to_string(), "(0,0.5,1)".to_string()];
+        generate_list_literal(&mut buf, &fmt, "x", &x);
+        fmt.set_trailing_comma(false);
+        generate_list_literal(&mut buf, &fmt, "x", &x);
+        assert_eq!(buf, "x=['red',(0,0.5,1),]\nx=['red',(0,0.5,1)]\n");
+    }
+
     #[test]
     fn vector_to_array_works() {
+        let fmt = ArrayFormat::new();
         let mut buf = String::new();
         let x: Vec<f64> = vec![0.1, 0.2, 0.3];
         let y: [f64; 3] = [1.0, 2.0, 3.0];
         let z: &[f64] = &[10.0, 20.0, 30.0];
-        vector_to_array(&mut buf, "x", &x);
-        vector_to_array(&mut buf, "y", &y);
-        vector_to_array(&mut buf, "z", &z);
+        vector_to_array(&mut buf, &fmt, "x", &x);
+        vector_to_array(&mut buf, &fmt, "y", &y);
+        vector_to_array(&mut buf, &fmt, "z", &z);
         assert_eq!(
             buf,
             "x=np.array([0.1,0.2,0.3,],dtype=float)\n\
@@ -130,23 +471,142 @@ mod tests {
         );
     }
 
+    #[test]
+    fn array_cache_works() {
+        let fmt = ArrayFormat::new();
+        let mut buf = String::new();
+        let mut cache = ArrayCache::default();
+        let x = vec![1.0, 2.0, 3.0];
+        let y: &[i32] = &[1, 2, 3];
+        cache.write(&mut buf, &fmt, "x", &x);
+        cache.write(&mut buf, &fmt, "y", &vec![4.0, 5.0]);
+        cache.write(&mut buf, &fmt, "x", &x);
+        cache.write(&mut buf, &fmt, "y", &y);
+        assert_eq!(
+            buf,
+            "da0=np.array([1,2,3,],dtype=float)\n\
+             x=da0\n\
+             da1=np.array([4,5,],dtype=float)\n\
+             y=da1\n\
+             x=da0\n\
+             y=da0\n"
+        );
+
+        // collisions (same hash) are resolved by comparing the values
+        let mut hasher = DefaultHasher::new();
+        vec![7.0_f64.to_bits()].hash(&mut hasher);
+        cache
+            .arrays
+            .insert(hasher.finish(), vec![(vec![8.0_f64.to_bits()], "fake".to_string())]);
+        buf.clear();
+        cache.write(&mut buf, &fmt, "z", &[7.0]);
+        assert_eq!(buf, "da2=np.array([7,],dtype=float)\nz=da2\n");
+
+        cache.clear();
+        buf.clear();
+        cache.write(&mut buf, &fmt, "x", &x);
+        assert_eq!(buf, "da0=np.array([1,2,3,],dtype=float)\nx=da0\n");
+    }
+
+    #[test]
+    fn vectors3_to_arrays_works() {
+        let fmt = ArrayFormat::new();
+        let mut buf = String::new();
+        let x = vec![0.1, 0.2];
+        let y = vec![1.0, 2.0];
+        let z = vec![10.0, 20.0];
+        vectors3_to_arrays(&mut buf, &fmt, "xx", "yy", "zz", &x, &y, &z);
+        assert_eq!(
+            buf,
+            "xx=np.array([0.1,0.2,],dtype=float)\n\
+             yy=np.array([1,2,],dtype=float)\n\
+             zz=np.array([10,20,],dtype=float)\n"
+        );
+        let mut other = String::new();
+        vector_to_array(&mut other, &fmt, "xx", &x);
+        vector_to_array(&mut other, &fmt, "yy", &y);
+        vector_to_array(&mut other, &fmt, "zz", &z);
+        assert_eq!(buf, other);
+    }
+
+    #[test]
+    fn vector_to_array_handles_constant_vectors() {
+        let fmt = ArrayFormat::new();
+        let mut buf = String::new();
+        let mut e = vec![1.0; 16];
+        e[15] = 2.0;
+        vector_to_array(&mut buf, &fmt, "a", &[0.0; 16]);
+        vector_to_array(&mut buf, &fmt, "b", &vec![f64::NAN; 20]);
+        vector_to_array(&mut buf, &fmt, "c", &vec![2; 17]);
+        vector_to_array(&mut buf, &fmt, "d", &[1.5; 5]);
+        vector_to_array(&mut buf, &fmt, "e", &e);
+        assert_eq!(
+            buf,
+            "a=np.full(16,0,dtype=float)\n\
+             b=np.full(20,np.nan,dtype=float)\n\
+             c=np.full(17,2,dtype=float)\n\
+             d=np.array([1.5,1.5,1.5,1.5,1.5,],dtype=float)\n\
+             e=np.array([1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,2,],dtype=float)\n"
+        );
+    }
+
+    #[test]
+    fn vector_to_array_handles_evenly_spaced_vectors() {
+        let fmt = ArrayFormat::new();
+        let mut buf = String::new();
+        let a: Vec<f64> = (0..17).map(|i| (i as f64) * 0.1).collect();
+        let b: Vec<i32> = (0..16).map(|i| 10 - 3 * i).collect();
+        let c: Vec<i32> = (0..16).map(|i| i * i).collect();
+        let mut d: Vec<f64> = (0..16).map(|i| i as f64).collect();
+        d[5] += 1e-6;
+        vector_to_array(&mut buf, &fmt, "a", &a);
+        vector_to_array(&mut buf, &fmt, "b", &b);
+        vector_to_array(&mut buf, &fmt, "c", &c);
+        vector_to_array(&mut buf, &fmt, "d", &d);
+        vector_to_array(&mut buf, &fmt, "e", &[0.0, 1.0, 2.0, 3.0]);
+        assert_eq!(
+            buf,
+            "a=np.linspace(0,1.6,17)\n\
+             b=np.linspace(10,-35,16)\n\
+             c=np.array([0,1,4,9,16,25,36,49,64,81,100,121,144,169,196,225,],dtype=float)\n\
+             d=np.array([0,1,2,3,4,5.000001,6,7,8,9,10,11,12,13,14,15,],dtype=float)\n\
+             e=np.array([0,1,2,3,],dtype=float)\n"
+        );
+    }
+
+    #[test]
+    fn vector_to_array_output_is_unchanged_for_large_arrays() {
+        let fmt = ArrayFormat::new();
+        let x: Vec<f64> = (0..10_000).map(|i| f64::sin(i as f64) * 1e3).collect();
+        let mut reference = String::from("x=np.array([");
+        for v in &x {
+            reference.push_str(&format!("{},", v));
+        }
+        reference.push_str("],dtype=float)\n");
+        let mut buf = String::new();
+        vector_to_array(&mut buf, &fmt, "x", &x);
+        assert_eq!(buf, reference);
+    }
+
     #[test]
     fn generate_nested_list_works() {
+        let fmt = ArrayFormat::new();
         let mut buf = String::new();
         let a = vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0], vec![6.0, 7.0, 8.0, 9.0]];
-        generate_nested_list(&mut buf, "a", &a);
+        generate_nested_list(&mut buf, &fmt, "a", &a);
         assert_eq!(buf, "a=[[1,2,3,],[4,5,],[6,7,8,9,],]\n");
     }
 
     #[test]
     fn matrix_to_array_works() {
+        let fmt = ArrayFormat::new();
         let mut buf = String::new();
         let a: Vec<Vec<f64>> = vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0], vec![7.0, 8.0, 9.0]];
         let b: [[f64; 3]; 3] = [[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]];
         let c: &[&[f64]] = &[&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0], &[7.0, 8.0, 9.0]];
-        matrix_to_array(&mut buf, "a", &a);
-        matrix_to_array(&mut buf, "b", &b);
-        matrix_to_array(&mut buf, "c", &c);
+        matrix_to_array(&mut buf, &fmt, "a", &a);
+        matrix_to_array(&mut buf, &fmt, "b", &b);
+        matrix_to_array(&mut buf, &fmt, "c", &c);
         assert_eq!(
             buf,
             "a=np.array([[1,2,3,],[4,5,6,],[7,8,9,],],dtype=float)\n\
@@ -154,4 +614,138 @@ mod tests {
              c=np.array([[1,2,3,],[4,5,6,],[7,8,9,],],dtype=float)\n"
         );
     }
+
+    #[test]
+    fn trailing_comma_can_be_omitted() {
+        let mut fmt = ArrayFormat::new();
+        fmt.set_trailing_comma(false);
+        let mut buf = String::new();
+        let x: Vec<f64> = vec![1.0, 2.0];
+        let e: Vec<f64> = Vec::new();
+        let a: Vec<Vec<f64>> = vec![vec![1.0, 2.0], vec![3.0, 4.0]];
+        generate_list(&mut buf, &fmt, "x", &x);
+        generate_list(&mut buf, &fmt, "e", &e);
+        generate_list_quoted(&mut buf, &fmt, "c", &["red", "blue"]);
+        vector_to_array(&mut buf, &fmt, "x", &x);
+        generate_nested_list(&mut buf, &fmt, "a", &a);
+        matrix_to_array(&mut buf, &fmt, "a", &a);
+        assert_eq!(
+            buf,
+            "x=[1,2]\n\
+             e=[]\n\
+             c=['red','blue']\n\
+             x=np.array([1,2],dtype=float)\n\
+             a=[[1,2],[3,4]]\n\
+             a=np.array([[1,2],[3,4]],dtype=float)\n"
+        );
+        let mut buf = String::new();
+        generate_list(&mut buf, &ArrayFormat::new(), "x", &x);
+        assert_eq!(buf, "x=[1,2,]\n");
+    }
+
+    #[test]
+    fn non_finite_values_use_numpy_constants() {
+        let fmt = ArrayFormat::new();
+        let mut buf = String::new();
+        let x: &[f64] = &[1.0, f64::NAN, f64::INFINITY, f64::NEG_INFINITY];
+        let a = vec![vec![f64::NAN, 2.0], vec![3.0, f64::INFINITY]];
+        generate_list(&mut buf, &fmt, "x", x);
+        vector_to_array(&mut buf, &fmt, "x", &x);
+        generate_nested_list(&mut buf, &fmt, "a", &a);
+        matrix_to_array(&mut buf, &fmt, "a", &a);
+        assert_eq!(
+            buf,
+            "x=[1,np.nan,np.inf,-np.inf,]\n\
+             x=np.array([1,np.nan,np.inf,-np.inf,],dtype=float)\n\
+             a=[[np.nan,2,],[3,np.inf,],]\n\
+             a=np.array([[np.nan,2,],[3,np.inf,],],dtype=float)\n"
+        );
+    }
+
+    #[test]
+    fn precision_can_be_set() {
+        let mut fmt = ArrayFormat::new();
+        let mut buf = String::new();
+        let x: &[f64] = &[1.0, 0.123456, f64::NAN];
+        let a = vec![vec![1.0, 2.5]];
+        let i: &[i32] = &[1, 2];
+        fmt.set_precision(Some(3));
+        vector_to_array(&mut buf, &fmt, "x", &x);
+        matrix_to_array(&mut buf, &fmt, "a", &a);
+        generate_list(&mut buf, &fmt, "i", i);
+        fmt.set_precision(Some(100));
+        generate_list(&mut buf, &fmt, "y", &[0.5]);
+        fmt.set_precision(None);
+        generate_list(&mut buf, &fmt, "z", &[0.5]);
+        assert_eq!(
+            buf,
+            "x=np.array([1.000,0.123,np.nan,],dtype=float)\n\
+             a=np.array([[1.000,2.500,],],dtype=float)\n\
+             i=[1,2,]\n\
+             y=[0.50000000000000000,]\n\
+             z=[0.5,]\n"
+        );
+    }
+
+    #[test]
+    fn integer_arrays_work() {
+        let fmt = ArrayFormat::new();
+        let mut buf = String::new();
+        let i: &[i64] = &[1, 2, 3];
+        let u: Vec<usize> = vec![4, 5];
+        let m: Vec<Vec<u64>> = vec![vec![1, 2], vec![3, 4]];
+        let f: &[f64] = &[1.0, 2.5];
+        vector_to_array(&mut buf, &fmt, "i", &i);
+        vector_to_array(&mut buf, &fmt, "u", &u);
+        matrix_to_array(&mut buf, &fmt, "m", &m);
+        vector_to_array(&mut buf, &fmt, "f", &f);
+        assert_eq!(
+            buf,
+            "i=np.array([1,2,3,],dtype=float)\n\
+             u=np.array([4,5,],dtype=float)\n\
+             m=np.array([[1,2,],[3,4,],],dtype=float)\n\
+             f=np.array([1,2.5,],dtype=float)\n"
+        );
+    }
+
+    #[test]
+    fn write_vector_works() {
+        let fmt = ArrayFormat::new();
+        // chunk boundaries: exact multiple plus one entry
+        let x: Vec<f64> = (0..(2 * STREAM_CHUNK + 1)).map(|i| f64::sin(i as f64)).collect();
+        let path = "/tmp/plotpy/unit_tests/write_vector_works.txt";
+        fs::create_dir_all("/tmp/plotpy/unit_tests").unwrap();
+        let mut out = BufWriter::new(File::create(path).unwrap());
+        write_vector(&mut out, &fmt, "x", &x).unwrap();
+        write_vector(&mut out, &fmt, "y", &[1.0, 2.5]).unwrap();
+        write_vector(&mut out, &fmt, "z", &vec![0.5; 20]).unwrap();
+        out.flush().unwrap();
+        let mut correct = String::new();
+        vector_to_array(&mut correct, &fmt, "x", &x);
+        vector_to_array(&mut correct, &fmt, "y", &[1.0, 2.5]);
+        vector_to_array(&mut correct, &fmt, "z", &vec![0.5; 20]);
+        assert_eq!(fs::read_to_string(path).unwrap(), correct);
+        assert!(correct.ends_with("y=np.array([1,2.5,],dtype=float)\nz=np.full(20,0.5,dtype=float)\n"));
+
+        let mut fmt = ArrayFormat::new();
+        fmt.set_trailing_comma(false);
+        let part = &x[0..STREAM_CHUNK];
+        let mut out: Vec<u8> = Vec::new();
+        write_vector(&mut out, &fmt, "x", &part).unwrap();
+        let mut correct = String::new();
+        vector_to_array(&mut correct, &fmt, "x", &part);
+        assert_eq!(String::from_utf8(out).unwrap(), correct);
+        assert!(correct.ends_with("],dtype=float)\n"));
+        assert!(!correct.ends_with(",],dtype=float)\n"));
+    }
+
+    #[test]
+    fn to_f64_works() {
+        assert_eq!(to_f64(123_usize), 123.0);
+        assert_eq!(to_f64(-5_i64), -5.0);
+        assert_eq!(to_f64(2.5_f32), 2.5);
+        assert_eq!(to_f64(f64::INFINITY), f64::INFINITY);
+        assert!(to_f64(f64::NAN).is_nan());
+        assert_eq!(to_f64(&7_u8), 7.0);
+    }
 }
@@ -1,6 +1,10 @@
-use super::{vector_to_array, AsVector, GraphMaker};
+use super::{
+    array2num, durations_to_seconds, extend_bounds, generate_list_quoted, option_to_nan, to_f64, vector_to_array,
+    vectors3_to_arrays, ArrayCache, ArrayFormat, AsF64, AsVector, Color, GraphMaker, StrError,
+};
 use crate::quote_marker;
 use std::fmt::Write;
+use std::time::Duration;
 
 /// Holds either the second point coordinates of a ray or the slope of the ray
 #[derive(Clone, Debug)]
@@ -18,6 +22,34 @@ pub enum RayEndpoint {
     Vertical,
 }
 
+/// Defines the order in which categories are assigned colors
+///
+/// See [Curve::draw_by_category]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CategoryOrder {
+    /// Categories are ordered by their first appearance in the data
+    FirstAppearance,
+
+    /// Categories are sorted by name
+    Sorted,
+}
+
+/// Defines how the sizes and colors of the scatter markers are given
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ScatterKind {
+    /// Same size and color for all markers
+    Plain,
+
+    /// One size for each marker (given by an array)
+    Sized,
+
+    /// One color for each marker (mapped from values by a colormap)
+    Colored,
+
+    /// One size and one color for each marker
+    SizedAndColored,
+}
+
 /// Generates a curve (aka line-plot) given two arrays (x,y)
 ///
 /// [See Matplotlib's documentation](https://matplotlib.org/stable/api/_as_gen/matplotlib.pyplot.plot.html)
@@ -157,21 +189,42 @@ pub enum RayEndpoint {
 ///
 /// ![integ_curve_3d.svg](https://raw.githubusercontent.com/cpmech/plotpy/main/figures/integ_curve_3d.svg)
 pub struct Curve {
-    label: String,             // Name of this curve in the legend
-    line_alpha: f64,           // Opacity of lines (0, 1]. A<1e-14 => A=1.0
-    line_color: String,        // Color of lines
-    line_style: String,        // Style of lines
-    line_width: f64,           // Width of lines
-    marker_color: String,      // Color of markers
-    marker_every: usize,       // Increment of data points to use when drawing markers
-    marker_void: bool,         // Draws a void marker (edge only)
-    marker_line_color: String, // Edge color of markers
-    marker_line_width: f64,    // Edge width of markers
-    marker_size: f64,          // Size of markers
-    marker_style: String,      // Style of markers, e.g., "`o`", "`+`"
-    stop_clip: bool,           // Stop clipping features within margins
-    extra: String,             // Extra commands (comma separated)
-    buffer: String,            // buffer
+    label: String,                                  // Name of this curve in the legend
+    line_alpha: f64,                                // Opacity of lines (0, 1]. A<1e-14 => A=1.0
+    line_color: String,                             // Color of lines (Python literal)
+    line_style: String,                             // Style of lines
+    line_width: f64,                                // Width of lines
+    markers_only: bool,                             // Draws markers without lines
+    marker_color: String,                           // Color of markers (Python literal)
+    marker_every: usize,                            // Increment of data points to use when drawing markers
+    marker_indices: Vec<usize>,                     // Indices of the data points to use when drawing markers
+    marker_void: bool,                              // Draws a void marker (edge only)
+    marker_line_color: String,                      // Edge color of markers (Python literal)
+    marker_line_width: f64,                         // Edge width of markers
+    marker_size: f64,                               // Size of markers
+    marker_style: String,                           // Style of markers, e.g., "`o`", "`+`"
+    stop_clip: bool,                                // Stop clipping features within margins
+    jitter_x: f64,                                  // Amount of random jitter added to x (0 means none)
+    jitter_y: f64,                                  // Amount of random jitter added to y (0 means none)
+    jitter_state: u64,                              // State of the random number generator of the jitter
+    gradient_fill: Option<(f64, f64)>,              // Alphas (top, bottom) of the gradient fill under the line
+    error_cap_size: f64,                            // Size of the caps of error bars
+    band_hatch: String,                             // Hatch pattern of bands (filled regions between two curves)
+    band_hatch_color: String,                       // Color of the hatch of bands
+    gap_marker: String,                             // Marker drawn at the boundaries of gaps (NaN values)
+    gap_marker_color: String,                       // Color of the markers at the boundaries of gaps
+    extra: String,                                  // Extra commands (comma separated)
+    colormap_name: String,                          // Colormap name for markers colored by values
+    with_colorbar: bool,                            // Draw a colorbar for markers colored by values
+    colorbar_label: String,                         // Colorbar label
+    size_legend_num: usize,                         // Number of entries in the legend of marker sizes
+    size_legend_title: String,                      // Title of the legend of marker sizes
+    category_order: CategoryOrder,                  // Order of the colors assigned to categories
+    bounds: Option<(f64, f64, f64, f64, f64, f64)>, // Bounds of the drawn data
+    points: Option<Vec<(f64, f64)>>,                // The (x,y) points drawn in 2D (if kept)
+    array_cache: Option<ArrayCache>,                // Arrays already written to the buffer (deduplication)
+    array_format: ArrayFormat,                      // Format of the numbers in the generated arrays
+    buffer: String,                                 // buffer
 }
 
 impl Curve {
@@ -183,15 +236,36 @@ impl Curve {
             line_color: String::new(),
             line_style: String::new(),
             line_width: 0.0,
+            markers_only: false,
             marker_color: String::new(),
             marker_every: 0,
+            marker_indices: Vec::new(),
             marker_void: false,
             marker_line_color: String::new(),
             marker_line_width: 0.0,
             marker_size: 0.0,
             marker_style: String::new(),
             stop_clip: false,
+            jitter_x: 0.0,
+            jitter_y: 0.0,
+            jitter_state: 0,
+            gradient_fill: None,
+            error_cap_size: 0.0,
+            band_hatch: String::new(),
+            band_hatch_color: String::new(),
+            gap_marker: String::new(),
+            gap_marker_color: String::new(),
             extra: String::new(),
+            colormap_name: String::new(),
+            with_colorbar: false,
+            colorbar_label: String::new(),
+            size_legend_num: 4,
+            size_legend_title: String::new(),
+            category_order: CategoryOrder::FirstAppearance,
+            bounds: None,
+            points: None,
+            array_cache: None,
+            array_format: ArrayFormat::new(),
             buffer: String::new(),
         }
     }
@@ -285,15 +359,115 @@ impl Curve {
     /// # Notes
     ///
     /// * The type `U` of the input array must be a number.
+    /// * The line is interrupted at NaN values (no segment is drawn across the gap). See [Curve::draw_with_gaps].
+    /// * The coordinates are perturbed if the jitter is set. See [Curve::set_jitter].
     pub fn draw<'a, T, U>(&mut self, x: &'a T, y: &'a T)
     where
         T: AsVector<'a, U>,
-        U: 'a + std::fmt::Display,
+        U: 'a + std::fmt::Display + AsF64,
+    {
+        if self.jitter_x != 0.0 || self.jitter_y != 0.0 {
+            let n = usize::min(x.vec_size(), y.vec_size());
+            let mut xx = vec![0.0; n];
+            let mut yy = vec![0.0; n];
+            for i in 0..n {
+                xx[i] = to_f64(x.vec_at(i)) + self.jitter_x * self.next_jitter();
+                yy[i] = to_f64(y.vec_at(i)) + self.jitter_y * self.next_jitter();
+            }
+            self.draw_xy(&xx, &yy);
+        } else {
+            self.draw_xy(x, y);
+        }
+    }
+
+    /// Draws curve (without jitter)
+    fn draw_xy<'a, T, U>(&mut self, x: &'a T, y: &'a T)
+    where
+        T: AsVector<'a, U>,
+        U: 'a + std::fmt::Display + AsF64,
     {
-        vector_to_array(&mut self.buffer, "x", x);
-        vector_to_array(&mut self.buffer, "y", y);
+        for i in 0..usize::min(x.vec_size(), y.vec_size()) {
+            let (xi, yi) = (to_f64(x.vec_at(i)), to_f64(y.vec_at(i)));
+            self.add_point(xi, yi);
+        }
+        match &mut self.array_cache {
+            Some(cache) => {
+                cache.write(&mut self.buffer, &self.array_format, "x", x);
+                cache.write(&mut self.buffer, &self.array_format, "y", y);
+            }
+            None => {
+                vector_to_array(&mut self.buffer, &self.array_format, "x", x);
+                vector_to_array(&mut self.buffer, &self.array_format, "y", y);
+            }
+        }
         let opt = self.options();
-        write!(&mut self.buffer, "plt.plot(x,y{})\n", &opt).unwrap();
+        match self.gradient_fill {
+            Some((top_alpha, bottom_alpha)) => {
+                write!(&mut self.buffer, "gl,=plt.plot(x,y{})\n", &opt).unwrap();
+                self.write_gradient_fill(top_alpha, bottom_alpha);
+            }
+            None => write!(&mut self.buffer, "plt.plot(x,y{})\n", &opt).unwrap(),
+        }
+        if self.gap_marker != "" {
+            let points: Vec<(f64, f64)> = (0..usize::min(x.vec_size(), y.vec_size()))
+                .map(|i| (to_f64(x.vec_at(i)), to_f64(y.vec_at(i))))
+                .collect();
+            let (gx, gy): (Vec<f64>, Vec<f64>) = gap_boundaries(&points).into_iter().unzip();
+            if gx.len() > 0 {
+                vector_to_array(&mut self.buffer, &self.array_format, "gx", &gx);
+                vector_to_array(&mut self.buffer, &self.array_format, "gy", &gy);
+                let mut opt = format!(",linestyle='none',marker={}", quote_marker(&self.gap_marker));
+                if self.gap_marker_color != "" {
+                    write!(&mut opt, ",color={}", self.gap_marker_color).unwrap();
+                }
+                write!(&mut self.buffer, "plt.plot(gx,gy{})\n", &opt).unwrap();
+            }
+        }
+    }
+
+    /// Draws curve named in the legend
+    ///
+    /// # Input
+    ///
+    /// * `name` - name of the series; it is stored as the label (see [Curve::set_label])
+    /// * `x` - abscissa values
+    /// * `y` - ordinate values
+    ///
+    /// # Notes
+    ///
+    /// * The label is kept for the next drawings; thus, call `set_label("")` to clear it.
+    /// * The type `U` of the input array must be a number.
+    pub fn draw_named<'a, T, U>(&mut self, name: &str, x: &'a T, y: &'a T)
+    where
+        T: AsVector<'a, U>,
+        U: 'a + std::fmt::Display + AsF64,
+    {
+        self.set_label(name);
+        self.draw(x, y);
+    }
+
+    /// Draws curve with gaps (broken line) where the ordinate values are missing
+    ///
+    /// # Input
+    ///
+    /// * `x` - abscissa values
+    /// * `y` - ordinate values; `None` is written as `np.nan`, thus the connecting line is interrupted
+    ///
+    /// See also [crate::option_to_nan] and [Curve::set_gap_markers]
+    pub fn draw_with_gaps(&mut self, x: &[f64], y: &[Option<f64>]) {
+        let yy = option_to_nan(y);
+        self.draw(&x, &yy.as_slice());
+    }
+
+    /// Draws curve of a time series with the abscissa values given as durations
+    ///
+    /// # Input
+    ///
+    /// * `t` - time stamps, written as seconds (see [crate::durations_to_seconds])
+    /// * `y` - ordinate values
+    pub fn draw_time(&mut self, t: &[Duration], y: &[f64]) {
+        let x = durations_to_seconds(t);
+        self.draw(&x.as_slice(), &y);
     }
 
     /// Draws curve on a previously drawn figure with the same x
@@ -306,9 +480,9 @@ impl Curve {
     pub fn draw_with_twin_x<'a, T, U>(&mut self, y: &'a T)
     where
         T: AsVector<'a, U>,
-        U: 'a + std::fmt::Display,
+        U: 'a + std::fmt::Display + AsF64,
     {
-        vector_to_array(&mut self.buffer, "y2", y);
+        vector_to_array(&mut self.buffer, &self.array_format, "y2", y);
         let opt = self.options();
         write!(
             &mut self.buffer,
@@ -321,6 +495,295 @@ impl Curve {
         .unwrap();
     }
 
+    /// Draws curve with categories along the x axis
+    ///
+    /// The points are placed at the integer positions 0, 1, 2, ... and the ticks
+    /// along the x axis are labeled with the categories.
+    ///
+    /// # Input
+    ///
+    /// * `categories` -- the labels of the categories (e.g., months)
+    /// * `y` -- the values corresponding to each category
+    ///
+    /// **Note:** Single quotes in the categories are replaced by `’` (as in [Curve::set_label]).
+    pub fn draw_categorical(&mut self, categories: &[&str], y: &[f64]) -> Result<(), StrError> {
+        if categories.len() != y.len() {
+            return Err("categories and y arrays must have the same length");
+        }
+        for (i, v) in y.iter().enumerate() {
+            self.add_point(i as f64, *v);
+        }
+        write!(&mut self.buffer, "x=np.arange({})\n", y.len()).unwrap();
+        vector_to_array(&mut self.buffer, &self.array_format, "y", &y);
+        let names: Vec<String> = categories.iter().map(|c| c.replace("'", "’")).collect();
+        generate_list_quoted(&mut self.buffer, &self.array_format, "categories", &names);
+        let opt = self.options();
+        write!(
+            &mut self.buffer,
+            "plt.plot(x,y{})\n\
+             plt.xticks(x,categories)\n",
+            &opt
+        )
+        .unwrap();
+        Ok(())
+    }
+
+    /// Draws markers (scatter) that are filled where the mask is true and hollow where it is false
+    ///
+    /// # Input
+    ///
+    /// * `x` -- abscissa array
+    /// * `y` -- ordinate array
+    /// * `filled` -- mask indicating which markers are filled
+    ///
+    /// # Notes
+    ///
+    /// * All markers are drawn by a single call to scatter; thus, the legend shows a single entry.
+    /// * The color of markers is given by `marker_color`, or `line_color`, or the first color in the cycle.
+    /// * The type `U` must be a number.
+    pub fn draw_with_mask<'a, T, U>(&mut self, x: &'a T, y: &'a T, filled: &[bool]) -> Result<(), StrError>
+    where
+        T: AsVector<'a, U>,
+        U: 'a + std::fmt::Display + AsF64,
+    {
+        if y.vec_size() != x.vec_size() {
+            return Err("x and y arrays must have the same length");
+        }
+        if filled.len() != x.vec_size() {
+            return Err("filled and x arrays must have the same length");
+        }
+        for i in 0..x.vec_size() {
+            let (xi, yi) = (to_f64(x.vec_at(i)), to_f64(y.vec_at(i)));
+            self.add_point(xi, yi);
+        }
+        vector_to_array(&mut self.buffer, &self.array_format, "x", x);
+        vector_to_array(&mut self.buffer, &self.array_format, "y", y);
+        write!(&mut self.buffer, "filled=np.array([").unwrap();
+        for f in filled {
+            write!(&mut self.buffer, "{},", if *f { "True" } else { "False" }).unwrap();
+        }
+        write!(&mut self.buffer, "],dtype=bool)\n").unwrap();
+        let color = self.scatter_color().to_string();
+        let opt = self.options_scatter(ScatterKind::Plain);
+        write!(
+            &mut self.buffer,
+            "plt.scatter(x,y,facecolors=[{} if f else 'none' for f in filled]{})\n",
+            color, &opt
+        )
+        .unwrap();
+        Ok(())
+    }
+
+    /// Draws markers (scatter) with a size for each point (e.g., bubble chart)
+    ///
+    /// # Input
+    ///
+    /// * `x` -- abscissa array
+    /// * `y` -- ordinate array
+    /// * `sizes` -- the marker sizes given as areas in points^2 (as in Matplotlib's scatter)
+    ///
+    /// # Notes
+    ///
+    /// * The marker size given by `set_marker_size` is ignored.
+    /// * The color of markers is given by `marker_color`, or `line_color`, or the first color in the cycle.
+    /// * The type `U` must be a number.
+    pub fn draw_with_sizes<'a, T, U>(&mut self, x: &'a T, y: &'a T, sizes: &[f64]) -> Result<(), StrError>
+    where
+        T: AsVector<'a, U>,
+        U: 'a + std::fmt::Display + AsF64,
+    {
+        if y.vec_size() != x.vec_size() {
+            return Err("x and y arrays must have the same length");
+        }
+        if sizes.len() != x.vec_size() {
+            return Err("sizes and x arrays must have the same length");
+        }
+        for i in 0..x.vec_size() {
+            let (xi, yi) = (to_f64(x.vec_at(i)), to_f64(y.vec_at(i)));
+            self.add_point(xi, yi);
+        }
+        vector_to_array(&mut self.buffer, &self.array_format, "x", x);
+        vector_to_array(&mut self.buffer, &self.array_format, "y", y);
+        vector_to_array(&mut self.buffer, &self.array_format, "sizes", &sizes);
+        let color = self.scatter_color().to_string();
+        let opt = self.options_scatter(ScatterKind::Sized);
+        write!(
+            &mut self.buffer,
+            "plt.scatter(x,y,s=sizes,facecolors={}{})\n",
+            color, &opt
+        )
+        .unwrap();
+        Ok(())
+    }
+
+    /// Draws markers (scatter) colored according to their values, using a colormap
+    ///
+    /// # Input
+    ///
+    /// * `x` -- abscissa array
+    /// * `y` -- ordinate array
+    /// * `values` -- the values mapped to colors
+    ///
+    /// # Notes
+    ///
+    /// * The colormap is given by `set_colormap_name` (default is Matplotlib's default).
+    /// * A colorbar is drawn if `set_with_colorbar` is enabled.
+    /// * The type `U` must be a number.
+    pub fn draw_with_colors<'a, T, U>(&mut self, x: &'a T, y: &'a T, values: &[f64]) -> Result<(), StrError>
+    where
+        T: AsVector<'a, U>,
+        U: 'a + std::fmt::Display + AsF64,
+    {
+        if y.vec_size() != x.vec_size() {
+            return Err("x and y arrays must have the same length");
+        }
+        if values.len() != x.vec_size() {
+            return Err("values and x arrays must have the same length");
+        }
+        for i in 0..x.vec_size() {
+            let (xi, yi) = (to_f64(x.vec_at(i)), to_f64(y.vec_at(i)));
+            self.add_point(xi, yi);
+        }
+        vector_to_array(&mut self.buffer, &self.array_format, "x", x);
+        vector_to_array(&mut self.buffer, &self.array_format, "y", y);
+        vector_to_array(&mut self.buffer, &self.array_format, "values", &values);
+        let opt = self.options_scatter(ScatterKind::Colored);
+        write!(&mut self.buffer, "sc=plt.scatter(x,y,c=values{})\n", &opt).unwrap();
+        if self.with_colorbar {
+            write!(&mut self.buffer, "cb=plt.colorbar(sc)\n").unwrap();
+            if self.colorbar_label != "" {
+                write!(&mut self.buffer, "cb.ax.set_ylabel(r'{}')\n", self.colorbar_label).unwrap();
+            }
+        }
+        Ok(())
+    }
+
+    /// Draws a bubble chart with markers sized and colored according to two sets of values
+    ///
+    /// # Input
+    ///
+    /// * `x` -- abscissa array
+    /// * `y` -- ordinate array
+    /// * `sizes` -- the marker sizes given as areas in points^2 (as in Matplotlib's scatter)
+    /// * `values` -- the values mapped to colors
+    ///
+    /// # Notes
+    ///
+    /// * A colorbar (for the colors) is drawn on the right-hand side of the axes and a legend
+    ///   of the sizes is drawn in a single row above the axes; thus, they do not overlap.
+    /// * The size legend is added as an artist; hence, it is kept by later calls to `legend`.
+    /// * The colormap is given by `set_colormap_name` and the colorbar label by `set_colorbar_label`.
+    /// * The number of entries and the title of the size legend are given by `set_size_legend`.
+    /// * The type `U` must be a number.
+    pub fn draw_bubbles<'a, T, U>(&mut self, x: &'a T, y: &'a T, sizes: &[f64], values: &[f64]) -> Result<(), StrError>
+    where
+        T: AsVector<'a, U>,
+        U: 'a + std::fmt::Display + AsF64,
+    {
+        if y.vec_size() != x.vec_size() {
+            return Err("x and y arrays must have the same length");
+        }
+        if sizes.len() != x.vec_size() {
+            return Err("sizes and x arrays must have the same length");
+        }
+        if values.len() != x.vec_size() {
+            return Err("values and x arrays must have the same length");
+        }
+        for i in 0..x.vec_size() {
+            let (xi, yi) = (to_f64(x.vec_at(i)), to_f64(y.vec_at(i)));
+            self.add_point(xi, yi);
+        }
+        vector_to_array(&mut self.buffer, &self.array_format, "x", x);
+        vector_to_array(&mut self.buffer, &self.array_format, "y", y);
+        vector_to_array(&mut self.buffer, &self.array_format, "sizes", &sizes);
+        vector_to_array(&mut self.buffer, &self.array_format, "values", &values);
+        let opt = self.options_scatter(ScatterKind::SizedAndColored);
+        write!(&mut self.buffer, "sc=plt.scatter(x,y,s=sizes,c=values{})\n", &opt).unwrap();
+        write!(&mut self.buffer, "cb=plt.colorbar(sc)\n").unwrap();
+        if self.colorbar_label != "" {
+            write!(&mut self.buffer, "cb.ax.set_ylabel(r'{}')\n", self.colorbar_label).unwrap();
+        }
+        write!(
+            &mut self.buffer,
+            "hs,ls=sc.legend_elements(prop='sizes',num={},color='gray')\n\
+             plt.gca().add_artist(plt.legend(hs,ls,title=r'{}',loc='lower center',bbox_to_anchor=(0.5,1.02),ncol=max(1,len(hs)),frameon=False))\n",
+            self.size_legend_num, self.size_legend_title
+        )
+        .unwrap();
+        Ok(())
+    }
+
+    /// Draws markers (scatter) with one color per category
+    ///
+    /// # Input
+    ///
+    /// * `x` -- abscissa array
+    /// * `y` -- ordinate array
+    /// * `categories` -- the category of each point
+    ///
+    /// # Notes
+    ///
+    /// * The points of each category are drawn by a separate scatter command labeled with
+    ///   the category name; thus, the legend lists all categories.
+    /// * The colors are taken from Matplotlib's default cycle (`C0`, `C1`, ...) in the order
+    ///   given by `set_category_order`; thus, the same data always gets the same colors.
+    pub fn draw_by_category(&mut self, x: &[f64], y: &[f64], categories: &[&str]) -> Result<(), StrError> {
+        if y.len() != x.len() {
+            return Err("x and y arrays must have the same length");
+        }
+        if categories.len() != x.len() {
+            return Err("categories and x arrays must have the same length");
+        }
+        let mut groups: Vec<(&str, Vec<usize>)> = Vec::new();
+        for (i, category) in categories.iter().enumerate() {
+            match groups.iter_mut().find(|(name, _)| name == category) {
+                Some((_, indices)) => indices.push(i),
+                None => groups.push((category, vec![i])),
+            }
+        }
+        if self.category_order == CategoryOrder::Sorted {
+            groups.sort_by(|a, b| a.0.cmp(b.0));
+        }
+        let opt = self.options_category();
+        for (k, (name, indices)) in groups.iter().enumerate() {
+            let xx: Vec<f64> = indices.iter().map(|i| x[*i]).collect();
+            let yy: Vec<f64> = indices.iter().map(|i| y[*i]).collect();
+            for (xi, yi) in xx.iter().zip(yy.iter()) {
+                self.add_point(*xi, *yi);
+            }
+            vector_to_array(&mut self.buffer, &self.array_format, "x", &xx);
+            vector_to_array(&mut self.buffer, &self.array_format, "y", &yy);
+            write!(
+                &mut self.buffer,
+                "plt.scatter(x,y,color='C{}',label=r'{}'{})\n",
+                k % 10,
+                name.replace("'", "’"),
+                &opt
+            )
+            .unwrap();
+        }
+        Ok(())
+    }
+
+    /// Returns the indices of the drawn points that are inside a box (bounds included)
+    ///
+    /// The indices refer to all points drawn in 2D by this curve (in the drawing order)
+    /// since the call to [Curve::set_keep_points] or the last call to `clear_buffer`.
+    ///
+    /// **Note:** Returns an empty list if the points are not kept (default).
+    pub fn points_in_box(&self, xmin: f64, xmax: f64, ymin: f64, ymax: f64) -> Vec<usize> {
+        let points = match &self.points {
+            Some(points) => points,
+            None => return Vec::new(),
+        };
+        points
+            .iter()
+            .enumerate()
+            .filter(|(_, (x, y))| *x >= xmin && *x <= xmax && *y >= ymin && *y <= ymax)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
     /// Draws curve in 3D plot
     ///
     /// # Input
@@ -336,18 +799,215 @@ impl Curve {
     pub fn draw_3d<'a, T, U>(&mut self, x: &'a T, y: &'a T, z: &'a T)
     where
         T: AsVector<'a, U>,
-        U: 'a + std::fmt::Display,
+        U: 'a + std::fmt::Display + AsF64,
     {
-        vector_to_array(&mut self.buffer, "x", x);
-        vector_to_array(&mut self.buffer, "y", y);
-        vector_to_array(&mut self.buffer, "z", z);
+        for i in 0..usize::min(x.vec_size(), usize::min(y.vec_size(), z.vec_size())) {
+            extend_bounds(
+                &mut self.bounds,
+                to_f64(x.vec_at(i)),
+                to_f64(y.vec_at(i)),
+                to_f64(z.vec_at(i)),
+            );
+        }
+        vectors3_to_arrays(&mut self.buffer, &self.array_format, "x", "y", "z", x, y, z);
         let opt = self.options();
         write!(&mut self.buffer, "ax3d().plot(x,y,z{})\n", &opt).unwrap();
     }
 
+    /// Draws curve with error bars
+    ///
+    /// # Input
+    ///
+    /// * `x` -- abscissa array
+    /// * `y` -- ordinate array
+    /// * `yerr` -- the (symmetric) errors along y
+    /// * `xerr` -- the (symmetric) errors along x, if any
+    ///
+    /// # Notes
+    ///
+    /// * Use `set_markers_only` to draw points (scatter) with error bars but without lines.
+    /// * The type `U` must be a number.
+    pub fn draw_with_errors<'a, T, U>(
+        &mut self,
+        x: &'a T,
+        y: &'a T,
+        yerr: &[f64],
+        xerr: Option<&[f64]>,
+    ) -> Result<(), StrError>
+    where
+        T: AsVector<'a, U>,
+        U: 'a + std::fmt::Display + AsF64,
+    {
+        if y.vec_size() != x.vec_size() {
+            return Err("x and y arrays must have the same length");
+        }
+        if yerr.len() != x.vec_size() {
+            return Err("yerr and x arrays must have the same length");
+        }
+        if let Some(xe) = xerr {
+            if xe.len() != x.vec_size() {
+                return Err("xerr and x arrays must have the same length");
+            }
+        }
+        for i in 0..x.vec_size() {
+            let (xi, yi) = (to_f64(x.vec_at(i)), to_f64(y.vec_at(i)));
+            self.add_point(xi, yi);
+        }
+        vector_to_array(&mut self.buffer, &self.array_format, "x", x);
+        vector_to_array(&mut self.buffer, &self.array_format, "y", y);
+        vector_to_array(&mut self.buffer, &self.array_format, "yerr", &yerr);
+        if let Some(xe) = xerr {
+            vector_to_array(&mut self.buffer, &self.array_format, "xerr", &xe);
+        }
+        write!(&mut self.buffer, "plt.errorbar(x,y,yerr=yerr").unwrap();
+        if xerr.is_some() {
+            write!(&mut self.buffer, ",xerr=xerr").unwrap();
+        }
+        if self.error_cap_size > 0.0 {
+            write!(&mut self.buffer, ",capsize={}", self.error_cap_size).unwrap();
+        }
+        let opt = self.options();
+        write!(&mut self.buffer, "{})\n", &opt).unwrap();
+        Ok(())
+    }
+
+    /// Draws a filled region (band) between two curves, with a label shown in the legend
+    ///
+    /// # Input
+    ///
+    /// * `x` -- abscissa array
+    /// * `y1` -- ordinates of the first bounding curve
+    /// * `y2` -- ordinates of the second bounding curve
+    /// * `label` -- the name of the band in the legend
+    ///
+    /// # Notes
+    ///
+    /// * Single quotes in the label are replaced by `’` (as in [Curve::set_label]).
+    /// * The fill color is given by `line_color` and the opacity by `line_alpha`.
+    /// * The hatch is given by [Curve::set_band_hatch()] and also appears in the legend.
+    /// * The type `U` must be a number.
+    pub fn draw_band_labeled<'a, T, U>(&mut self, x: &'a T, y1: &'a T, y2: &'a T, label: &str) -> Result<(), StrError>
+    where
+        T: AsVector<'a, U>,
+        U: 'a + std::fmt::Display + AsF64,
+    {
+        if y1.vec_size() != x.vec_size() || y2.vec_size() != x.vec_size() {
+            return Err("x, y1, and y2 arrays must have the same length");
+        }
+        for i in 0..x.vec_size() {
+            let xi = to_f64(x.vec_at(i));
+            extend_bounds(&mut self.bounds, xi, to_f64(y1.vec_at(i)), 0.0);
+            extend_bounds(&mut self.bounds, xi, to_f64(y2.vec_at(i)), 0.0);
+        }
+        vector_to_array(&mut self.buffer, &self.array_format, "x", x);
+        vector_to_array(&mut self.buffer, &self.array_format, "y1", y1);
+        vector_to_array(&mut self.buffer, &self.array_format, "y2", y2);
+        let opt = self.options_band();
+        write!(
+            &mut self.buffer,
+            "plt.fill_between(x,y1,y2,label=r'{}'{})\n",
+            label.replace("'", "’"),
+            &opt
+        )
+        .unwrap();
+        Ok(())
+    }
+
+    /// Draws markers (scatter) in 3D plot
+    ///
+    /// # Input
+    ///
+    /// * `x` - x values
+    /// * `y` - y values
+    /// * `z` - z values
+    ///
+    /// # Notes
+    ///
+    /// * The color of markers is given by `marker_color`, or `line_color`, or the first color in the cycle.
+    /// * The type `U` of the input array must be a number.
+    pub fn draw_scatter_3d<'a, T, U>(&mut self, x: &'a T, y: &'a T, z: &'a T)
+    where
+        T: AsVector<'a, U>,
+        U: 'a + std::fmt::Display + AsF64,
+    {
+        for i in 0..usize::min(x.vec_size(), usize::min(y.vec_size(), z.vec_size())) {
+            extend_bounds(
+                &mut self.bounds,
+                to_f64(x.vec_at(i)),
+                to_f64(y.vec_at(i)),
+                to_f64(z.vec_at(i)),
+            );
+        }
+        vectors3_to_arrays(&mut self.buffer, &self.array_format, "x", "y", "z", x, y, z);
+        let color = self.scatter_color().to_string();
+        let opt = self.options_scatter(ScatterKind::Plain);
+        write!(&mut self.buffer, "ax3d().scatter(x,y,z,facecolors={}{})\n", color, &opt).unwrap();
+    }
+
+    /// Fills the region between the line and y=0 with a vertical gradient of the line color
+    ///
+    /// # Input
+    ///
+    /// * `top_alpha` -- opacity of the fill at the highest point (e.g., 0.6)
+    /// * `bottom_alpha` -- opacity of the fill at the lowest point (e.g., 0.0)
+    ///
+    /// # Notes
+    ///
+    /// * Only [Curve::draw] considers this option.
+    pub fn set_gradient_fill(&mut self, top_alpha: f64, bottom_alpha: f64) -> &mut Self {
+        self.gradient_fill = Some((top_alpha, bottom_alpha));
+        self
+    }
+
+    /// Sets the hatch pattern of bands drawn by [Curve::draw_band_labeled()]
+    ///
+    /// # Input
+    ///
+    /// * `hatch` -- the pattern; e.g., "/", "|", "-", "+", "x", "o", "O", ".", "*", "//"
+    /// * `color` -- the color of the hatch lines (and band edges), by name or as a [Color]; ignored if empty
+    pub fn set_band_hatch<C>(&mut self, hatch: &str, color: C) -> &mut Self
+    where
+        C: Into<Color>,
+    {
+        self.band_hatch = hatch.to_string();
+        self.band_hatch_color = color.into().to_python();
+        self
+    }
+
+    /// Sets a marker to flag the boundaries of gaps (missing data given as NaN values)
+    ///
+    /// The markers are placed at the valid points next to NaN values, i.e., where the line breaks.
+    ///
+    /// # Input
+    ///
+    /// * `marker` -- the marker style; e.g., "x" (disabled if empty)
+    /// * `color` -- the color of markers, by name (e.g., "red") or as a [Color]; ignored if empty
+    ///
+    /// # Notes
+    ///
+    /// * Only [Curve::draw] considers this option.
+    pub fn set_gap_markers<C>(&mut self, marker: &str, color: C) -> &mut Self
+    where
+        C: Into<Color>,
+    {
+        self.gap_marker = marker.to_string();
+        self.gap_marker_color = color.into().to_python();
+        self
+    }
+
+    /// Sets the size of the caps of error bars (drawn if positive)
+    ///
+    /// See [Curve::draw_with_errors]
+    pub fn set_error_cap_size(&mut self, size: f64) -> &mut Self {
+        self.error_cap_size = size;
+        self
+    }
+
     /// Sets the name of this curve in the legend
+    ///
+    /// Single quotation marks are replaced as in [crate::Plot::set_title()]
     pub fn set_label(&mut self, label: &str) -> &mut Self {
-        self.label = String::from(label);
+        self.label = label.replace("'", "’");
         self
     }
 
@@ -358,8 +1018,13 @@ impl Curve {
     }
 
     /// Sets the color of lines
-    pub fn set_line_color(&mut self, color: &str) -> &mut Self {
-        self.line_color = String::from(color);
+    ///
+    /// The color may be given by name (e.g., "red" or "#ff0000") or as a [Color]
+    pub fn set_line_color<C>(&mut self, color: C) -> &mut Self
+    where
+        C: Into<Color>,
+    {
+        self.line_color = color.into().to_python();
         self
     }
 
@@ -399,14 +1064,30 @@ impl Curve {
     }
 
     /// Sets the width of lines
+    ///
+    /// **Note:** A width ≤ 0 means the default width (the line is still drawn).
+    /// Use [Curve::set_markers_only()] to hide the line.
     pub fn set_line_width(&mut self, width: f64) -> &mut Self {
         self.line_width = width;
         self
     }
 
+    /// Sets option to draw the markers only (the line is hidden)
+    ///
+    /// This option overrides the line style and width, emitting `linestyle='none'`.
+    pub fn set_markers_only(&mut self, flag: bool) -> &mut Self {
+        self.markers_only = flag;
+        self
+    }
+
     /// Sets the color of markers
-    pub fn set_marker_color(&mut self, color: &str) -> &mut Self {
-        self.marker_color = String::from(color);
+    ///
+    /// The color may be given by name (e.g., "red" or "#ff0000") or as a [Color]
+    pub fn set_marker_color<C>(&mut self, color: C) -> &mut Self
+    where
+        C: Into<Color>,
+    {
+        self.marker_color = color.into().to_python();
         self
     }
 
@@ -416,15 +1097,29 @@ impl Curve {
         self
     }
 
-    /// Sets the option to draw a void marker (draw edge only)
-    pub fn set_marker_void(&mut self, flag: bool) -> &mut Self {
-        self.marker_void = flag;
+    /// Sets the indices of the data points to use when drawing markers
+    ///
+    /// For example, to highlight the local maxima. The indices take precedence over
+    /// [Curve::set_marker_every]; an empty slice disables this option.
+    pub fn set_marker_indices(&mut self, indices: &[usize]) -> &mut Self {
+        self.marker_indices = indices.to_vec();
+        self
+    }
+
+    /// Sets the option to draw a void marker (draw edge only)
+    pub fn set_marker_void(&mut self, flag: bool) -> &mut Self {
+        self.marker_void = flag;
         self
     }
 
     /// Sets the edge color of markers
-    pub fn set_marker_line_color(&mut self, color: &str) -> &mut Self {
-        self.marker_line_color = String::from(color);
+    ///
+    /// The color may be given by name (e.g., "red" or "#ff0000") or as a [Color]
+    pub fn set_marker_line_color<C>(&mut self, color: C) -> &mut Self
+    where
+        C: Into<Color>,
+    {
+        self.marker_line_color = color.into().to_python();
         self
     }
 
@@ -451,12 +1146,53 @@ impl Curve {
         self
     }
 
+    /// Sets the amount of random jitter added to the coordinates drawn by [Curve::draw]
+    ///
+    /// Each coordinate is perturbed by a uniform random value within `[-jitter, jitter]`, e.g.,
+    /// to separate overlapping markers of categorical data. A jitter of 0.0 means no perturbation
+    /// (default). The random numbers are generated in Rust; see [Curve::set_jitter_seed].
+    pub fn set_jitter(&mut self, jitter_x: f64, jitter_y: f64) -> &mut Self {
+        self.jitter_x = jitter_x;
+        self.jitter_y = jitter_y;
+        self
+    }
+
+    /// Enables the deduplication of the arrays written by [Curve::draw] (default = false)
+    ///
+    /// If enabled, identical arrays (e.g., the same x values of many series) are written only once
+    /// to the buffer (as `da0`, `da1`, ...) and then reused. This reduces the size of the script.
+    pub fn set_dedup_arrays(&mut self, flag: bool) -> &mut Self {
+        self.array_cache = if flag { Some(ArrayCache::default()) } else { None };
+        self
+    }
+
+    /// Enables keeping a copy of the points drawn in 2D, as required by [Curve::points_in_box] (default = false)
+    pub fn set_keep_points(&mut self, flag: bool) -> &mut Self {
+        self.points = if flag { Some(Vec::new()) } else { None };
+        self
+    }
+
+    /// Sets the seed of the random numbers of the jitter (the results are reproducible)
+    pub fn set_jitter_seed(&mut self, seed: u64) -> &mut Self {
+        self.jitter_state = seed;
+        self
+    }
+
     /// Sets the flag to stop clipping features within margins
     pub fn set_stop_clip(&mut self, flag: bool) -> &mut Self {
         self.stop_clip = flag;
         self
     }
 
+    /// Sets whether the lines and markers are clipped by the axes (default is true, as in Matplotlib)
+    ///
+    /// Passing false emits `clip_on=False`; thus, the markers at the axes limits are fully drawn.
+    /// This is the same as calling `set_stop_clip(!flag)`.
+    pub fn set_clip_on(&mut self, flag: bool) -> &mut Self {
+        self.stop_clip = !flag;
+        self
+    }
+
     /// Sets extra matplotlib commands (comma separated)
     ///
     /// **Important:** The extra commands must be comma separated. For example:
@@ -471,11 +1207,190 @@ impl Curve {
         self
     }
 
+    /// Sets the colormap name for markers colored by values
+    ///
+    /// See [Curve::draw_with_colors] and [Matplotlib's colormaps](https://matplotlib.org/stable/tutorials/colors/colormaps.html)
+    pub fn set_colormap_name(&mut self, name: &str) -> &mut Self {
+        self.colormap_name = String::from(name);
+        self
+    }
+
+    /// Sets option to draw a colorbar for markers colored by values
+    pub fn set_with_colorbar(&mut self, flag: bool) -> &mut Self {
+        self.with_colorbar = flag;
+        self
+    }
+
+    /// Sets the colorbar label
+    pub fn set_colorbar_label(&mut self, label: &str) -> &mut Self {
+        self.colorbar_label = String::from(label);
+        self
+    }
+
+    /// Sets the number of entries (approximately) and the title of the legend of marker sizes
+    ///
+    /// See [Curve::draw_bubbles]
+    pub fn set_size_legend(&mut self, num: usize, title: &str) -> &mut Self {
+        self.size_legend_num = num;
+        self.size_legend_title = String::from(title);
+        self
+    }
+
+    /// Sets the order in which categories are assigned colors
+    ///
+    /// See [Curve::draw_by_category]
+    pub fn set_category_order(&mut self, order: CategoryOrder) -> &mut Self {
+        self.category_order = order;
+        self
+    }
+
+    /// Sets the format of the numbers in the generated lists and arrays
+    pub fn set_array_format(&mut self, format: &ArrayFormat) -> &mut Self {
+        self.array_format = format.clone();
+        self
+    }
+
+    /// Returns the next random number of the jitter, uniform in [-1, 1) (SplitMix64 generator)
+    fn next_jitter(&mut self) -> f64 {
+        self.jitter_state = self.jitter_state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.jitter_state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^= z >> 31;
+        2.0 * ((z >> 11) as f64) / ((1_u64 << 53) as f64) - 1.0
+    }
+
+    /// Writes the commands to fill the region under the line (handle gl) with a gradient
+    fn write_gradient_fill(&mut self, top_alpha: f64, bottom_alpha: f64) {
+        write!(
+            &mut self.buffer,
+            "import matplotlib.colors as mcl\n\
+             gy0,gy1=min(0.0,np.nanmin(y)),max(0.0,np.nanmax(y))\n\
+             gz=np.empty((256,1,4))\n\
+             gz[:,:,:3]=mcl.to_rgb(gl.get_color())\n\
+             gz[:,:,3]=np.linspace({},{},256).reshape(-1,1)\n\
+             gim=plt.imshow(gz,aspect='auto',origin='lower',extent=(np.nanmin(x),np.nanmax(x),gy0,gy1),zorder=gl.get_zorder()-0.1)\n\
+             gpo=pat.Polygon(np.column_stack((np.concatenate(([x[0]],x,[x[-1]])),np.concatenate(([0.0],y,[0.0])))),closed=True,facecolor='none',edgecolor='none')\n\
+             plt.gca().add_patch(gpo)\n\
+             gim.set_clip_path(gpo)\n\
+             plt.gca().autoscale(True)\n",
+            bottom_alpha, top_alpha
+        )
+        .unwrap();
+    }
+
+    /// Returns the color of markers used by scatter
+    fn scatter_color(&self) -> &str {
+        if self.marker_color != "" {
+            &self.marker_color
+        } else if self.line_color != "" {
+            &self.line_color
+        } else {
+            "'C0'"
+        }
+    }
+
+    /// Extends the bounds with a point drawn in 2D and keeps the point (if enabled)
+    fn add_point(&mut self, x: f64, y: f64) {
+        extend_bounds(&mut self.bounds, x, y, 0.0);
+        if let Some(points) = &mut self.points {
+            points.push((x, y));
+        }
+    }
+
+    /// Returns options for bands (fill between)
+    fn options_band(&self) -> String {
+        let mut opt = String::new();
+        if self.line_alpha > 0.0 {
+            write!(&mut opt, ",alpha={}", self.line_alpha).unwrap();
+        }
+        if self.line_color != "" {
+            write!(&mut opt, ",facecolor={}", self.line_color).unwrap();
+        }
+        if self.band_hatch != "" {
+            write!(&mut opt, ",hatch='{}'", self.band_hatch).unwrap();
+        }
+        if self.band_hatch_color != "" {
+            write!(&mut opt, ",edgecolor={}", self.band_hatch_color).unwrap();
+        }
+        if self.stop_clip {
+            write!(&mut opt, ",clip_on=False").unwrap();
+        }
+        opt
+    }
+
+    /// Returns options for scatter
+    ///
+    /// The marker size is skipped if the sizes are given by an array.
+    /// The colormap is used and the default edge color is skipped if the colors are given by values.
+    fn options_scatter(&self, kind: ScatterKind) -> String {
+        let per_point_sizes = matches!(kind, ScatterKind::Sized | ScatterKind::SizedAndColored);
+        let colormapped = matches!(kind, ScatterKind::Colored | ScatterKind::SizedAndColored);
+        let mut opt = String::new();
+        if self.label != "" {
+            write!(&mut opt, ",label=r'{}'", self.label).unwrap();
+        }
+        if self.line_alpha > 0.0 {
+            write!(&mut opt, ",alpha={}", self.line_alpha).unwrap();
+        }
+        if colormapped && self.colormap_name != "" {
+            write!(&mut opt, ",cmap=plt.get_cmap('{}')", self.colormap_name).unwrap();
+        }
+        if self.marker_line_color != "" {
+            write!(&mut opt, ",edgecolors={}", self.marker_line_color).unwrap();
+        } else if !colormapped {
+            write!(&mut opt, ",edgecolors={}", self.scatter_color()).unwrap();
+        }
+        if self.marker_line_width > 0.0 {
+            write!(&mut opt, ",linewidths={}", self.marker_line_width).unwrap();
+        }
+        if self.marker_size > 0.0 && !per_point_sizes {
+            write!(&mut opt, ",s={}", self.marker_size * self.marker_size).unwrap();
+        }
+        if self.marker_style != "" {
+            write!(&mut opt, ",marker={}", quote_marker(&self.marker_style)).unwrap();
+        }
+        if self.stop_clip {
+            write!(&mut opt, ",clip_on=False").unwrap();
+        }
+        if self.extra != "" {
+            write!(&mut opt, ",{}", self.extra).unwrap();
+        }
+        opt
+    }
+
+    /// Returns options for scatter of categories (the color and label are set by the caller)
+    fn options_category(&self) -> String {
+        let mut opt = String::new();
+        if self.line_alpha > 0.0 {
+            write!(&mut opt, ",alpha={}", self.line_alpha).unwrap();
+        }
+        if self.marker_line_color != "" {
+            write!(&mut opt, ",edgecolors={}", self.marker_line_color).unwrap();
+        }
+        if self.marker_line_width > 0.0 {
+            write!(&mut opt, ",linewidths={}", self.marker_line_width).unwrap();
+        }
+        if self.marker_size > 0.0 {
+            write!(&mut opt, ",s={}", self.marker_size * self.marker_size).unwrap();
+        }
+        if self.marker_style != "" {
+            write!(&mut opt, ",marker={}", quote_marker(&self.marker_style)).unwrap();
+        }
+        if self.stop_clip {
+            write!(&mut opt, ",clip_on=False").unwrap();
+        }
+        if self.extra != "" {
+            write!(&mut opt, ",{}", self.extra).unwrap();
+        }
+        opt
+    }
+
     /// Returns options for curve
     fn options(&self) -> String {
         // fix color if marker is void
         let line_color = if self.marker_void && self.line_color == "" {
-            "red"
+            "'red'"
         } else {
             &self.line_color
         };
@@ -493,27 +1408,38 @@ impl Curve {
             write!(&mut opt, ",alpha={}", self.line_alpha).unwrap();
         }
         if line_color != "" {
-            write!(&mut opt, ",color='{}'", line_color).unwrap();
-        }
-        if self.line_style != "" {
-            write!(&mut opt, ",linestyle='{}'", self.line_style).unwrap();
+            write!(&mut opt, ",color={}", line_color).unwrap();
         }
-        if self.line_width > 0.0 {
-            write!(&mut opt, ",linewidth={}", self.line_width).unwrap();
+        if self.markers_only {
+            write!(&mut opt, ",linestyle='none'").unwrap();
+        } else {
+            if self.line_style != "" {
+                write!(&mut opt, ",linestyle='{}'", self.line_style).unwrap();
+            }
+            if self.line_width > 0.0 {
+                write!(&mut opt, ",linewidth={}", self.line_width).unwrap();
+            }
         }
 
         // markers
         if !self.marker_void && self.marker_color != "" {
-            write!(&mut opt, ",markerfacecolor='{}'", self.marker_color).unwrap();
+            write!(&mut opt, ",markerfacecolor={}", self.marker_color).unwrap();
         }
-        if self.marker_every > 0 {
+        if self.marker_indices.len() > 0 {
+            write!(
+                &mut opt,
+                ",markevery={}",
+                array2num(&self.array_format, &self.marker_indices)
+            )
+            .unwrap();
+        } else if self.marker_every > 0 {
             write!(&mut opt, ",markevery={}", self.marker_every).unwrap();
         }
         if self.marker_void {
             write!(&mut opt, ",markerfacecolor='none'").unwrap();
         }
         if self.marker_line_color != "" {
-            write!(&mut opt, ",markeredgecolor='{}'", self.marker_line_color).unwrap();
+            write!(&mut opt, ",markeredgecolor={}", self.marker_line_color).unwrap();
         }
         if self.marker_line_width > 0.0 {
             write!(&mut opt, ",markeredgewidth={}", self.marker_line_width).unwrap();
@@ -538,12 +1464,40 @@ impl Curve {
     }
 }
 
+/// Returns the valid (finite) points next to invalid points (e.g., NaN), i.e., the boundaries of gaps
+fn gap_boundaries(points: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    let valid = |p: &(f64, f64)| p.0.is_finite() && p.1.is_finite();
+    let n = points.len();
+    let mut res = Vec::new();
+    for i in 0..n {
+        if !valid(&points[i]) {
+            continue;
+        }
+        let gap_before = i > 0 && !valid(&points[i - 1]);
+        let gap_after = i + 1 < n && !valid(&points[i + 1]);
+        if gap_before || gap_after {
+            res.push(points[i]);
+        }
+    }
+    res
+}
+
 impl GraphMaker for Curve {
     fn get_buffer<'a>(&'a self) -> &'a String {
         &self.buffer
     }
     fn clear_buffer(&mut self) {
         self.buffer.clear();
+        self.bounds = None;
+        if let Some(points) = &mut self.points {
+            points.clear();
+        }
+        if let Some(cache) = &mut self.array_cache {
+            cache.clear();
+        }
+    }
+    fn data_bounds(&self) -> Option<(f64, f64, f64, f64, f64, f64)> {
+        self.bounds
     }
 }
 
@@ -551,8 +1505,9 @@ impl GraphMaker for Curve {
 
 #[cfg(test)]
 mod tests {
-    use super::{Curve, RayEndpoint};
-    use crate::GraphMaker;
+    use super::{gap_boundaries, CategoryOrder, Curve, RayEndpoint, ScatterKind};
+    use crate::{Color, GraphMaker};
+    use std::time::Duration;
 
     #[test]
     fn new_works() {
@@ -562,8 +1517,10 @@ mod tests {
         assert_eq!(curve.line_color.len(), 0);
         assert_eq!(curve.line_style.len(), 0);
         assert_eq!(curve.line_width, 0.0);
+        assert_eq!(curve.markers_only, false);
         assert_eq!(curve.marker_color.len(), 0);
         assert_eq!(curve.marker_every, 0);
+        assert_eq!(curve.marker_indices.len(), 0);
         assert_eq!(curve.marker_void, false);
         assert_eq!(curve.marker_line_color.len(), 0);
         assert_eq!(curve.marker_line_width, 0.0);
@@ -613,6 +1570,52 @@ mod tests {
         }
     }
 
+    #[test]
+    fn options_with_marker_indices_works() {
+        let mut curve = Curve::new();
+        curve.set_marker_indices(&[0, 3, 7]);
+        assert_eq!(curve.options(), ",markevery=[0,3,7]");
+        curve.set_marker_every(2);
+        assert_eq!(curve.options(), ",markevery=[0,3,7]");
+        curve.set_marker_indices(&[]);
+        assert_eq!(curve.options(), ",markevery=2");
+    }
+
+    #[test]
+    fn options_with_markers_only_works() {
+        let mut curve = Curve::new();
+        curve.set_line_style("--").set_line_width(1e-10).set_marker_style("o");
+        assert_eq!(curve.options(), ",linestyle='--',linewidth=0.0000000001,marker='o'");
+        curve.set_markers_only(true);
+        assert_eq!(curve.options(), ",linestyle='none',marker='o'");
+        curve.set_markers_only(false).set_line_width(0.0);
+        assert_eq!(curve.options(), ",linestyle='--',marker='o'");
+    }
+
+    #[test]
+    fn options_with_rgba_colors_works() {
+        let mut curve = Curve::new();
+        curve
+            .set_line_color(Color::Rgb(0.0, 0.0, 1.0))
+            .set_marker_color(Color::Rgba(1.0, 0.0, 0.0, 0.5))
+            .set_marker_line_color("black");
+        let options = curve.options();
+        assert_eq!(
+            options,
+            ",color=(0,0,1)\
+             ,markerfacecolor=(1,0,0,0.5)\
+             ,markeredgecolor='black'"
+        );
+        curve.set_line_color("").set_marker_void(true);
+        let options = curve.options();
+        assert_eq!(
+            options,
+            ",color='red'\
+             ,markerfacecolor='none'\
+             ,markeredgecolor='black'"
+        );
+    }
+
     #[test]
     fn points_methods_work() {
         let mut curve = Curve::new();
@@ -651,6 +1654,530 @@ mod tests {
         assert_eq!(curve.buffer, "");
     }
 
+    #[test]
+    fn draw_categorical_works() {
+        let mut curve = Curve::new();
+        assert_eq!(
+            curve.draw_categorical(&["Jan", "Feb"], &[1.0]).err(),
+            Some("categories and y arrays must have the same length")
+        );
+        curve.set_marker_style("o");
+        curve
+            .draw_categorical(&["Jan", "Feb", "Mar"], &[3.0, 1.0, 2.0])
+            .unwrap();
+        let b: &str = "x=np.arange(3)\n\
+                       y=np.array([3,1,2,],dtype=float)\n\
+                       categories=['Jan','Feb','Mar',]\n\
+                       plt.plot(x,y,marker='o')\n\
+                       plt.xticks(x,categories)\n";
+        assert_eq!(curve.buffer, b);
+        assert_eq!(curve.data_bounds(), Some((0.0, 2.0, 1.0, 3.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn draw_categorical_and_by_category_replace_quotes() {
+        let mut curve = Curve::new();
+        curve.draw_categorical(&["Jan's", "Feb"], &[1.0, 2.0]).unwrap();
+        assert!(curve.buffer.contains("categories=['Jan’s','Feb',]\n"));
+        curve.clear_buffer();
+        curve.draw_by_category(&[1.0], &[2.0], &["O'Brien"]).unwrap();
+        assert!(curve.buffer.ends_with("plt.scatter(x,y,color='C0',label=r'O’Brien')\n"));
+    }
+
+    #[test]
+    fn set_clip_on_works() {
+        let mut curve = Curve::new();
+        assert!(!curve.options().contains("clip_on"));
+        curve.set_clip_on(false);
+        assert_eq!(curve.options(), ",clip_on=False");
+        assert_eq!(curve.options_scatter(ScatterKind::Colored), ",clip_on=False");
+        curve.draw_by_category(&[1.0], &[2.0], &["a"]).unwrap();
+        assert!(curve.buffer.ends_with("label=r'a',clip_on=False)\n"));
+        curve.set_clip_on(true);
+        assert_eq!(curve.options(), "");
+    }
+
+    #[test]
+    fn options_scatter_uses_valid_alpha_keyword() {
+        let mut curve = Curve::new();
+        curve.set_line_alpha(0.5).set_marker_color("red");
+        let opt = curve.options_scatter(ScatterKind::Plain);
+        assert_eq!(opt, ",alpha=0.5,edgecolors='red'");
+        assert!(!opt.contains("markeralpha"));
+        assert!(!curve.options().contains("markeralpha"));
+    }
+
+    #[test]
+    fn draw_with_sizes_works() {
+        let x: &[f64] = &[1.0, 2.0, 3.0];
+        let y: &[f64] = &[4.0, 5.0, 6.0];
+        let w: &[f64] = &[4.0, 5.0];
+        let mut curve = Curve::new();
+        assert_eq!(
+            curve.draw_with_sizes(&x, &w, &[10.0, 20.0, 30.0]).err(),
+            Some("x and y arrays must have the same length")
+        );
+        assert_eq!(
+            curve.draw_with_sizes(&x, &y, &[10.0, 20.0]).err(),
+            Some("sizes and x arrays must have the same length")
+        );
+        curve.set_marker_color("red").set_marker_size(5.0);
+        curve.draw_with_sizes(&x, &y, &[10.0, 20.0, 30.0]).unwrap();
+        let b: &str = "x=np.array([1,2,3,],dtype=float)\n\
+                       y=np.array([4,5,6,],dtype=float)\n\
+                       sizes=np.array([10,20,30,],dtype=float)\n\
+                       plt.scatter(x,y,s=sizes,facecolors='red',edgecolors='red')\n";
+        assert_eq!(curve.buffer, b);
+        assert_eq!(curve.data_bounds(), Some((1.0, 3.0, 4.0, 6.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn options_scatter_with_label_works() {
+        let mut curve = Curve::new();
+        curve.set_label("O'Brien").set_marker_color("red");
+        assert_eq!(
+            curve.options_scatter(ScatterKind::Plain),
+            ",label=r'O’Brien',edgecolors='red'"
+        );
+    }
+
+    #[test]
+    fn draw_by_category_works() {
+        let x: &[f64] = &[1.0, 2.0, 3.0];
+        let y: &[f64] = &[4.0, 5.0, 6.0];
+        let mut curve = Curve::new();
+        assert_eq!(
+            curve.draw_by_category(x, &[4.0], &["b", "a", "b"]).err(),
+            Some("x and y arrays must have the same length")
+        );
+        assert_eq!(
+            curve.draw_by_category(x, y, &["b", "a"]).err(),
+            Some("categories and x arrays must have the same length")
+        );
+        curve.draw_by_category(x, y, &["b", "a", "b"]).unwrap();
+        let b: &str = "x=np.array([1,3,],dtype=float)\n\
+                       y=np.array([4,6,],dtype=float)\n\
+                       plt.scatter(x,y,color='C0',label=r'b')\n\
+                       x=np.array([2,],dtype=float)\n\
+                       y=np.array([5,],dtype=float)\n\
+                       plt.scatter(x,y,color='C1',label=r'a')\n";
+        assert_eq!(curve.buffer, b);
+        assert_eq!(curve.data_bounds(), Some((1.0, 3.0, 4.0, 6.0, 0.0, 0.0)));
+        curve.clear_buffer();
+        curve.set_category_order(CategoryOrder::Sorted).set_marker_style("s");
+        curve.draw_by_category(x, y, &["b", "a", "b"]).unwrap();
+        let b: &str = "x=np.array([2,],dtype=float)\n\
+                       y=np.array([5,],dtype=float)\n\
+                       plt.scatter(x,y,color='C0',label=r'a',marker='s')\n\
+                       x=np.array([1,3,],dtype=float)\n\
+                       y=np.array([4,6,],dtype=float)\n\
+                       plt.scatter(x,y,color='C1',label=r'b',marker='s')\n";
+        assert_eq!(curve.buffer, b);
+    }
+
+    #[test]
+    fn draw_with_colors_works() {
+        let x: &[f64] = &[1.0, 2.0];
+        let y: &[f64] = &[3.0, 4.0];
+        let w: &[f64] = &[3.0];
+        let mut curve = Curve::new();
+        assert_eq!(
+            curve.draw_with_colors(&x, &w, &[0.5, 1.0]).err(),
+            Some("x and y arrays must have the same length")
+        );
+        assert_eq!(
+            curve.draw_with_colors(&x, &y, &[0.5]).err(),
+            Some("values and x arrays must have the same length")
+        );
+        curve.draw_with_colors(&x, &y, &[0.5, 1.0]).unwrap();
+        assert!(curve.buffer.ends_with("sc=plt.scatter(x,y,c=values)\n"));
+        curve.clear_buffer();
+        curve
+            .set_colormap_name("viridis")
+            .set_with_colorbar(true)
+            .set_colorbar_label("temperature");
+        curve.draw_with_colors(&x, &y, &[0.5, 1.0]).unwrap();
+        let b: &str = "x=np.array([1,2,],dtype=float)\n\
+                       y=np.array([3,4,],dtype=float)\n\
+                       values=np.array([0.5,1,],dtype=float)\n\
+                       sc=plt.scatter(x,y,c=values,cmap=plt.get_cmap('viridis'))\n\
+                       cb=plt.colorbar(sc)\n\
+                       cb.ax.set_ylabel(r'temperature')\n";
+        assert_eq!(curve.buffer, b);
+    }
+
+    #[test]
+    fn draw_bubbles_works() {
+        let x: &[f64] = &[1.0, 2.0];
+        let y: &[f64] = &[3.0, 4.0];
+        let w: &[f64] = &[3.0];
+        let mut curve = Curve::new();
+        assert_eq!(
+            curve.draw_bubbles(&x, &w, &[10.0, 20.0], &[0.5, 1.0]).err(),
+            Some("x and y arrays must have the same length")
+        );
+        assert_eq!(
+            curve.draw_bubbles(&x, &y, &[10.0], &[0.5, 1.0]).err(),
+            Some("sizes and x arrays must have the same length")
+        );
+        assert_eq!(
+            curve.draw_bubbles(&x, &y, &[10.0, 20.0], &[0.5]).err(),
+            Some("values and x arrays must have the same length")
+        );
+        curve
+            .set_colormap_name("viridis")
+            .set_colorbar_label("temperature")
+            .set_size_legend(3, "population");
+        curve.draw_bubbles(&x, &y, &[10.0, 20.0], &[0.5, 1.0]).unwrap();
+        let b: &str = "x=np.array([1,2,],dtype=float)\n\
+                       y=np.array([3,4,],dtype=float)\n\
+                       sizes=np.array([10,20,],dtype=float)\n\
+                       values=np.array([0.5,1,],dtype=float)\n\
+                       sc=plt.scatter(x,y,s=sizes,c=values,cmap=plt.get_cmap('viridis'))\n\
+                       cb=plt.colorbar(sc)\n\
+                       cb.ax.set_ylabel(r'temperature')\n\
+                       hs,ls=sc.legend_elements(prop='sizes',num=3,color='gray')\n\
+                       plt.gca().add_artist(plt.legend(hs,ls,title=r'population',loc='lower center',bbox_to_anchor=(0.5,1.02),ncol=max(1,len(hs)),frameon=False))\n";
+        assert_eq!(curve.buffer, b);
+    }
+
+    #[test]
+    fn draw_with_gradient_fill_works() {
+        let mut curve = Curve::new();
+        curve.set_gradient_fill(0.6, 0.0);
+        curve.draw(&[0.0, 1.0], &[1.0, 2.0]);
+        let b: &str = "x=np.array([0,1,],dtype=float)\n\
+                       y=np.array([1,2,],dtype=float)\n\
+                       gl,=plt.plot(x,y)\n\
+                       import matplotlib.colors as mcl\n\
+                       gy0,gy1=min(0.0,np.nanmin(y)),max(0.0,np.nanmax(y))\n\
+                       gz=np.empty((256,1,4))\n\
+                       gz[:,:,:3]=mcl.to_rgb(gl.get_color())\n\
+                       gz[:,:,3]=np.linspace(0,0.6,256).reshape(-1,1)\n\
+                       gim=plt.imshow(gz,aspect='auto',origin='lower',extent=(np.nanmin(x),np.nanmax(x),gy0,gy1),zorder=gl.get_zorder()-0.1)\n\
+                       gpo=pat.Polygon(np.column_stack((np.concatenate(([x[0]],x,[x[-1]])),np.concatenate(([0.0],y,[0.0])))),closed=True,facecolor='none',edgecolor='none')\n\
+                       plt.gca().add_patch(gpo)\n\
+                       gim.set_clip_path(gpo)\n\
+                       plt.gca().autoscale(True)\n";
+        assert_eq!(curve.buffer, b);
+    }
+
+    #[test]
+    fn draw_with_errors_works() {
+        let x: &[f64] = &[1.0, 2.0];
+        let y: &[f64] = &[3.0, 4.0];
+        let w: &[f64] = &[3.0];
+        let mut curve = Curve::new();
+        assert_eq!(
+            curve.draw_with_errors(&x, &w, &[0.1, 0.2], None).err(),
+            Some("x and y arrays must have the same length")
+        );
+        assert_eq!(
+            curve.draw_with_errors(&x, &y, &[0.1], None).err(),
+            Some("yerr and x arrays must have the same length")
+        );
+        assert_eq!(
+            curve.draw_with_errors(&x, &y, &[0.1, 0.2], Some(&[0.5])).err(),
+            Some("xerr and x arrays must have the same length")
+        );
+        curve.draw_with_errors(&x, &y, &[0.1, 0.2], None).unwrap();
+        let b: &str = "x=np.array([1,2,],dtype=float)\n\
+                       y=np.array([3,4,],dtype=float)\n\
+                       yerr=np.array([0.1,0.2,],dtype=float)\n\
+                       plt.errorbar(x,y,yerr=yerr)\n";
+        assert_eq!(curve.buffer, b);
+        curve.clear_buffer();
+        curve
+            .set_error_cap_size(3.0)
+            .set_markers_only(true)
+            .set_marker_style("o");
+        curve.draw_with_errors(&x, &y, &[0.1, 0.2], Some(&[0.5, 0.6])).unwrap();
+        let b: &str = "x=np.array([1,2,],dtype=float)\n\
+                       y=np.array([3,4,],dtype=float)\n\
+                       yerr=np.array([0.1,0.2,],dtype=float)\n\
+                       xerr=np.array([0.5,0.6,],dtype=float)\n\
+                       plt.errorbar(x,y,yerr=yerr,xerr=xerr,capsize=3,linestyle='none',marker='o')\n";
+        assert_eq!(curve.buffer, b);
+    }
+
+    #[test]
+    fn draw_band_labeled_works() {
+        let x: &[f64] = &[1.0, 2.0];
+        let y1: &[f64] = &[0.0, 1.0];
+        let y2: &[f64] = &[2.0, 3.0];
+        let w: &[f64] = &[2.0];
+        let mut curve = Curve::new();
+        assert_eq!(
+            curve.draw_band_labeled(&x, &y1, &w, "band").err(),
+            Some("x, y1, and y2 arrays must have the same length")
+        );
+        curve.draw_band_labeled(&x, &y1, &y2, "band").unwrap();
+        let b: &str = "x=np.array([1,2,],dtype=float)\n\
+                       y1=np.array([0,1,],dtype=float)\n\
+                       y2=np.array([2,3,],dtype=float)\n\
+                       plt.fill_between(x,y1,y2,label=r'band')\n";
+        assert_eq!(curve.buffer, b);
+        assert_eq!(curve.data_bounds(), Some((1.0, 2.0, 0.0, 3.0, 0.0, 0.0)));
+        curve.clear_buffer();
+        curve
+            .set_line_color("#f0f0f0")
+            .set_line_alpha(0.5)
+            .set_band_hatch("//", "black");
+        curve.draw_band_labeled(&x, &y1, &y2, "uncertainty").unwrap();
+        assert!(curve.buffer.ends_with(
+            "plt.fill_between(x,y1,y2,label=r'uncertainty',alpha=0.5,facecolor='#f0f0f0',hatch='//',edgecolor='black')\n"
+        ));
+        curve.clear_buffer();
+        curve.set_band_hatch("x", Color::Rgb(0.5, 0.5, 0.5));
+        curve.draw_band_labeled(&x, &y1, &y2, "uncertainty").unwrap();
+        assert!(curve.buffer.ends_with(",hatch='x',edgecolor=(0.5,0.5,0.5))\n"));
+        curve.clear_buffer();
+        curve.draw_band_labeled(&x, &y1, &y2, "John's band").unwrap();
+        assert!(curve.buffer.contains("plt.fill_between(x,y1,y2,label=r'John’s band',"));
+    }
+
+    #[test]
+    fn gap_boundaries_works() {
+        let nan = f64::NAN;
+        assert_eq!(gap_boundaries(&[]), &[]);
+        assert_eq!(gap_boundaries(&[(0.0, 0.0), (1.0, 1.0)]), &[]);
+        let points = &[
+            (0.0, 0.0),
+            (1.0, 1.0),
+            (2.0, nan),
+            (3.0, 3.0),
+            (nan, 4.0),
+            (5.0, 5.0),
+            (6.0, 6.0),
+        ];
+        assert_eq!(gap_boundaries(points), &[(1.0, 1.0), (3.0, 3.0), (5.0, 5.0)]);
+        assert_eq!(gap_boundaries(&[(nan, nan), (1.0, 1.0)]), &[(1.0, 1.0)]);
+    }
+
+    #[test]
+    fn draw_with_jitter_works() {
+        let x = &[1.0, 1.0, 1.0, 2.0, 2.0, 2.0];
+        let y = &[5.0, 5.0, 5.0, 6.0, 6.0, 6.0];
+        let values = |buffer: &str, name: &str| -> Vec<f64> {
+            let start = buffer.find(&format!("{}=np.array([", name)).unwrap() + 12;
+            let end = start + buffer[start..].find("]").unwrap();
+            buffer[start..end]
+                .split(',')
+                .filter(|v| *v != "")
+                .map(|v| v.parse().unwrap())
+                .collect()
+        };
+        let mut curve = Curve::new();
+        curve.set_jitter(0.25, 0.1).set_jitter_seed(42);
+        curve.draw(x, y);
+        let (xx, yy) = (values(&curve.buffer, "x"), values(&curve.buffer, "y"));
+        assert_eq!(xx.len(), 6);
+        for i in 0..6 {
+            assert!(xx[i] != x[i] && f64::abs(xx[i] - x[i]) <= 0.25);
+            assert!(yy[i] != y[i] && f64::abs(yy[i] - y[i]) <= 0.1);
+        }
+
+        // reproducible
+        let mut other = Curve::new();
+        other.set_jitter(0.25, 0.1).set_jitter_seed(42);
+        other.draw(x, y);
+        assert_eq!(other.buffer, curve.buffer);
+
+        // no jitter
+        let mut curve = Curve::new();
+        curve.set_jitter(0.0, 0.0).draw(x, y);
+        assert!(curve
+            .buffer
+            .starts_with("x=np.array([1,1,1,2,2,2,],dtype=float)\ny=np.array([5,5,5,6,6,6,],dtype=float)\n"));
+    }
+
+    #[test]
+    fn dedup_arrays_works() {
+        let x = &[1.0, 2.0, 3.0];
+        let mut curve = Curve::new();
+        curve.set_dedup_arrays(true);
+        curve.draw(x, &[4.0, 5.0, 6.0]);
+        curve.draw(x, &[1.0, 2.0, 3.0]);
+        let b: &str = "da0=np.array([1,2,3,],dtype=float)\n\
+                       x=da0\n\
+                       da1=np.array([4,5,6,],dtype=float)\n\
+                       y=da1\n\
+                       plt.plot(x,y)\n\
+                       x=da0\n\
+                       y=da0\n\
+                       plt.plot(x,y)\n";
+        assert_eq!(curve.buffer, b);
+        assert_eq!(curve.buffer.matches("np.array([1,2,3,]").count(), 1);
+
+        // the arrays are written again after clearing the buffer
+        curve.clear_buffer();
+        curve.draw(x, x);
+        assert_eq!(
+            curve.buffer,
+            "da0=np.array([1,2,3,],dtype=float)\nx=da0\ny=da0\nplt.plot(x,y)\n"
+        );
+
+        // disabled (default)
+        curve.clear_buffer();
+        curve.set_dedup_arrays(false);
+        curve.draw(x, x);
+        assert_eq!(curve.buffer.matches("np.array([1,2,3,]").count(), 2);
+    }
+
+    #[test]
+    fn draw_named_works() {
+        let mut curve = Curve::new();
+        curve.draw_named("Alice's data", &[1, 2], &[3, 4]);
+        curve.draw_named("second", &[5, 6], &[7, 8]);
+        let b: &str = "x=np.array([1,2,],dtype=float)\n\
+                       y=np.array([3,4,],dtype=float)\n\
+                       plt.plot(x,y,label=r'Alice’s data')\n\
+                       x=np.array([5,6,],dtype=float)\n\
+                       y=np.array([7,8,],dtype=float)\n\
+                       plt.plot(x,y,label=r'second')\n";
+        assert_eq!(curve.buffer, b);
+        assert_eq!(curve.label, "second");
+    }
+
+    #[test]
+    fn draw_time_works() {
+        let mut curve = Curve::new();
+        let t = [
+            Duration::from_millis(500),
+            Duration::from_secs(1),
+            Duration::from_secs(2),
+        ];
+        curve.draw_time(&t, &[1.0, 2.0, 3.0]);
+        let b: &str = "x=np.array([0.5,1,2,],dtype=float)\n\
+                       y=np.array([1,2,3,],dtype=float)\n\
+                       plt.plot(x,y)\n";
+        assert_eq!(curve.buffer, b);
+        assert_eq!(curve.data_bounds(), Some((0.5, 2.0, 1.0, 3.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn draw_with_gaps_works() {
+        let mut curve = Curve::new();
+        curve.draw_with_gaps(&[1.0, 2.0, 3.0], &[Some(1.0), None, Some(3.0)]);
+        let b: &str = "x=np.array([1,2,3,],dtype=float)\n\
+                       y=np.array([1,np.nan,3,],dtype=float)\n\
+                       plt.plot(x,y)\n";
+        assert_eq!(curve.buffer, b);
+        assert_eq!(curve.data_bounds(), Some((1.0, 3.0, 1.0, 3.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn draw_with_gap_markers_works() {
+        let mut curve = Curve::new();
+        curve.set_gap_markers("x", "red");
+        curve.draw(&[0.0, 1.0, 2.0], &[0.0, 1.0, 2.0]);
+        assert!(!curve.buffer.contains("gx"));
+        curve.clear_buffer();
+        curve.draw(&[0.0, 1.0, 2.0, 3.0], &[0.0, f64::NAN, 2.0, 3.0]);
+        let b: &str = "x=np.array([0,1,2,3,],dtype=float)\n\
+                       y=np.array([0,np.nan,2,3,],dtype=float)\n\
+                       plt.plot(x,y)\n\
+                       gx=np.array([0,2,],dtype=float)\n\
+                       gy=np.array([0,2,],dtype=float)\n\
+                       plt.plot(gx,gy,linestyle='none',marker='x',color='red')\n";
+        assert_eq!(curve.buffer, b);
+        curve.clear_buffer();
+        curve.set_gap_markers("o", Color::Rgba(0.0, 0.0, 0.0, 0.5));
+        curve.draw(&[0.0, 1.0, 2.0], &[0.0, f64::NAN, 2.0]);
+        assert!(curve
+            .buffer
+            .ends_with("plt.plot(gx,gy,linestyle='none',marker='o',color=(0,0,0,0.5))\n"));
+    }
+
+    #[test]
+    fn draw_scatter_3d_works() {
+        let mut curve = Curve::new();
+        curve.set_marker_style("^");
+        curve.draw_scatter_3d(&[1.0, 2.0], &[3.0, 4.0], &[5.0, 6.0]);
+        let b: &str = "x=np.array([1,2,],dtype=float)\n\
+                       y=np.array([3,4,],dtype=float)\n\
+                       z=np.array([5,6,],dtype=float)\n\
+                       ax3d().scatter(x,y,z,facecolors='C0',edgecolors='C0',marker='^')\n";
+        assert_eq!(curve.buffer, b);
+        assert_eq!(curve.data_bounds(), Some((1.0, 2.0, 3.0, 4.0, 5.0, 6.0)));
+    }
+
+    #[test]
+    fn draw_with_mask_works() {
+        let x: &[f64] = &[1.0, 2.0, 3.0];
+        let y: &[f64] = &[4.0, 5.0, 6.0];
+        let w: &[f64] = &[4.0, 5.0];
+        let mut curve = Curve::new();
+        assert_eq!(
+            curve.draw_with_mask(&x, &w, &[true, false, true]).err(),
+            Some("x and y arrays must have the same length")
+        );
+        assert_eq!(
+            curve.draw_with_mask(&x, &y, &[true, false]).err(),
+            Some("filled and x arrays must have the same length")
+        );
+        curve.set_label("selected").set_marker_size(5.0);
+        curve.draw_with_mask(&x, &y, &[true, false, true]).unwrap();
+        let b: &str = "x=np.array([1,2,3,],dtype=float)\n\
+                       y=np.array([4,5,6,],dtype=float)\n\
+                       filled=np.array([True,False,True,],dtype=bool)\n\
+                       plt.scatter(x,y,facecolors=['C0' if f else 'none' for f in filled],label=r'selected',edgecolors='C0',s=25)\n";
+        assert_eq!(curve.buffer, b);
+        curve.clear_buffer();
+        curve
+            .set_line_color("red")
+            .set_marker_line_color("black")
+            .set_marker_style("s");
+        curve.draw_with_mask(&x, &y, &[false, false, true]).unwrap();
+        assert!(curve.buffer.contains(
+            "plt.scatter(x,y,facecolors=['red' if f else 'none' for f in filled],label=r'selected',edgecolors='black',s=25,marker='s')\n"
+        ));
+    }
+
+    #[test]
+    fn draw_works_with_integers() {
+        let x: &[usize] = &[0, 1, 2];
+        let y: &[usize] = &[3, 4, 5];
+        let mut curve = Curve::new();
+        curve.draw(&x, &y);
+        assert_eq!(
+            curve.buffer,
+            "x=np.array([0,1,2,],dtype=float)\n\
+             y=np.array([3,4,5,],dtype=float)\n\
+             plt.plot(x,y)\n"
+        );
+        assert_eq!(curve.data_bounds(), Some((0.0, 2.0, 3.0, 5.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn points_in_box_works() {
+        let mut curve = Curve::new();
+        curve.draw(&[0.0, 0.5, 2.0], &[0.0, 0.5, 0.5]);
+        assert_eq!(curve.points, None);
+        assert_eq!(curve.points_in_box(0.0, 1.0, 0.0, 1.0).len(), 0);
+        curve.clear_buffer();
+        curve.set_keep_points(true);
+        assert_eq!(curve.points_in_box(0.0, 1.0, 0.0, 1.0).len(), 0);
+        curve.draw(&[0.0, 0.5, 2.0], &[0.0, 0.5, 0.5]);
+        curve.draw_with_mask(&[1.0, 0.5], &[1.0, 3.0], &[true, false]).unwrap();
+        assert_eq!(curve.points_in_box(0.0, 1.0, 0.0, 1.0), &[0, 1, 3]);
+        assert_eq!(curve.points_in_box(0.25, 2.0, 0.25, 0.75), &[1, 2]);
+        curve.clear_buffer();
+        assert_eq!(curve.points_in_box(0.0, 1.0, 0.0, 1.0).len(), 0);
+    }
+
+    #[test]
+    fn data_bounds_works() {
+        let mut curve = Curve::new();
+        assert_eq!(curve.data_bounds(), None);
+        curve.draw(&[1.0, 2.0, 3.0], &[-1.0, 5.0, 0.0]);
+        assert_eq!(curve.data_bounds(), Some((1.0, 3.0, -1.0, 5.0, 0.0, 0.0)));
+        curve.draw_3d(&[0.0, 1.0], &[0.0, 1.0], &[-2.0, 2.0]);
+        assert_eq!(curve.data_bounds(), Some((0.0, 3.0, -1.0, 5.0, -2.0, 2.0)));
+        curve.clear_buffer();
+        assert_eq!(curve.data_bounds(), None);
+    }
+
     #[test]
     fn draw_3d_works() {
         let x = &[1.0, 2.0, 3.0, 4.0, 5.0];
@@ -1,7 +1,7 @@
 use super::{StrError, PYTHON_HEADER};
 use std::fs;
 use std::fs::File;
-use std::io::Write;
+use std::io::{ErrorKind, Write};
 use std::path::Path;
 use std::process::Command;
 
@@ -35,10 +35,18 @@ pub(crate) fn call_python3(python_exe: &str, python_commands: &String, path: &Pa
     file.sync_all().map_err(|_| "cannot sync file")?;
 
     // execute file
+    run_python3(python_exe, path)
+}
+
+/// Calls python3 on an existing python file and returns its output (stdout and stderr)
+pub(crate) fn run_python3(python_exe: &str, path: &Path) -> Result<String, StrError> {
     let output = Command::new(python_exe)
         .arg(path)
         .output()
-        .map_err(|_| "cannot run python3")?;
+        .map_err(|e| match e.kind() {
+            ErrorKind::NotFound => "cannot find the python executable; check the PATH or use set_python_exe",
+            _ => "cannot run python3",
+        })?;
 
     // results
     let out = String::from_utf8(output.stdout).unwrap();
@@ -109,4 +117,14 @@ mod tests {
         assert_eq!(data_second, correct_second);
         assert_eq!(output_second, "Python says: Hello World! again\n");
     }
+
+    #[test]
+    fn call_python3_handles_missing_executable() {
+        let commands = "print(\"Hello\")".to_string();
+        let path = Path::new(OUT_DIR).join("call_python3_handles_missing_executable.py");
+        assert_eq!(
+            call_python3("/tmp/plotpy/does/not/exist/python3", &commands, &path).err(),
+            Some("cannot find the python executable; check the PATH or use set_python_exe")
+        );
+    }
 }
@@ -1,6 +1,12 @@
-use super::{generate_list_quoted, generate_nested_list, GraphMaker};
+use super::{
+    generate_list_literal, generate_list_quoted, generate_nested_list, linspace, to_f64, ArrayFormat, AsF64, Color,
+    GraphMaker,
+};
 use std::fmt::Write;
 
+/// Line styles of the percentile lines (cycled)
+const PERCENTILE_LINE_STYLES: [&str; 3] = ["--", ":", "-."];
+
 /// Generates a Histogram plot
 ///
 /// [See Matplotlib's documentation](https://matplotlib.org/stable/api/_as_gen/matplotlib.pyplot.hist.html)
@@ -49,14 +55,21 @@ use std::fmt::Write;
 ///
 /// ![integ_histogram_1.svg](https://raw.githubusercontent.com/cpmech/plotpy/main/figures/integ_histogram_1.svg)
 pub struct Histogram {
-    colors: Vec<String>, // Colors for each bar
-    line_width: f64,     // Line width
-    style: String,       // Type of histogram; e.g. "bar"
-    stacked: bool,       // Draws stacked histogram
-    no_fill: bool,       // Skip filling bars
-    number_bins: usize,  // Number of bins
-    extra: String,       // Extra commands (comma separated)
-    buffer: String,      // buffer
+    colors: Vec<String>,         // Colors for each bar (Python literals)
+    line_width: f64,             // Line width
+    style: String,               // Type of histogram; e.g. "bar"
+    stacked: bool,               // Draws stacked histogram
+    no_fill: bool,               // Skip filling bars
+    horizontal: bool,            // Draws horizontal bars
+    log_counts: bool,            // Uses a log scale for the counts axis
+    number_bins: usize,          // Number of bins
+    extra: String,               // Extra commands (comma separated)
+    percentiles: Vec<f64>,       // Percentiles (in [0, 100]) drawn as lines over the histogram
+    percentile_values: Vec<f64>, // Values of the percentiles computed by draw
+    bin_edges: Vec<f64>,         // Bin edges computed by draw
+    counts: Vec<Vec<usize>>,     // Counts for each series computed by draw
+    array_format: ArrayFormat,   // Format of the numbers in the generated arrays
+    buffer: String,              // buffer
 }
 
 impl Histogram {
@@ -68,8 +81,15 @@ impl Histogram {
             style: String::new(),
             stacked: false,
             no_fill: false,
+            horizontal: false,
+            log_counts: false,
             number_bins: 0,
             extra: String::new(),
+            percentiles: Vec::new(),
+            percentile_values: Vec::new(),
+            bin_edges: Vec::new(),
+            counts: Vec::new(),
+            array_format: ArrayFormat::new(),
             buffer: String::new(),
         }
     }
@@ -85,24 +105,68 @@ impl Histogram {
     ///
     /// * The type `T` must be a number.
     /// * The type `U` must be a String or &str.
+    /// * The bin edges and counts are also computed (in Rust) and can be retrieved
+    ///   with [Histogram::bin_edges] and [Histogram::counts].
     ///
     pub fn draw<T, U>(&mut self, values: &Vec<Vec<T>>, labels: &[U])
     where
-        T: std::fmt::Display,
+        T: std::fmt::Display + AsF64,
         U: std::fmt::Display,
     {
+        self.calc_bins_and_counts(values);
         let opt = self.options();
-        generate_nested_list(&mut self.buffer, "values", values);
-        generate_list_quoted(&mut self.buffer, "labels", labels);
+        generate_nested_list(&mut self.buffer, &self.array_format, "values", values);
+        generate_list_quoted(&mut self.buffer, &self.array_format, "labels", labels);
         if self.colors.len() > 0 {
-            generate_list_quoted(&mut self.buffer, "colors", self.colors.as_slice());
+            generate_list_literal(&mut self.buffer, &self.array_format, "colors", self.colors.as_slice());
         }
         write!(&mut self.buffer, "plt.hist(values,label=labels{})\n", &opt).unwrap();
+        self.calc_percentiles(values);
+        let command = if self.horizontal { "plt.axhline" } else { "plt.axvline" };
+        for (k, (p, v)) in self.percentiles.iter().zip(&self.percentile_values).enumerate() {
+            write!(
+                &mut self.buffer,
+                "{}({},color='black',linestyle='{}',linewidth=1,label=r'P{} = {}')\n",
+                command,
+                v,
+                PERCENTILE_LINE_STYLES[k % PERCENTILE_LINE_STYLES.len()],
+                p,
+                v
+            )
+            .unwrap();
+        }
+    }
+
+    /// Returns the values of the percentiles computed by the last call to [Histogram::draw]
+    ///
+    /// The percentiles are given by [Histogram::set_percentile_lines] and computed from all series.
+    pub fn percentile_values(&self) -> &[f64] {
+        &self.percentile_values
+    }
+
+    /// Returns the bin edges computed by the last call to [Histogram::draw]
+    ///
+    /// The number of edges is equal to the number of bins plus one.
+    pub fn bin_edges(&self) -> &[f64] {
+        &self.bin_edges
+    }
+
+    /// Returns the counts (one list for each series) computed by the last call to [Histogram::draw]
+    ///
+    /// **Note:** The counts are not accumulated, even if the stacked option is set.
+    /// Non-finite values (NaN and infinity) are not counted.
+    pub fn counts(&self) -> &[Vec<usize>] {
+        &self.counts
     }
 
     /// Sets the colors for each bar
-    pub fn set_colors(&mut self, colors: &[&str]) -> &mut Self {
-        self.colors = colors.iter().map(|color| color.to_string()).collect();
+    ///
+    /// The colors may be given by name (e.g., "red" or "#ff0000") or as [Color]s
+    pub fn set_colors<C>(&mut self, colors: &[C]) -> &mut Self
+    where
+        C: Into<Color> + Clone,
+    {
+        self.colors = colors.iter().map(|color| color.clone().into().to_python()).collect();
         self
     }
 
@@ -138,6 +202,36 @@ impl Histogram {
         self
     }
 
+    /// Sets option to draw a horizontal histogram
+    ///
+    /// **Note:** The values are then shown along the y axis and the counts along the x axis;
+    /// thus, the axis labels should be swapped, e.g., `plot.grid_and_labels("count", "values")`
+    pub fn set_horizontal(&mut self, flag: bool) -> &mut Self {
+        self.horizontal = flag;
+        self
+    }
+
+    /// Sets option to use a log scale for the counts axis
+    ///
+    /// This reveals bins with small counts next to dominant ones. The counts axis is the
+    /// y axis, or the x axis if [Histogram::set_horizontal] is enabled. Empty bins are not shown.
+    ///
+    /// **Note:** This option also works with normalized counts, e.g., via `set_extra("density=True")`.
+    pub fn set_log_counts(&mut self, flag: bool) -> &mut Self {
+        self.log_counts = flag;
+        self
+    }
+
+    /// Sets the percentiles (in [0, 100]) to be drawn as labeled lines over the histogram
+    ///
+    /// For example, `&[25.0, 50.0, 75.0]` draws the quartiles and the median. The percentiles are
+    /// computed from the data of all series (with linear interpolation, as NumPy's default) and
+    /// non-finite values are ignored. Values outside [0, 100] are clamped.
+    pub fn set_percentile_lines(&mut self, percentiles: &[f64]) -> &mut Self {
+        self.percentiles = percentiles.iter().map(|p| p.clamp(0.0, 100.0)).collect();
+        self
+    }
+
     /// Sets the number of bins
     pub fn set_number_bins(&mut self, bins: usize) -> &mut Self {
         self.number_bins = bins;
@@ -158,6 +252,86 @@ impl Histogram {
         self
     }
 
+    /// Sets the format of the numbers in the generated lists and arrays
+    pub fn set_array_format(&mut self, format: &ArrayFormat) -> &mut Self {
+        self.array_format = format.clone();
+        self
+    }
+
+    /// Computes the bin edges and counts following the same rules as numpy.histogram
+    ///
+    /// Non-finite values are skipped.
+    fn calc_bins_and_counts<T>(&mut self, values: &Vec<Vec<T>>)
+    where
+        T: std::fmt::Display + AsF64,
+    {
+        // range of all series
+        let mut first = f64::MAX;
+        let mut last = f64::MIN;
+        for series in values {
+            for x in series.iter().map(to_f64).filter(|x| x.is_finite()) {
+                first = f64::min(first, x);
+                last = f64::max(last, x);
+            }
+        }
+        if first > last {
+            first = 0.0;
+            last = 1.0;
+        }
+        if first == last {
+            first -= 0.5;
+            last += 0.5;
+        }
+
+        // bin edges
+        let nbins = if self.number_bins > 0 { self.number_bins } else { 10 };
+        self.bin_edges = linspace(first, last, nbins + 1);
+
+        // counts
+        self.counts = vec![vec![0; nbins]; values.len()];
+        for (k, series) in values.iter().enumerate() {
+            for x in series.iter().map(to_f64).filter(|x| x.is_finite()) {
+                let mut i = (((x - first) / (last - first)) * (nbins as f64)) as usize;
+                if i >= nbins {
+                    i = nbins - 1; // the last bin includes the right edge
+                }
+                if i > 0 && x < self.bin_edges[i] {
+                    i -= 1;
+                } else if i < nbins - 1 && x >= self.bin_edges[i + 1] {
+                    i += 1;
+                }
+                self.counts[k][i] += 1;
+            }
+        }
+    }
+
+    /// Computes the values of the percentiles using all series
+    fn calc_percentiles<T>(&mut self, values: &Vec<Vec<T>>)
+    where
+        T: std::fmt::Display + AsF64,
+    {
+        self.percentile_values.clear();
+        if self.percentiles.len() == 0 {
+            return;
+        }
+        let mut data: Vec<f64> = values.iter().flatten().map(to_f64).filter(|x| x.is_finite()).collect();
+        if data.len() == 0 {
+            return;
+        }
+        data.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let last = (data.len() - 1) as f64;
+        for p in &self.percentiles {
+            let rank = p / 100.0 * last;
+            let (i, frac) = (rank.floor() as usize, rank - rank.floor());
+            let value = if i + 1 < data.len() {
+                data[i] + frac * (data[i + 1] - data[i])
+            } else {
+                data[i]
+            };
+            self.percentile_values.push(value);
+        }
+    }
+
     /// Returns options for histogram
     fn options(&self) -> String {
         let mut opt = String::new();
@@ -179,6 +353,12 @@ impl Histogram {
         if self.number_bins > 0 {
             write!(&mut opt, ",bins={}", self.number_bins).unwrap();
         }
+        if self.horizontal {
+            write!(&mut opt, ",orientation='horizontal'").unwrap();
+        }
+        if self.log_counts {
+            write!(&mut opt, ",log=True").unwrap();
+        }
         if self.extra != "" {
             write!(&mut opt, ",{}", self.extra).unwrap();
         }
@@ -200,7 +380,7 @@ impl GraphMaker for Histogram {
 #[cfg(test)]
 mod tests {
     use super::Histogram;
-    use crate::GraphMaker;
+    use crate::{Color, GraphMaker};
 
     #[test]
     fn new_works() {
@@ -210,7 +390,11 @@ mod tests {
         assert_eq!(histogram.style.len(), 0);
         assert_eq!(histogram.stacked, false);
         assert_eq!(histogram.no_fill, false);
+        assert_eq!(histogram.horizontal, false);
+        assert_eq!(histogram.log_counts, false);
         assert_eq!(histogram.number_bins, 0);
+        assert_eq!(histogram.bin_edges.len(), 0);
+        assert_eq!(histogram.counts.len(), 0);
         assert_eq!(histogram.buffer.len(), 0);
     }
 
@@ -223,7 +407,8 @@ mod tests {
             .set_style("step")
             .set_stacked(true)
             .set_no_fill(true)
-            .set_number_bins(8);
+            .set_number_bins(8)
+            .set_horizontal(true);
         let opt = histogram.options();
         assert_eq!(
             opt,
@@ -232,8 +417,12 @@ mod tests {
              ,histtype='step'\
              ,stacked=True\
              ,fill=False\
-             ,bins=8"
+             ,bins=8\
+             ,orientation='horizontal'"
         );
+        let mut histogram = Histogram::new();
+        histogram.set_log_counts(true).set_extra("density=True");
+        assert_eq!(histogram.options(), ",log=True,density=True");
     }
 
     #[test]
@@ -250,5 +439,78 @@ mod tests {
         assert_eq!(histogram.buffer, b);
         histogram.clear_buffer();
         assert_eq!(histogram.buffer, "");
+        histogram.set_colors(&[Color::Rgb(1.0, 0.0, 0.0), Color::from("#00ff00")]);
+        histogram.draw(&values, &labels);
+        assert!(histogram.buffer.contains("colors=[(1,0,0),'#00ff00',]\n"));
+    }
+
+    #[test]
+    fn percentile_lines_work() {
+        let values = vec![vec![1.0, 2.0, 3.0, 4.0], vec![5.0, f64::NAN]];
+        let labels = ["first", "second"];
+        let mut histogram = Histogram::new();
+        histogram.draw(&values, &labels);
+        assert_eq!(histogram.percentile_values().len(), 0);
+        assert!(!histogram.buffer.contains("axvline"));
+
+        histogram.clear_buffer();
+        histogram
+            .set_percentile_lines(&[50.0, 25.0, 110.0])
+            .draw(&values, &labels);
+        assert_eq!(histogram.percentile_values(), &[3.0, 2.0, 5.0]);
+        assert!(histogram.buffer.ends_with(
+            "plt.axvline(3,color='black',linestyle='--',linewidth=1,label=r'P50 = 3')\n\
+             plt.axvline(2,color='black',linestyle=':',linewidth=1,label=r'P25 = 2')\n\
+             plt.axvline(5,color='black',linestyle='-.',linewidth=1,label=r'P100 = 5')\n"
+        ));
+
+        histogram.clear_buffer();
+        histogram.set_percentile_lines(&[10.0]).set_horizontal(true);
+        histogram.draw(&vec![vec![0, 10, 20]], &["data"]);
+        assert_eq!(histogram.percentile_values(), &[2.0]);
+        assert!(histogram
+            .buffer
+            .ends_with("plt.axhline(2,color='black',linestyle='--',linewidth=1,label=r'P10 = 2')\n"));
+    }
+
+    #[test]
+    fn bin_edges_and_counts_work() {
+        let values = vec![vec![1, 1, 1, 2, 2, 2, 2, 2, 3, 3], vec![5, 6, 7, 8]];
+        let labels = ["first", "second"];
+        let mut histogram = Histogram::new();
+        histogram.set_number_bins(7).draw(&values, &labels);
+        assert_eq!(histogram.bin_edges(), &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]);
+        assert_eq!(
+            histogram.counts(),
+            &[vec![3, 5, 2, 0, 0, 0, 0], vec![0, 0, 0, 0, 1, 1, 2]]
+        );
+
+        // default number of bins
+        let values = vec![vec![0.0, 0.05, 0.5, 1.0]];
+        histogram.set_number_bins(0).draw(&values, &labels);
+        assert_eq!(histogram.bin_edges().len(), 11);
+        assert_eq!(histogram.counts(), &[vec![2, 0, 0, 0, 0, 1, 0, 0, 0, 1]]);
+
+        // single value
+        let values = vec![vec![2.0, 2.0]];
+        histogram.set_number_bins(2).draw(&values, &labels);
+        assert_eq!(histogram.bin_edges(), &[1.5, 2.0, 2.5]);
+        assert_eq!(histogram.counts(), &[vec![0, 2]]);
+    }
+
+    #[test]
+    fn bin_edges_and_counts_skip_non_finite_values() {
+        let values = vec![vec![f64::NAN, 0.0, 1.0, f64::INFINITY, 2.0, f64::NEG_INFINITY]];
+        let labels = ["first"];
+        let mut histogram = Histogram::new();
+        histogram.set_number_bins(2).draw(&values, &labels);
+        assert_eq!(histogram.bin_edges(), &[0.0, 1.0, 2.0]);
+        assert_eq!(histogram.counts(), &[vec![1, 2]]);
+
+        // only non-finite values
+        let values = vec![vec![f64::NAN, f64::INFINITY]];
+        histogram.draw(&values, &labels);
+        assert_eq!(histogram.bin_edges(), &[0.0, 0.5, 1.0]);
+        assert_eq!(histogram.counts(), &[vec![0, 0]]);
     }
 }
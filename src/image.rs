@@ -1,4 +1,4 @@
-use super::{matrix_to_array, AsMatrix, GraphMaker};
+use super::{matrix_to_array, ArrayFormat, AsMatrix, GraphMaker};
 use std::fmt::Write;
 
 /// Generates an image plot (imshow)
@@ -38,9 +38,10 @@ use std::fmt::Write;
 ///
 /// See also integration test in the **tests** directory.
 pub struct Image {
-    colormap_name: String, // Colormap name
-    extra: String,         // Extra commands (comma separated)
-    buffer: String,        // buffer
+    colormap_name: String,     // Colormap name
+    extra: String,             // Extra commands (comma separated)
+    array_format: ArrayFormat, // Format of the numbers in the generated arrays
+    buffer: String,            // buffer
 }
 
 impl Image {
@@ -49,6 +50,7 @@ impl Image {
         Image {
             colormap_name: String::new(),
             extra: String::new(),
+            array_format: ArrayFormat::new(),
             buffer: String::new(),
         }
     }
@@ -63,7 +65,7 @@ impl Image {
         T: AsMatrix<'a, U>,
         U: 'a + std::fmt::Display,
     {
-        matrix_to_array(&mut self.buffer, "data", data);
+        matrix_to_array(&mut self.buffer, &self.array_format, "data", data);
         let opt = self.options();
         write!(&mut self.buffer, "plt.imshow(data{})\n", &opt).unwrap();
     }
@@ -104,6 +106,12 @@ impl Image {
         self
     }
 
+    /// Sets the format of the numbers in the generated lists and arrays
+    pub fn set_array_format(&mut self, format: &ArrayFormat) -> &mut Self {
+        self.array_format = format.clone();
+        self
+    }
+
     /// Returns options for barplot
     fn options(&self) -> String {
         let mut opt = String::new();
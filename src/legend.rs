@@ -1,4 +1,4 @@
-use super::{generate_list, GraphMaker};
+use super::{generate_list, ArrayFormat, Color, GraphMaker};
 use std::fmt::Write;
 
 /// Generates a Legend
@@ -68,15 +68,20 @@ use std::fmt::Write;
 ///
 /// See also integration tests in the [tests directory](https://github.com/cpmech/plotpy/tree/main/tests)
 pub struct Legend {
-    fontsize: f64,      // Fontsize
-    handle_len: f64,    // Length of legend's indicator line
-    num_col: usize,     // Number of columns
-    location: String,   // Location, e.g., "best", "right", "center left"
-    outside: bool,      // Put legend outside plot area
-    show_frame: bool,   // Show frame around legend
-    x_coords: Vec<f64>, // Normalized coordinates to put legend outside
-    extra: String,      // Extra commands (comma separated)
-    buffer: String,     // buffer
+    fontsize: f64,                      // Fontsize
+    handle_len: f64,                    // Length of legend's indicator line
+    num_col: usize,                     // Number of columns
+    location: String,                   // Location, e.g., "best", "right", "center left"
+    outside: bool,                      // Put legend outside plot area
+    show_frame: bool,                   // Show frame around legend
+    frame_alpha: f64,                   // Opacity of the frame (0, 1]
+    frame_edge_color: String,           // Color of the frame edge
+    frame_face_color: String,           // Color of the frame background
+    x_coords: Vec<f64>,                 // Normalized coordinates to put legend outside
+    extra: String,                      // Extra commands (comma separated)
+    groups: Vec<(String, Vec<String>)>, // Grouped entries (title and labels)
+    array_format: ArrayFormat,          // Format of the numbers in the generated arrays
+    buffer: String,                     // buffer
 }
 
 impl Legend {
@@ -89,8 +94,13 @@ impl Legend {
             location: "best".to_string(),
             outside: false,
             show_frame: true,
+            frame_alpha: 0.0,
+            frame_edge_color: String::new(),
+            frame_face_color: String::new(),
             x_coords: vec![0.0, 1.02, 1.0, 0.102],
             extra: String::new(),
+            groups: Vec::new(),
+            array_format: ArrayFormat::new(),
             buffer: String::new(),
         }
     }
@@ -99,17 +109,68 @@ impl Legend {
     pub fn draw(&mut self) {
         let opt = self.options();
         if self.outside {
-            generate_list(&mut self.buffer, "coo", self.x_coords.as_slice());
+            generate_list(&mut self.buffer, &self.array_format, "coo", self.x_coords.as_slice());
         }
         write!(&mut self.buffer, "h,l=plt.gca().get_legend_handles_labels()\n").unwrap();
+        if self.groups.len() > 0 {
+            write!(&mut self.buffer, "hl=dict(zip(l,h))\n").unwrap();
+            write!(&mut self.buffer, "h,l,headers=[],[],[]\n").unwrap();
+            for (title, entries) in &self.groups {
+                write!(
+                    &mut self.buffer,
+                    "h.append(lns.Line2D([],[],linestyle='None',marker='None'))\n\
+                     l.append(r'{}')\n\
+                     headers.append(len(l)-1)\n",
+                    title
+                )
+                .unwrap();
+                write!(&mut self.buffer, "for e in [").unwrap();
+                for entry in entries {
+                    write!(&mut self.buffer, "r'{}',", entry).unwrap();
+                }
+                write!(&mut self.buffer, "]:\n").unwrap();
+                write!(&mut self.buffer, "    if e in hl: h.append(hl[e]); l.append(e)\n").unwrap();
+            }
+        }
         write!(&mut self.buffer, "if len(h)>0 and len(l)>0:\n").unwrap();
-        write!(&mut self.buffer, "    leg=plt.legend({})\n", &opt).unwrap();
+        if self.groups.len() > 0 {
+            write!(&mut self.buffer, "    leg=plt.legend(h,l,{})\n", &opt).unwrap();
+            write!(
+                &mut self.buffer,
+                "    for i in headers: leg.get_texts()[i].set_weight('bold')\n"
+            )
+            .unwrap();
+        } else {
+            write!(&mut self.buffer, "    leg=plt.legend({})\n", &opt).unwrap();
+        }
         write!(&mut self.buffer, "    add_to_ea(leg)\n").unwrap();
         if !self.show_frame {
             write!(&mut self.buffer, "    leg.get_frame().set_linewidth(0.0)\n").unwrap();
         }
     }
 
+    /// Adds a group of entries with a title (header) to the legend
+    ///
+    /// # Input
+    ///
+    /// * `title` -- the title of the group, drawn in bold without an indicator (handle)
+    /// * `entries` -- the labels of the previously drawn graphs belonging to this group
+    ///
+    /// # Notes
+    ///
+    /// * If at least one group is added, only the grouped entries are shown, in the order the groups were added.
+    /// * Labels that do not correspond to a drawn graph are ignored.
+    /// * Single quotes in the title and entries are replaced by `’`, as the labels of the graphs are
+    ///   (e.g., by [crate::Curve::set_label]); thus, the entries match the labels.
+    /// * This function must be called before [Legend::draw()].
+    pub fn add_group(&mut self, title: &str, entries: &[&str]) -> &mut Self {
+        self.groups.push((
+            title.replace("'", "’"),
+            entries.iter().map(|entry| entry.replace("'", "’")).collect(),
+        ));
+        self
+    }
+
     /// Sets the fontsize
     pub fn set_fontsize(&mut self, fontsize: f64) -> &mut Self {
         self.fontsize = fontsize;
@@ -151,6 +212,36 @@ impl Legend {
         self
     }
 
+    /// Sets the opacity of the frame (background) in (0, 1]
+    ///
+    /// A semi-transparent frame keeps the data behind the legend visible.
+    pub fn set_frame_alpha(&mut self, alpha: f64) -> &mut Self {
+        self.frame_alpha = alpha;
+        self
+    }
+
+    /// Sets the color of the frame edge
+    ///
+    /// The color may be given by name (e.g., "red" or "#ff0000") or as a [Color]
+    pub fn set_frame_edge_color<C>(&mut self, color: C) -> &mut Self
+    where
+        C: Into<Color>,
+    {
+        self.frame_edge_color = color.into().to_python();
+        self
+    }
+
+    /// Sets the color of the frame background
+    ///
+    /// The color may be given by name (e.g., "red" or "#ff0000") or as a [Color]
+    pub fn set_frame_face_color<C>(&mut self, color: C) -> &mut Self
+    where
+        C: Into<Color>,
+    {
+        self.frame_face_color = color.into().to_python();
+        self
+    }
+
     /// Sets the normalized coordinates when drawing an outside legend
     ///
     /// Example: `[0.0, 1.02, 1.0, 0.102]`
@@ -173,6 +264,12 @@ impl Legend {
         self
     }
 
+    /// Sets the format of the numbers in the generated lists and arrays
+    pub fn set_array_format(&mut self, format: &ArrayFormat) -> &mut Self {
+        self.array_format = format.clone();
+        self
+    }
+
     /// Returns options for legend
     fn options(&self) -> String {
         let mut opt = String::new();
@@ -199,8 +296,20 @@ impl Legend {
         } else {
             if self.location != "" {
                 write!(&mut opt, "{}loc='{}'", comma, self.location).unwrap();
+                comma = ",";
             }
         }
+        if self.frame_alpha > 0.0 && self.frame_alpha <= 1.0 {
+            write!(&mut opt, "{}framealpha={}", comma, self.frame_alpha).unwrap();
+            comma = ",";
+        }
+        if self.frame_edge_color != "" {
+            write!(&mut opt, "{}edgecolor={}", comma, self.frame_edge_color).unwrap();
+            comma = ",";
+        }
+        if self.frame_face_color != "" {
+            write!(&mut opt, "{}facecolor={}", comma, self.frame_face_color).unwrap();
+        }
         if self.extra != "" {
             write!(&mut opt, ",{}", self.extra).unwrap();
         }
@@ -222,7 +331,7 @@ impl GraphMaker for Legend {
 #[cfg(test)]
 mod tests {
     use super::Legend;
-    use crate::GraphMaker;
+    use crate::{Color, GraphMaker};
 
     #[test]
     fn new_works() {
@@ -233,7 +342,11 @@ mod tests {
         assert_eq!(legend.location, "best".to_string());
         assert_eq!(legend.outside, false);
         assert_eq!(legend.show_frame, true);
+        assert_eq!(legend.frame_alpha, 0.0);
+        assert_eq!(legend.frame_edge_color, "");
+        assert_eq!(legend.frame_face_color, "");
         assert_eq!(legend.x_coords, vec![0.0, 1.02, 1.0, 0.102]);
+        assert_eq!(legend.groups.len(), 0);
         assert_eq!(legend.buffer.len(), 0);
     }
 
@@ -243,6 +356,19 @@ mod tests {
         legend.set_handle_len(6.0);
         let opt = legend.options();
         assert_eq!(opt, "handlelength=6,ncol=1,loc='best'");
+
+        let mut legend = Legend::new();
+        legend
+            .set_handle_len(0.0)
+            .set_num_col(0)
+            .set_location("")
+            .set_frame_alpha(0.5)
+            .set_frame_edge_color("gray")
+            .set_frame_face_color("#f0f0f0");
+        let opt = legend.options();
+        assert_eq!(opt, "framealpha=0.5,edgecolor='gray',facecolor='#f0f0f0'");
+        legend.set_frame_face_color(Color::Rgba(1.0, 1.0, 1.0, 0.5));
+        assert!(legend.options().ends_with(",facecolor=(1,1,1,0.5)"));
     }
 
     #[test]
@@ -257,4 +383,39 @@ mod tests {
         legend.clear_buffer();
         assert_eq!(legend.buffer, "");
     }
+
+    #[test]
+    fn draw_with_groups_works() {
+        let mut legend = Legend::new();
+        legend
+            .add_group("Group A", &["a1", "a2"])
+            .add_group("Group B", &["b1"])
+            .draw();
+        let b: &str = "h,l=plt.gca().get_legend_handles_labels()\n\
+                       hl=dict(zip(l,h))\n\
+                       h,l,headers=[],[],[]\n\
+                       h.append(lns.Line2D([],[],linestyle='None',marker='None'))\n\
+                       l.append(r'Group A')\n\
+                       headers.append(len(l)-1)\n\
+                       for e in [r'a1',r'a2',]:\n\
+                       \x20\x20\x20\x20if e in hl: h.append(hl[e]); l.append(e)\n\
+                       h.append(lns.Line2D([],[],linestyle='None',marker='None'))\n\
+                       l.append(r'Group B')\n\
+                       headers.append(len(l)-1)\n\
+                       for e in [r'b1',]:\n\
+                       \x20\x20\x20\x20if e in hl: h.append(hl[e]); l.append(e)\n\
+                       if len(h)>0 and len(l)>0:\n\
+                       \x20\x20\x20\x20leg=plt.legend(h,l,handlelength=3,ncol=1,loc='best')\n\
+                       \x20\x20\x20\x20for i in headers: leg.get_texts()[i].set_weight('bold')\n\
+                       \x20\x20\x20\x20add_to_ea(leg)\n";
+        assert_eq!(legend.buffer, b);
+    }
+
+    #[test]
+    fn add_group_replaces_quotes() {
+        let mut legend = Legend::new();
+        legend.add_group("Developer's group", &["Developer's data"]).draw();
+        assert!(legend.buffer.contains("l.append(r'Developer’s group')\n"));
+        assert!(legend.buffer.contains("for e in [r'Developer’s data',]:\n"));
+    }
 }
@@ -73,12 +73,15 @@
 pub type StrError = &'static str;
 
 // modules ////////////////////////////////////////
+mod animation;
+mod array_format;
 mod as_matrix;
 mod as_vector;
 mod auxiliary;
 mod barplot;
 mod boxplot;
 mod canvas;
+mod color;
 mod constants;
 mod contour;
 mod conversions;
@@ -93,15 +96,19 @@ mod super_title_params;
 mod surface;
 mod surface_geometry;
 mod text;
+pub use crate::animation::*;
+pub use crate::array_format::*;
 pub use crate::as_matrix::*;
 pub use crate::as_vector::*;
 pub use crate::auxiliary::*;
 pub use crate::barplot::*;
 pub use crate::boxplot::*;
 pub use crate::canvas::*;
+pub use crate::color::*;
 pub use crate::constants::*;
 pub use crate::contour::*;
 use crate::conversions::*;
+pub use crate::conversions::{write_vector, AsF64};
 pub use crate::curve::*;
 use crate::fileio::*;
 pub use crate::histogram::*;
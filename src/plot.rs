@@ -1,12 +1,88 @@
-use super::{call_python3, generate_list_quoted, vector_to_array, AsVector, Legend, StrError, SuperTitleParams};
+use super::{
+    call_python3, generate_list_quoted, run_python3, vector_to_array, Animation, ArrayFormat, AsF64, AsVector, Color,
+    Legend, StrError, SuperTitleParams, PYTHON_HEADER,
+};
 use std::ffi::OsStr;
 use std::fmt::Write;
 use std::fs::{self, File};
-use std::io::Write as IoWrite;
-use std::path::Path;
+use std::io::{BufWriter, Write as IoWrite};
+use std::path::{Path, PathBuf};
 
 const DEFAULT_PYTHON_EXE: &str = "python3";
 
+const DEFAULT_SAVE_AUTO_THRESHOLD: usize = 1_000_000;
+
+/// Defines the direction of ticks
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Direction {
+    /// Ticks inside the axes
+    In,
+
+    /// Ticks outside the axes (Matplotlib's default)
+    Out,
+
+    /// Ticks crossing the axes
+    InOut,
+}
+
+impl Direction {
+    /// Returns the Matplotlib keyword for this direction
+    fn as_str(&self) -> &'static str {
+        match self {
+            Direction::In => "in",
+            Direction::Out => "out",
+            Direction::InOut => "inout",
+        }
+    }
+}
+
+/// Defines a set of style presets (rcParams) for the whole figure
+///
+/// See [Plot::set_theme]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Theme {
+    /// Matplotlib's default style (no extra commands)
+    Default,
+
+    /// Light text and lines over a dark background
+    Dark,
+
+    /// Grid with light lines and no top/right spines
+    Minimal,
+
+    /// Small serif fonts, inward ticks on all sides, and high resolution
+    Publication,
+}
+
+impl Theme {
+    /// Returns the commands setting the rcParams of this theme
+    fn rc_params(&self) -> &'static str {
+        match self {
+            Theme::Default => "",
+            Theme::Dark => {
+                "plt.rcParams.update({'figure.facecolor':'#1e1e1e','axes.facecolor':'#1e1e1e',\
+                 'savefig.facecolor':'#1e1e1e','axes.edgecolor':'#bbbbbb','axes.labelcolor':'#e0e0e0',\
+                 'text.color':'#e0e0e0','xtick.color':'#bbbbbb','ytick.color':'#bbbbbb',\
+                 'axes.grid':True,'grid.color':'#444444','grid.linestyle':'--','legend.facecolor':'#2a2a2a',\
+                 'legend.edgecolor':'#555555','axes.prop_cycle':plt.cycler(color=['#4cc9f0','#f72585',\
+                 '#b5e48c','#ffb703','#b388eb','#ff8fa3'])})\n"
+            }
+            Theme::Minimal => {
+                "plt.rcParams.update({'axes.spines.top':False,'axes.spines.right':False,\
+                 'axes.edgecolor':'#666666','axes.grid':True,'grid.color':'#dddddd','grid.linestyle':'-',\
+                 'grid.linewidth':0.8,'axes.axisbelow':True,'xtick.color':'#666666','ytick.color':'#666666',\
+                 'legend.frameon':False})\n"
+            }
+            Theme::Publication => {
+                "plt.rcParams.update({'font.family':'serif','font.size':9,'axes.labelsize':9,\
+                 'axes.titlesize':10,'legend.fontsize':8,'xtick.labelsize':8,'ytick.labelsize':8,\
+                 'xtick.direction':'in','ytick.direction':'in','xtick.top':True,'ytick.right':True,\
+                 'axes.linewidth':0.8,'lines.linewidth':1.2,'axes.grid':False,'savefig.dpi':300})\n"
+            }
+        }
+    }
+}
+
 /// Defines the trait used by Plot to add graph entities
 pub trait GraphMaker {
     /// Returns the text buffer with Python3 commands
@@ -14,6 +90,37 @@ pub trait GraphMaker {
 
     /// Clear the text buffer with Python commands
     fn clear_buffer(&mut self);
+
+    /// Returns the bounds (xmin, xmax, ymin, ymax, zmin, zmax) of the drawn data, if known
+    ///
+    /// The default implementation returns None. The z bounds of 2D data are zero.
+    fn data_bounds(&self) -> Option<(f64, f64, f64, f64, f64, f64)> {
+        None
+    }
+}
+
+/// Extends the bounds (xmin, xmax, ymin, ymax, zmin, zmax) such that they contain the point (x, y, z)
+///
+/// NaN values are ignored.
+pub(crate) fn extend_bounds(bounds: &mut Option<(f64, f64, f64, f64, f64, f64)>, x: f64, y: f64, z: f64) {
+    let (xmin, xmax, ymin, ymax, zmin, zmax) =
+        bounds.unwrap_or((f64::NAN, f64::NAN, f64::NAN, f64::NAN, f64::NAN, f64::NAN));
+    *bounds = Some((
+        f64::min(xmin, x),
+        f64::max(xmax, x),
+        f64::min(ymin, y),
+        f64::max(ymax, y),
+        f64::min(zmin, z),
+        f64::max(zmax, z),
+    ));
+}
+
+/// Merges two (optional) bounds (xmin, xmax, ymin, ymax, zmin, zmax)
+fn merge_bounds(bounds: &mut Option<(f64, f64, f64, f64, f64, f64)>, other: Option<(f64, f64, f64, f64, f64, f64)>) {
+    if let Some((xmin, xmax, ymin, ymax, zmin, zmax)) = other {
+        extend_bounds(bounds, xmin, ymin, zmin);
+        extend_bounds(bounds, xmax, ymax, zmax);
+    }
 }
 
 /// Driver structure that calls Python
@@ -155,12 +262,26 @@ pub trait GraphMaker {
 ///
 /// See also integration tests in the [tests directory](https://github.com/cpmech/plotpy/tree/main/tests)
 pub struct Plot {
-    show_errors: bool,              // show python errors, if any
-    buffer: String,                 // buffer
-    save_tight: bool,               // option for savefig: enable bbox_inches='tight'
-    save_pad_inches: Option<f64>,   // option for savefig: add some padding when save_tight==true
-    save_transparent: Option<bool>, // option for savefig: make it transparent
-    python_exe: String,             // `python3` or simply `python` (e.g., on Windows)
+    show_errors: bool,                              // show python errors, if any
+    suppress_warnings: bool,                        // ignore the warnings issued by python (e.g., Matplotlib)
+    theme: Theme,                                   // rcParams: style preset of the whole figure
+    array_format: ArrayFormat,                      // Format of the numbers in the generated arrays
+    buffer: String,                                 // buffer
+    save_tight: bool,                               // option for savefig: enable bbox_inches='tight'
+    save_pad_inches: Option<f64>,                   // option for savefig: add some padding when save_tight==true
+    save_transparent: Option<bool>,                 // option for savefig: make it transparent
+    save_dpi: usize,                                // option for savefig: dots per inch (0 means unset)
+    figure_size: Option<(f64, f64)>,                // figure size in inches
+    figure_dpi: Option<f64>,                        // figure (display) dots per inch
+    python_exe: String,                             // `python3` or simply `python` (e.g., on Windows)
+    num_handles: usize,                             // number of graphs added with add_with_handle
+    default_line_width: Option<f64>,                // rcParams: default width of lines
+    default_marker_size: Option<f64>,               // rcParams: default size of markers
+    bounds: Option<(f64, f64, f64, f64, f64, f64)>, // bounds of the data in all added graphs
+    save_auto_threshold: usize,                     // approximate size above which save_auto generates a PNG file
+    stream: Option<BufWriter<File>>,                // writer of the python script in streaming mode
+    stream_figure_path: PathBuf,                    // path of the figure to be saved in streaming mode
+    stream_error: Option<StrError>,                 // first error that occurred while streaming
 }
 
 impl Plot {
@@ -168,17 +289,99 @@ impl Plot {
     pub fn new() -> Self {
         Plot {
             show_errors: false,
+            suppress_warnings: false,
+            theme: Theme::Default,
+            array_format: ArrayFormat::new(),
             buffer: String::new(),
             save_tight: true,
             save_pad_inches: None,
             save_transparent: None,
+            save_dpi: 0,
+            figure_size: None,
+            figure_dpi: None,
             python_exe: DEFAULT_PYTHON_EXE.to_string(),
+            num_handles: 0,
+            default_line_width: None,
+            default_marker_size: None,
+            bounds: None,
+            save_auto_threshold: DEFAULT_SAVE_AUTO_THRESHOLD,
+            stream: None,
+            stream_figure_path: PathBuf::new(),
+            stream_error: None,
         }
     }
 
     /// Adds new graph entity
+    ///
+    /// In streaming mode (see [Plot::begin_stream]), the commands are written to the script file
+    /// instead of being copied to the buffer of this plot.
     pub fn add(&mut self, graph: &dyn GraphMaker) -> &mut Self {
-        self.buffer.push_str(graph.get_buffer());
+        self.push_graph(graph.get_buffer());
+        merge_bounds(&mut self.bounds, graph.data_bounds());
+        self
+    }
+
+    /// Adds new graph entity and returns a handle to restyle it later (before saving)
+    ///
+    /// The handle collects all artists created by the graph in the current axes and
+    /// can be passed to [Plot::set_visible] and [Plot::set_zorder].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use plotpy::{Curve, Plot};
+    ///
+    /// let mut curve = Curve::new();
+    /// curve.draw(&[0.0, 1.0], &[0.0, 1.0]);
+    /// let mut plot = Plot::new();
+    /// let handle = plot.add_with_handle(&curve);
+    /// plot.set_visible(handle, false);
+    /// ```
+    pub fn add_with_handle(&mut self, graph: &dyn GraphMaker) -> usize {
+        let handle = self.num_handles;
+        write!(&mut self.buffer, "h{}_before=set(plt.gca().get_children())\n", handle).unwrap();
+        self.push_graph(graph.get_buffer());
+        merge_bounds(&mut self.bounds, graph.data_bounds());
+        write!(
+            &mut self.buffer,
+            "h{}=[a for a in plt.gca().get_children() if a not in h{}_before]\n",
+            handle, handle
+        )
+        .unwrap();
+        self.num_handles += 1;
+        handle
+    }
+
+    /// Returns the bounds (xmin, xmax, ymin, ymax, zmin, zmax) of the data in all added graphs
+    ///
+    /// Returns None if no graph reports its bounds (see [GraphMaker::data_bounds]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use plotpy::{Curve, Plot};
+    ///
+    /// let mut curve = Curve::new();
+    /// curve.draw(&[-1.0, 2.0], &[3.0, 4.0]);
+    /// let mut plot = Plot::new();
+    /// assert_eq!(plot.compute_bounds(), None);
+    /// plot.add(&curve);
+    /// assert_eq!(plot.compute_bounds(), Some((-1.0, 2.0, 3.0, 4.0, 0.0, 0.0)));
+    /// ```
+    pub fn compute_bounds(&self) -> Option<(f64, f64, f64, f64, f64, f64)> {
+        self.bounds
+    }
+
+    /// Sets the visibility of a graph entity added with [Plot::add_with_handle]
+    pub fn set_visible(&mut self, handle: usize, visible: bool) -> &mut Self {
+        let option = if visible { "True" } else { "False" };
+        write!(&mut self.buffer, "for a in h{}: a.set_visible({})\n", handle, option).unwrap();
+        self
+    }
+
+    /// Sets the z-order of a graph entity added with [Plot::add_with_handle]
+    pub fn set_zorder(&mut self, handle: usize, zorder: f64) -> &mut Self {
+        write!(&mut self.buffer, "for a in h{}: a.set_zorder({})\n", handle, zorder).unwrap();
         self
     }
 
@@ -197,12 +400,49 @@ impl Plot {
         self
     }
 
+    /// Sets the resolution (dots per inch) of the saved figure
+    ///
+    /// This option is independent of the figure (display) resolution set by [Plot::set_dpi()].
+    /// A value of 0 (default) means that the figure resolution is used.
+    pub fn set_save_dpi(&mut self, dpi: usize) -> &mut Self {
+        self.save_dpi = dpi;
+        self
+    }
+
     /// Sets the transparency during saving
     pub fn set_save_transparent(&mut self, transparent: bool) -> &mut Self {
         self.save_transparent = Some(transparent);
         self
     }
 
+    /// Sets the default width of lines for the whole figure (rcParams)
+    ///
+    /// The option is written at the top of the script; thus, it does not depend on the
+    /// order of calls. The width set in each graph entity (e.g., Curve) takes precedence.
+    pub fn set_default_line_width(&mut self, width: f64) -> &mut Self {
+        self.default_line_width = Some(width);
+        self
+    }
+
+    /// Sets the default size of markers for the whole figure (rcParams)
+    ///
+    /// The option is written at the top of the script; thus, it does not depend on the
+    /// order of calls. The size set in each graph entity (e.g., Curve) takes precedence.
+    pub fn set_default_marker_size(&mut self, size: f64) -> &mut Self {
+        self.default_marker_size = Some(size);
+        self
+    }
+
+    /// Sets a style preset (rcParams) for the whole figure
+    ///
+    /// The theme is written at the top of the script; thus, it does not depend on the order of calls.
+    /// The options set by [Plot::set_default_line_width], [Plot::set_default_marker_size], and
+    /// the other Plot and graph commands are written afterward and therefore take precedence.
+    pub fn set_theme(&mut self, theme: Theme) -> &mut Self {
+        self.theme = theme;
+        self
+    }
+
     /// Calls Python and saves the python script and figure
     ///
     /// # Input
@@ -213,6 +453,8 @@ impl Plot {
     ///
     /// 1. You may want to call [Plot::set_show_errors()] to enable the
     ///    display of Python errors (if any)
+    /// 2. If Python fails, its full output (e.g., the traceback) is written to a log file
+    ///    next to the figure (same name with the `log` extension)
     pub fn save<S>(&self, figure_path: &S) -> Result<(), StrError>
     where
         S: AsRef<OsStr> + ?Sized,
@@ -220,6 +462,229 @@ impl Plot {
         self.run(figure_path, false)
     }
 
+    /// Calls Python and saves the figure as SVG (small scripts) or PNG (large scripts)
+    ///
+    /// The format is selected by comparing [Plot::approx_size()] with the threshold
+    /// given by [Plot::set_save_auto_threshold()]. Thus, figures with many points are saved
+    /// as raster images, avoiding very large SVG files.
+    ///
+    /// # Input
+    ///
+    /// * `figure_path` -- may be a String, &str, or Path; the extension, if any, is replaced
+    ///
+    /// # Output
+    ///
+    /// Returns the path of the saved figure (with the selected extension)
+    pub fn save_auto<S>(&self, figure_path: &S) -> Result<PathBuf, StrError>
+    where
+        S: AsRef<OsStr> + ?Sized,
+    {
+        let path = self.auto_path(figure_path);
+        self.run(&path, false)?;
+        Ok(path)
+    }
+
+    /// Calls Python and saves many figures, invoking a callback after each one is saved
+    ///
+    /// # Input
+    ///
+    /// * `plots` -- the plots to be saved
+    /// * `figure_paths` -- the corresponding paths; may be String, &str, or Path
+    /// * `callback` -- function called as `callback(index, path)` after saving `plots[index]`;
+    ///   e.g., to update a progress bar or to write a log
+    ///
+    /// # Notes
+    ///
+    /// * The procedure stops at the first error.
+    pub fn save_all<S, F>(plots: &[Plot], figure_paths: &[S], mut callback: F) -> Result<(), StrError>
+    where
+        S: AsRef<OsStr>,
+        F: FnMut(usize, &Path),
+    {
+        if plots.len() != figure_paths.len() {
+            return Err("plots and figure_paths must have the same length");
+        }
+        for (index, (plot, figure_path)) in plots.iter().zip(figure_paths).enumerate() {
+            plot.save(figure_path)?;
+            callback(index, Path::new(figure_path));
+        }
+        Ok(())
+    }
+
+    /// Calls Python and saves a horizontal strip with the colors of a colormap
+    ///
+    /// This is useful to compare colormaps before choosing one.
+    ///
+    /// # Input
+    ///
+    /// * `name` -- the colormap name; see <https://matplotlib.org/stable/tutorials/colors/colormaps.html>
+    /// * `figure_path` -- may be a String, &str, or Path
+    pub fn save_colormap_preview<S>(name: &str, figure_path: &S) -> Result<(), StrError>
+    where
+        S: AsRef<OsStr> + ?Sized,
+    {
+        Plot::colormap_preview(name).save(figure_path)
+    }
+
+    /// Calls Python and saves many plots in a multi-page PDF file (one plot per page)
+    ///
+    /// # Input
+    ///
+    /// * `plots` -- the plots to be saved; the Python options (e.g., the executable) of the first plot are used
+    /// * `figure_path` -- may be a String, &str, or Path; the extension should be `.pdf`
+    ///
+    /// # Notes
+    ///
+    /// * Each page is saved with the options (e.g., tight bounding box) of the corresponding plot.
+    pub fn save_pdf_pages<S>(plots: &[&Plot], figure_path: &S) -> Result<(), StrError>
+    where
+        S: AsRef<OsStr> + ?Sized,
+    {
+        if plots.len() == 0 {
+            return Err("there must be at least one plot");
+        }
+        let commands = Plot::pdf_pages_commands(plots, figure_path);
+        plots[0].call_python(&commands, figure_path)
+    }
+
+    /// Returns the full Python script that [Plot::save()] would write and run (nothing is executed)
+    ///
+    /// The script includes the header (imports and auxiliary functions), the commands of all
+    /// added graphs, and the command to save the figure. This is useful to inspect or test the
+    /// generated code without Python/Matplotlib.
+    ///
+    /// # Input
+    ///
+    /// * `figure_path` -- may be a String, &str, or Path
+    pub fn render_python<S>(&self, figure_path: &S) -> String
+    where
+        S: AsRef<OsStr> + ?Sized,
+    {
+        format!("{}{}", PYTHON_HEADER, self.commands(figure_path, false))
+    }
+
+    /// Calls Python and saves an animation as an animated GIF (using Matplotlib's FuncAnimation and PillowWriter)
+    ///
+    /// # Input
+    ///
+    /// * `animation` -- the frames; see [Animation]
+    /// * `figure_path` -- may be a String, &str, or Path; the extension is replaced by `.gif`
+    ///
+    /// # Output
+    ///
+    /// Returns the path of the saved figure (with the `.gif` extension)
+    ///
+    /// # Notes
+    ///
+    /// * The axes are cleared before each frame; then, the commands of this plot (e.g., the ranges,
+    ///   labels, and the graphs added with [Plot::add]) are drawn after the commands of the frame.
+    /// * The Pillow package is required.
+    pub fn save_animation<S>(&self, animation: &Animation, figure_path: &S) -> Result<PathBuf, StrError>
+    where
+        S: AsRef<OsStr> + ?Sized,
+    {
+        if animation.num_frames() == 0 {
+            return Err("the animation must have at least one frame");
+        }
+        let mut path = Path::new(figure_path).to_path_buf();
+        path.set_extension("gif");
+        let commands = self.animation_commands(animation, &path);
+        self.call_python(&commands, &path)?;
+        Ok(path)
+    }
+
+    /// Returns the full Python script that [Plot::save_animation()] would write and run (nothing is executed)
+    ///
+    /// # Input
+    ///
+    /// * `animation` -- the frames; see [Animation]
+    /// * `figure_path` -- may be a String, &str, or Path; the extension is replaced by `.gif`
+    pub fn render_animation<S>(&self, animation: &Animation, figure_path: &S) -> String
+    where
+        S: AsRef<OsStr> + ?Sized,
+    {
+        let mut path = Path::new(figure_path).to_path_buf();
+        path.set_extension("gif");
+        format!("{}{}", PYTHON_HEADER, self.animation_commands(animation, &path))
+    }
+
+    /// Starts writing the Python script directly to disk (streaming mode), e.g., for very large datasets
+    ///
+    /// In streaming mode, the commands of the graphs passed to [Plot::add] are written to the script
+    /// file (`figure_path` with extension `.py`) instead of being kept in memory; thus, each graph may
+    /// be dropped (or cleared) right after being added. The figure is saved by [Plot::end_stream].
+    ///
+    /// # Input
+    ///
+    /// * `figure_path` -- may be a String, &str, or Path
+    ///
+    /// # Notes
+    ///
+    /// 1. The default line width and marker size (rcParams) must be set before calling this function
+    /// 2. The commands already streamed are not included by [Plot::save()] or [Plot::render_python()]
+    /// 3. Streaming only avoids the copy of all commands inside the Plot: each graph still keeps the
+    ///    commands of its own arrays in its buffer until it is added (and cleared or dropped)
+    pub fn begin_stream<S>(&mut self, figure_path: &S) -> Result<(), StrError>
+    where
+        S: AsRef<OsStr> + ?Sized,
+    {
+        let fig_path = Path::new(figure_path).to_path_buf();
+        let mut path = fig_path.clone();
+        path.set_extension("py");
+        if let Some(p) = path.parent() {
+            fs::create_dir_all(p).map_err(|_| "cannot create directory")?;
+        }
+        let file = File::create(&path).map_err(|_| "cannot create file")?;
+        let mut stream = BufWriter::new(file);
+        write!(stream, "{}{}", PYTHON_HEADER, self.rc_params()).map_err(|_| "cannot write file")?;
+        self.stream = Some(stream);
+        self.stream_figure_path = fig_path;
+        self.stream_error = None;
+        self.push_graph("");
+        Ok(())
+    }
+
+    /// Finishes the streaming mode by writing the command to save the figure and calling Python
+    ///
+    /// See [Plot::begin_stream]
+    pub fn end_stream(&mut self) -> Result<(), StrError> {
+        let mut stream = self.stream.take().ok_or("begin_stream must be called first")?;
+        if let Some(error) = self.stream_error.take() {
+            return Err(error);
+        }
+        let fig_path = std::mem::take(&mut self.stream_figure_path);
+        write!(
+            stream,
+            "{}\nfn=r'{}'\nplt.savefig(fn{})\n",
+            self.buffer,
+            fig_path.to_string_lossy(),
+            self.options_savefig()
+        )
+        .map_err(|_| "cannot write file")?;
+        self.buffer.clear();
+        let file = stream.into_inner().map_err(|_| "cannot write file")?;
+        file.sync_all().map_err(|_| "cannot sync file")?;
+        let mut path = fig_path.clone();
+        path.set_extension("py");
+        let output = run_python3(&self.python_exe, &path)?;
+        self.check_python_output(&output, &fig_path)
+    }
+
+    /// Returns the approximate size of the figure, given by the length (in bytes) of the Python commands
+    ///
+    /// The size grows with the number of points (and artists) added to the plot.
+    pub fn approx_size(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Sets the threshold on [Plot::approx_size()] above which [Plot::save_auto()] generates a PNG file
+    ///
+    /// The default is 1,000,000
+    pub fn set_save_auto_threshold(&mut self, threshold: usize) -> &mut Self {
+        self.save_auto_threshold = threshold;
+        self
+    }
+
     /// Calls Python, saves the python script and figure, and shows the plot window
     ///
     /// # Input
@@ -284,6 +749,17 @@ impl Plot {
         self.add(&legend)
     }
 
+    /// Adds legend to plot at the given location (see Legend for further options)
+    ///
+    /// # Input
+    ///
+    /// * `location` -- e.g., "best", "upper left", "lower right", "center left"
+    pub fn legend_with_loc(&mut self, location: &str) -> &mut Self {
+        let mut legend = Legend::new();
+        legend.set_location(location).draw();
+        self.add(&legend)
+    }
+
     /// Adds grid and labels
     pub fn grid_and_labels(&mut self, xlabel: &str, ylabel: &str) -> &mut Self {
         write!(
@@ -318,6 +794,14 @@ impl Plot {
         self
     }
 
+    /// Suppresses the warnings issued by Python (e.g., Matplotlib's tight_layout warnings)
+    ///
+    /// The default is false; i.e., the warnings are written to the log file. Errors are not affected.
+    pub fn set_suppress_warnings(&mut self, flag: bool) -> &mut Self {
+        self.suppress_warnings = flag;
+        self
+    }
+
     /// Configures 3D subplots
     ///
     /// # Input
@@ -402,6 +886,48 @@ impl Plot {
         self
     }
 
+    /// Sets the direction of ticks along both axes
+    ///
+    /// # Input
+    ///
+    /// * `direction` -- the direction of ticks (e.g., inward ticks are often required by journals)
+    /// * `with_minor` -- also applies the direction to the minor ticks
+    pub fn set_tick_direction(&mut self, direction: Direction, with_minor: bool) -> &mut Self {
+        let which = if with_minor { "both" } else { "major" };
+        write!(
+            &mut self.buffer,
+            "plt.gca().tick_params(axis='both',which='{}',direction='{}')\n",
+            which,
+            direction.as_str()
+        )
+        .unwrap();
+        self
+    }
+
+    /// Adds a secondary x axis at the top with values given by a transformation of the primary x values
+    ///
+    /// # Input
+    ///
+    /// * `forward` -- Python expression of `x` converting primary values to secondary values; e.g., `1239.84/x`
+    /// * `inverse` -- Python expression of `x` converting secondary values back to primary values; e.g., `1239.84/x`
+    /// * `label` -- the label of the secondary axis (ignored if empty)
+    ///
+    /// # Notes
+    ///
+    /// * The expressions are evaluated with NumPy arrays; thus, use functions such as `np.log10(x)`.
+    pub fn add_secondary_x_axis(&mut self, forward: &str, inverse: &str, label: &str) -> &mut Self {
+        write!(
+            &mut self.buffer,
+            "sax=plt.gca().secondary_xaxis('top',functions=(lambda x: {},lambda x: {}))\n",
+            forward, inverse
+        )
+        .unwrap();
+        if label != "" {
+            write!(&mut self.buffer, "sax.set_xlabel(r'{}')\n", label).unwrap();
+        }
+        self
+    }
+
     /// Aligns the labels when using subplots
     pub fn set_align_labels(&mut self) -> &mut Self {
         write!(&mut self.buffer, "plt.gcf().align_labels()\n").unwrap();
@@ -421,6 +947,51 @@ impl Plot {
         self
     }
 
+    /// Adds a caption below the axes (wrapped to the width of the figure)
+    ///
+    /// # Input
+    ///
+    /// * `caption` -- the text (single quotation marks are replaced as in [Plot::set_title()])
+    /// * `fontsize` -- the font size; a value ≤ 0 means the default font size
+    pub fn set_caption(&mut self, caption: &str, fontsize: f64) -> &mut Self {
+        let t = caption.replace("'", "’");
+        write!(
+            &mut self.buffer,
+            "cap=plt.figtext(0.5,0.0,r'{}',wrap=True,ha='center',va='top'",
+            t
+        )
+        .unwrap();
+        if fontsize > 0.0 {
+            write!(&mut self.buffer, ",fontsize={}", fontsize).unwrap();
+        }
+        write!(&mut self.buffer, ")\nadd_to_ea(cap)\n").unwrap();
+        self
+    }
+
+    /// Adds a title to the plot or sub-plot with a given alignment
+    ///
+    /// # Input
+    ///
+    /// * `title` -- the title (single quotation marks are replaced as in [Plot::set_title()])
+    /// * `loc` -- the alignment. Options: "left", "center", "right"
+    pub fn set_title_with_loc(&mut self, title: &str, loc: &str) -> &mut Self {
+        let t = title.replace("'", "’");
+        write!(&mut self.buffer, "plt.title(r'{}',loc='{}')\n", t, loc).unwrap();
+        self
+    }
+
+    /// Adds a title to the plot or sub-plot with a given distance (in points) from the top of the axes
+    ///
+    /// # Input
+    ///
+    /// * `title` -- the title (single quotation marks are replaced as in [Plot::set_title()])
+    /// * `pad` -- the offset in points; e.g., to avoid collision with the tick labels of a top axis
+    pub fn set_title_with_pad(&mut self, title: &str, pad: f64) -> &mut Self {
+        let t = title.replace("'", "’");
+        write!(&mut self.buffer, "plt.gca().set_title(r'{}',pad={})\n", t, pad).unwrap();
+        self
+    }
+
     /// Adds a title to all sub-plots
     ///
     /// # Notes
@@ -438,6 +1009,9 @@ impl Plot {
             None => write!(&mut self.buffer, "st=plt.suptitle('{}')\n", t).unwrap(),
         }
         write!(&mut self.buffer, "add_to_ea(st)\n").unwrap();
+        if let Some(top) = params.and_then(|p| p.subplots_top()) {
+            write!(&mut self.buffer, "plt.subplots_adjust(top={})\n", top).unwrap();
+        }
         self
     }
 
@@ -465,6 +1039,9 @@ impl Plot {
     }
 
     /// Sets same scale for both axes
+    ///
+    /// Only the current axes is modified; thus, with subplots, this function affects
+    /// the subplot (2D or 3D) selected by the last call to `set_subplot` or `set_subplot_3d`.
     pub fn set_equal_axes(&mut self, equal: bool) -> &mut Self {
         if equal {
             self.buffer.push_str("set_equal_axes()\n");
@@ -477,6 +1054,7 @@ impl Plot {
     /// Sets the figure size in inches
     pub fn set_figure_size_inches(&mut self, width: f64, height: f64) -> &mut Self {
         write!(&mut self.buffer, "plt.gcf().set_size_inches({},{})\n", width, height).unwrap();
+        self.figure_size = Some((width, height));
         self
     }
 
@@ -485,9 +1063,37 @@ impl Plot {
     pub fn set_figure_size_points(&mut self, width: f64, height: f64) -> &mut Self {
         const FACTOR: f64 = 72.27;
         write!(&mut self.buffer, "plt.gcf().set_size_inches({},{})\n", width / FACTOR, height / FACTOR).unwrap();
+        self.figure_size = Some((width / FACTOR, height / FACTOR));
         self
     }
 
+    /// Sets the figure (display) resolution in dots per inch
+    ///
+    /// See also [Plot::set_save_dpi()] to set the resolution of the saved figure independently.
+    pub fn set_dpi(&mut self, dpi: f64) -> &mut Self {
+        write!(&mut self.buffer, "plt.gcf().set_dpi({})\n", dpi).unwrap();
+        self.figure_dpi = Some(dpi);
+        self
+    }
+
+    /// Returns the expected size of the saved (raster) figure in pixels
+    ///
+    /// The size is the figure size in inches times the save resolution (if set), or the
+    /// figure resolution (if set), or the Matplotlib default of 100 dots per inch.
+    ///
+    /// Returns None if the figure size has not been set.
+    ///
+    /// **Note:** The tight bounding box option (enabled by default) crops the figure; thus,
+    /// disable it with [Plot::set_save_tight()] to obtain exactly this size.
+    pub fn figure_size_pixels(&self) -> Option<(f64, f64)> {
+        let dpi = if self.save_dpi > 0 {
+            self.save_dpi as f64
+        } else {
+            self.figure_dpi.unwrap_or(100.0)
+        };
+        self.figure_size.map(|(width, height)| (width * dpi, height * dpi))
+    }
+
     /// Sets an option to hide the ticks along the x axis
     pub fn set_hide_xticks(&mut self) -> &mut Self {
         write!(&mut self.buffer, "plt.gca().set_xticklabels([])\n").unwrap();
@@ -507,12 +1113,30 @@ impl Plot {
     }
 
     /// Sets an option to hide/show all axes
+    ///
+    /// Hiding the axes, together with `set_save_tight(true)` and `set_save_pad_inches(0.0)`,
+    /// yields a borderless figure; e.g., to export an image used as a texture.
     pub fn set_hide_axes(&mut self, hide: bool) -> &mut Self {
         let option = if hide { "off" } else { "on" };
         write!(&mut self.buffer, "plt.axis('{}')\n", option).unwrap();
         self
     }
 
+    /// Turns the axes off (or on), e.g., to export an image without axes
+    ///
+    /// This is the same as [Plot::set_hide_axes()].
+    pub fn set_axis_off(&mut self, off: bool) -> &mut Self {
+        self.set_hide_axes(off)
+    }
+
+    /// Sets the padding added to the data limits by autoscaling, as a fraction of the data range
+    ///
+    /// Zero values give a tight fit. Matplotlib's default is 0.05 for both x and y.
+    pub fn set_margins(&mut self, x_frac: f64, y_frac: f64) -> &mut Self {
+        write!(&mut self.buffer, "plt.gca().margins({},{})\n", x_frac, y_frac).unwrap();
+        self
+    }
+
     /// Sets axes limits
     pub fn set_range_3d(&mut self, xmin: f64, xmax: f64, ymin: f64, ymax: f64, zmin: f64, zmax: f64) -> &mut Self {
         write!(
@@ -705,11 +1329,11 @@ impl Plot {
     where
         S: std::fmt::Display,
         T: AsVector<'a, U>,
-        U: 'a + std::fmt::Display,
+        U: 'a + std::fmt::Display + AsF64,
     {
         assert_eq!(ticks.vec_size(), labels.len());
-        vector_to_array(&mut self.buffer, "tx", ticks);
-        generate_list_quoted(&mut self.buffer, "lx", labels);
+        vector_to_array(&mut self.buffer, &self.array_format, "tx", ticks);
+        generate_list_quoted(&mut self.buffer, &self.array_format, "lx", labels);
         write!(
             &mut self.buffer,
             "plt.gca().set_xticks(tx)\nplt.gca().set_xticklabels(lx)\n"
@@ -723,11 +1347,11 @@ impl Plot {
     where
         S: std::fmt::Display,
         T: AsVector<'a, U>,
-        U: 'a + std::fmt::Display,
+        U: 'a + std::fmt::Display + AsF64,
     {
         assert_eq!(ticks.vec_size(), labels.len());
-        vector_to_array(&mut self.buffer, "ty", ticks);
-        generate_list_quoted(&mut self.buffer, "ly", labels);
+        vector_to_array(&mut self.buffer, &self.array_format, "ty", ticks);
+        generate_list_quoted(&mut self.buffer, &self.array_format, "ly", labels);
         write!(
             &mut self.buffer,
             "plt.gca().set_yticks(ty)\nplt.gca().set_yticklabels(ly)\n"
@@ -736,6 +1360,30 @@ impl Plot {
         self
     }
 
+    /// Sets the fontsize of the ticks for all axes
+    ///
+    /// This affects the tick labels only; i.e., the axis labels keep their own font size.
+    pub fn set_tick_font_size(&mut self, fontsize: f64) -> &mut Self {
+        write!(
+            &mut self.buffer,
+            "plt.gca().tick_params(axis='both',labelsize={})\n",
+            fontsize,
+        )
+        .unwrap();
+        self
+    }
+
+    /// Sets the line width of the visible frame borders (spines)
+    pub fn set_spine_width(&mut self, width: f64) -> &mut Self {
+        write!(
+            &mut self.buffer,
+            "for s in plt.gca().spines.values():\n    if s.get_visible(): s.set_linewidth({})\n",
+            width,
+        )
+        .unwrap();
+        self
+    }
+
     /// Sets the fontsize of the ticks for the x-axis
     pub fn set_ticks_x_fontsize(&mut self, fontsize: f64) -> &mut Self {
         write!(
@@ -1084,6 +1732,37 @@ impl Plot {
         self.set_frame_border(show_all, show_all, show_all, show_all)
     }
 
+    /// Draws a vertical color gradient spanning the axes behind all other artists
+    ///
+    /// # Input
+    ///
+    /// * `top_color` -- color at the top of the axes
+    /// * `bottom_color` -- color at the bottom of the axes
+    ///
+    /// # Note
+    ///
+    /// The gradient is drawn in axes coordinates with a very low z-order; thus, it does
+    /// not change the data limits and the data stays on top.
+    pub fn set_gradient_background<C>(&mut self, top_color: C, bottom_color: C) -> &mut Self
+    where
+        C: Into<Color>,
+    {
+        let top = top_color.into().to_python();
+        let bottom = bottom_color.into().to_python();
+        write!(
+            &mut self.buffer,
+            "import matplotlib.colors as mcl\n\
+             import matplotlib.image as mim\n\
+             gcm=mcl.LinearSegmentedColormap.from_list('gradient',[{},{}])\n\
+             gim=mim.AxesImage(plt.gca(),cmap=gcm,interpolation='bicubic',origin='lower',extent=(0,1,0,1),transform=plt.gca().transAxes,zorder=-100)\n\
+             gim.set_data(np.linspace(0,1,256).reshape(-1,1))\n\
+             plt.gca().add_image(gim)\n",
+            bottom, top
+        )
+        .unwrap();
+        self
+    }
+
     /// Draws an infinite horizontal line at y
     pub fn set_horiz_line(&mut self, y: f64, color: &str, line_style: &str, line_width: f64) -> &mut Self {
         let opt = format!(",color='{}',linestyle='{}',linewidth={}", color, line_style, line_width);
@@ -1120,37 +1799,135 @@ impl Plot {
         self
     }
 
-    /// Run python
-    fn run<S>(&self, figure_path: &S, show: bool) -> Result<(), StrError>
+    /// Sets the format of the numbers in the generated lists and arrays
+    pub fn set_array_format(&mut self, format: &ArrayFormat) -> &mut Self {
+        self.array_format = format.clone();
+        self
+    }
+
+    /// Returns the figure path with the extension selected by save_auto
+    fn auto_path<S>(&self, figure_path: &S) -> PathBuf
     where
         S: AsRef<OsStr> + ?Sized,
     {
-        // update commands
-        let fig_path = Path::new(figure_path);
-        let mut txt = "plt.savefig(fn".to_string();
+        let mut path = Path::new(figure_path).to_path_buf();
+        if self.approx_size() > self.save_auto_threshold {
+            path.set_extension("png");
+        } else {
+            path.set_extension("svg");
+        }
+        path
+    }
+
+    /// Returns the rcParams (and warnings filter) commands to be written at the top of the script
+    fn rc_params(&self) -> String {
+        let mut rc = String::new();
+        if self.suppress_warnings {
+            rc.push_str("import warnings\nwarnings.filterwarnings('ignore')\n");
+        }
+        rc.push_str(self.theme.rc_params());
+        if let Some(width) = self.default_line_width {
+            write!(&mut rc, "plt.rcParams['lines.linewidth']={}\n", width).unwrap();
+        }
+        if let Some(size) = self.default_marker_size {
+            write!(&mut rc, "plt.rcParams['lines.markersize']={}\n", size).unwrap();
+        }
+        rc
+    }
+
+    /// Returns the options of the savefig command
+    fn options_savefig(&self) -> String {
+        let mut opt = String::new();
         if self.save_tight {
-            txt.push_str(",bbox_inches='tight',bbox_extra_artists=EXTRA_ARTISTS");
+            write!(&mut opt, ",bbox_inches='tight',bbox_extra_artists=EXTRA_ARTISTS").unwrap();
         }
         if let Some(pad) = self.save_pad_inches {
-            txt.push_str(format!(",pad_inches={}", pad).as_str());
+            write!(&mut opt, ",pad_inches={}", pad).unwrap();
+        }
+        if self.save_dpi > 0 {
+            write!(&mut opt, ",dpi={}", self.save_dpi).unwrap();
         }
         if let Some(transparent) = self.save_transparent {
             if transparent {
-                txt.push_str(",transparent=True");
+                write!(&mut opt, ",transparent=True").unwrap();
             }
         }
-        txt.push_str(")\n");
+        opt
+    }
+
+    /// Run python
+    fn run<S>(&self, figure_path: &S, show: bool) -> Result<(), StrError>
+    where
+        S: AsRef<OsStr> + ?Sized,
+    {
+        let commands = self.commands(figure_path, show);
+        self.call_python(&commands, figure_path)
+    }
+
+    /// Returns the commands (without the header) to save and optionally show the figure
+    fn commands<S>(&self, figure_path: &S, show: bool) -> String
+    where
+        S: AsRef<OsStr> + ?Sized,
+    {
+        let fig_path = Path::new(figure_path);
+        let mut txt = format!("plt.savefig(fn{})\n", self.options_savefig());
         if show {
             txt.push_str("\nplt.show()\n");
         };
-        let commands = format!("{}\nfn=r'{}'\n{}", self.buffer, fig_path.to_string_lossy(), txt);
+        format!(
+            "{}{}\nfn=r'{}'\n{}",
+            self.rc_params(),
+            self.buffer,
+            fig_path.to_string_lossy(),
+            txt
+        )
+    }
+
+    /// Returns the commands (without the header) to save an animation
+    fn animation_commands(&self, animation: &Animation, figure_path: &Path) -> String {
+        let mut opt = String::new();
+        if self.save_dpi > 0 {
+            write!(&mut opt, ",dpi={}", self.save_dpi).unwrap();
+        }
+        format!(
+            "{}{}\nfn=r'{}'\nanim.save(fn,writer=writer{})\n{}",
+            self.rc_params(),
+            animation.commands(&self.buffer),
+            figure_path.to_string_lossy(),
+            opt,
+            animation.loop_commands()
+        )
+    }
 
-        // call python
+    /// Appends the commands of a graph to the buffer or writes them to the stream (with the pending commands)
+    fn push_graph(&mut self, graph_buffer: &str) {
+        match &mut self.stream {
+            Some(stream) => {
+                let res = stream
+                    .write_all(self.buffer.as_bytes())
+                    .and_then(|_| stream.write_all(graph_buffer.as_bytes()));
+                if res.is_err() && self.stream_error.is_none() {
+                    self.stream_error = Some("cannot write file");
+                }
+                self.buffer.clear();
+            }
+            None => self.buffer.push_str(graph_buffer),
+        }
+    }
+
+    /// Calls Python with the given commands and writes a log file if an error occurs
+    fn call_python<S>(&self, commands: &String, figure_path: &S) -> Result<(), StrError>
+    where
+        S: AsRef<OsStr> + ?Sized,
+    {
         let mut path = Path::new(figure_path).to_path_buf();
         path.set_extension("py");
-        let output = call_python3(&self.python_exe, &commands, &path)?;
+        let output = call_python3(&self.python_exe, commands, &path)?;
+        self.check_python_output(&output, Path::new(figure_path))
+    }
 
-        // handle error => write log file
+    /// Writes a log file (and returns an error) if the output of Python is not empty
+    fn check_python_output(&self, output: &str, figure_path: &Path) -> Result<(), StrError> {
         if output != "" {
             let mut log_path = Path::new(figure_path).to_path_buf();
             log_path.set_extension("log");
@@ -1165,17 +1942,63 @@ impl Plot {
         }
         Ok(())
     }
+
+    /// Returns a plot with a horizontal strip showing the colors of a colormap
+    fn colormap_preview(name: &str) -> Plot {
+        let mut plot = Plot::new();
+        write!(
+            &mut plot.buffer,
+            "gradient=np.linspace(0,1,256).reshape(1,-1)\n\
+             plt.imshow(gradient,aspect='auto',cmap=plt.get_cmap('{}'))\n",
+            name
+        )
+        .unwrap();
+        plot.set_title(name)
+            .set_hide_axes(true)
+            .set_figure_size_points(400.0, 60.0);
+        plot
+    }
+
+    /// Returns the commands to save many plots in a multi-page PDF file
+    fn pdf_pages_commands<S>(plots: &[&Plot], figure_path: &S) -> String
+    where
+        S: AsRef<OsStr> + ?Sized,
+    {
+        let mut commands = format!(
+            "from matplotlib.backends.backend_pdf import PdfPages\n\
+             pdf=PdfPages(r'{}')\n",
+            Path::new(figure_path).to_string_lossy()
+        );
+        for plot in plots {
+            write!(
+                &mut commands,
+                "{}plt.figure()\n\
+                 EXTRA_ARTISTS=[]\n\
+                 THREE_D=dict()\n\
+                 THREE_D_ACTIVE=(1,1,1)\n\
+                 {}\n\
+                 pdf.savefig(plt.gcf(){})\n\
+                 plt.close()\n",
+                plot.rc_params(),
+                plot.buffer,
+                plot.options_savefig()
+            )
+            .unwrap();
+        }
+        commands.push_str("pdf.close()\n");
+        commands
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
 #[cfg(test)]
 mod tests {
-    use crate::SuperTitleParams;
+    use crate::{Animation, ArrayFormat, Color, Curve, Legend, SuperTitleParams, Surface, PYTHON_HEADER};
 
-    use super::Plot;
-    use std::fs::File;
-    use std::io::{BufRead, BufReader};
+    use super::{extend_bounds, vector_to_array, Direction, GraphMaker, Plot, Theme};
+    use std::fs::{self, File};
+    use std::io::{BufRead, BufReader, Write};
     use std::path::Path;
 
     const OUT_DIR: &str = "/tmp/plotpy/unit_tests";
@@ -1207,6 +2030,94 @@ mod tests {
         assert_eq!(result, ());
     }
 
+    #[test]
+    fn legend_with_loc_works() {
+        let mut curve = Curve::new();
+        curve.set_label("Developer's data").draw(&[1.0, 2.0], &[3.0, 4.0]);
+        let mut plot = Plot::new();
+        plot.add(&curve).legend_with_loc("upper left");
+        assert!(plot.buffer.contains("label=r'Developer’s data'"));
+        assert!(plot
+            .buffer
+            .contains("leg=plt.legend(handlelength=3,ncol=1,loc='upper left')"));
+    }
+
+    #[test]
+    fn colormap_preview_works() {
+        let plot = Plot::colormap_preview("viridis");
+        assert!(plot.buffer.starts_with(
+            "gradient=np.linspace(0,1,256).reshape(1,-1)\n\
+             plt.imshow(gradient,aspect='auto',cmap=plt.get_cmap('viridis'))\n\
+             plt.title(r'viridis')\n\
+             plt.axis('off')\n"
+        ));
+        assert!(plot.buffer.contains("plt.gcf().set_size_inches("));
+    }
+
+    #[test]
+    fn render_python_works() {
+        let mut curve = Curve::new();
+        curve.draw(&[1.0, 2.0], &[3.0, 4.0]);
+        let mut plot = Plot::new();
+        plot.add(&curve).set_title("hello");
+        let script = plot.render_python("/tmp/plotpy/unit_tests/render_python.svg");
+        assert!(script.contains("import matplotlib"));
+        assert!(script.contains(curve.get_buffer()));
+        assert!(script.contains("plt.title(r'hello')"));
+        assert!(script.contains("fn=r'/tmp/plotpy/unit_tests/render_python.svg'\nplt.savefig(fn"));
+        assert!(!Path::new("/tmp/plotpy/unit_tests/render_python.py").exists());
+    }
+
+    #[test]
+    fn render_animation_works() {
+        let mut curve = Curve::new();
+        let mut animation = Animation::new();
+        curve.draw(&[0.0, 1.0], &[0.0, 1.0]);
+        animation.add_frame(&[&curve]);
+        curve.clear_buffer();
+        curve.draw(&[0.0, 1.0], &[1.0, 0.0]);
+        animation.add_frame(&[&curve]);
+        let mut plot = Plot::new();
+        plot.set_title("moving").set_save_dpi(80);
+        let script = plot.render_animation(&animation, "/tmp/plotpy/unit_tests/render_animation.svg");
+        assert!(script.starts_with(PYTHON_HEADER));
+        assert!(
+            script.contains("def frame_1():\n    x=np.array([0,1,],dtype=float)\n    y=np.array([1,0,],dtype=float)\n")
+        );
+        assert!(script.contains("def draw_common():\n    plt.title(r'moving')\n"));
+        assert!(script.contains("anim=ani.FuncAnimation(plt.gcf(),update,frames=len(FRAMES))\n"));
+        assert!(script.contains("writer=ani.PillowWriter(fps=5)\n"));
+        assert!(
+            script.ends_with("fn=r'/tmp/plotpy/unit_tests/render_animation.gif'\nanim.save(fn,writer=writer,dpi=80)\n")
+        );
+        animation.set_loop_count(1);
+        let script = plot.render_animation(&animation, "/tmp/plotpy/unit_tests/render_animation.svg");
+        assert!(script.contains("anim.save(fn,writer=writer,dpi=80)\nimport io\n"));
+        assert!(script.ends_with("gif.save(fn,save_all=True,loop=1)\n"));
+        assert_eq!(
+            plot.save_animation(&Animation::new(), "/tmp/plotpy/unit_tests/empty_animation.gif")
+                .err(),
+            Some("the animation must have at least one frame")
+        );
+    }
+
+    #[test]
+    fn save_dpi_and_tight_flow_into_savefig() {
+        let path = "/tmp/plotpy/unit_tests/save_dpi_and_tight.png";
+        let mut plot = Plot::new();
+        assert!(plot
+            .render_python(path)
+            .ends_with("plt.savefig(fn,bbox_inches='tight',bbox_extra_artists=EXTRA_ARTISTS)\n"));
+        plot.set_save_dpi(300).set_save_tight(true);
+        assert!(plot
+            .render_python(path)
+            .ends_with("plt.savefig(fn,bbox_inches='tight',bbox_extra_artists=EXTRA_ARTISTS,dpi=300)\n"));
+        plot.set_save_tight(false);
+        assert!(plot.render_python(path).ends_with("plt.savefig(fn,dpi=300)\n"));
+        plot.set_save_dpi(0);
+        assert!(plot.render_python(path).ends_with("plt.savefig(fn)\n"));
+    }
+
     #[test]
     fn save_str_works() {
         let plot = Plot::new();
@@ -1229,6 +2140,17 @@ mod tests {
         assert_eq!(plot.save(&path).err(), Some("python3 failed; please see the log file"));
     }
 
+    #[test]
+    fn save_error_contains_the_traceback() {
+        let mut plot = Plot::new();
+        plot.extra("plt.plot(1,,2)\n");
+        let path = Path::new(OUT_DIR).join("save_error_contains_the_traceback.svg");
+        assert_eq!(plot.save(&path).err(), Some("python3 failed; please see the log file"));
+        let log = fs::read_to_string(path.with_extension("log")).unwrap();
+        assert!(log.contains("plt.plot(1,,2)"));
+        assert!(log.contains("SyntaxError"));
+    }
+
     #[test]
     fn subplot_3d_works() {
         let mut plot = Plot::new();
@@ -1254,6 +2176,77 @@ mod tests {
         assert_eq!(plot.buffer, b);
     }
 
+    #[test]
+    fn set_subplot_switches_the_active_cell() {
+        let mut top = Curve::new();
+        let mut bottom = Curve::new();
+        top.draw(&[0.0, 1.0], &[0.0, 1.0]);
+        bottom.draw(&[0.0, 1.0], &[1.0, 0.0]);
+        let mut plot = Plot::new();
+        plot.set_subplot(2, 1, 1).add(&top).set_subplot(2, 1, 2).add(&bottom);
+        let first = plot.buffer.find("plt.subplot(2,1,1)").unwrap();
+        let second = plot.buffer.find("plt.subplot(2,1,2)").unwrap();
+        assert!(first < second);
+        assert!(plot.buffer[first..second].contains("y=np.array([0,1,],dtype=float)"));
+        assert!(plot.buffer[second..].contains("y=np.array([1,0,],dtype=float)"));
+    }
+
+    #[test]
+    fn set_equal_axes_targets_the_active_subplot() {
+        let mut plot = Plot::new();
+        plot.set_subplot(1, 2, 1)
+            .set_subplot(1, 2, 2)
+            .set_equal_axes(true)
+            .set_subplot_3d(2, 2, 3);
+        let first = plot.buffer.find("plt.subplot(1,2,1)").unwrap();
+        let second = plot.buffer.find("plt.subplot(1,2,2)").unwrap();
+        let third = plot.buffer.find("subplot_3d(2,2,3)").unwrap();
+        assert_eq!(plot.buffer.matches("set_equal_axes()").count(), 1);
+        assert!(!plot.buffer[first..second].contains("set_equal_axes()"));
+        assert!(plot.buffer[second..third].contains("set_equal_axes()"));
+        assert!(PYTHON_HEADER
+            .contains("    ax = plt.gca()\n    if ax.name != '3d':\n        ax.axes.set_aspect('equal')\n"));
+    }
+
+    #[test]
+    fn begin_stream_writes_the_graphs_to_the_script() {
+        let x: Vec<f64> = (0..20_000).map(|i| f64::sin(i as f64)).collect();
+        let mut curve = Curve::new();
+        curve.draw(&x, &x);
+        let mut correct = String::new();
+        vector_to_array(&mut correct, &ArrayFormat::new(), "x", &x);
+        let mut plot = Plot::new();
+        plot.set_default_line_width(2.0).set_title("streaming");
+        plot.begin_stream("/tmp/plotpy/unit_tests/begin_stream_works.svg")
+            .unwrap();
+        plot.add(&curve).set_labels("x", "y");
+        assert_eq!(plot.buffer, "plt.gca().set_xlabel(r'x')\nplt.gca().set_ylabel(r'y')\n");
+        plot.stream.as_mut().unwrap().flush().unwrap();
+        let data = fs::read_to_string("/tmp/plotpy/unit_tests/begin_stream_works.py").unwrap();
+        let commands = data.strip_prefix(PYTHON_HEADER).unwrap();
+        assert!(commands.starts_with("plt.rcParams['lines.linewidth']=2\nplt.title(r'streaming')\nx=np.array(["));
+        assert!(commands.contains(&correct));
+        assert!(commands.ends_with("plt.plot(x,y)\n"));
+    }
+
+    #[test]
+    fn end_stream_requires_begin_stream() {
+        let mut plot = Plot::new();
+        assert_eq!(plot.end_stream().err(), Some("begin_stream must be called first"));
+    }
+
+    #[test]
+    fn super_title_reserves_space() {
+        let mut params = SuperTitleParams::new();
+        params.set_y(0.95).set_subplots_top(0.85);
+        let mut plot = Plot::new();
+        plot.set_super_title("all subplots", Some(&params));
+        let b: &str = "st=plt.suptitle('all subplots',y=0.95)\n\
+                       add_to_ea(st)\n\
+                       plt.subplots_adjust(top=0.85)\n";
+        assert_eq!(plot.buffer, b);
+    }
+
     #[test]
     fn super_title_works() {
         let mut params = SuperTitleParams::new();
@@ -1319,6 +2312,44 @@ mod tests {
         assert_eq!(p3.buffer, b);
     }
 
+    #[test]
+    fn set_caption_works() {
+        let mut plot = Plot::new();
+        plot.set_caption("Figure 1: the author's data", 0.0)
+            .set_caption("Figure 2", 8.0);
+        let b: &str = "cap=plt.figtext(0.5,0.0,r'Figure 1: the author’s data',wrap=True,ha='center',va='top')\n\
+                       add_to_ea(cap)\n\
+                       cap=plt.figtext(0.5,0.0,r'Figure 2',wrap=True,ha='center',va='top',fontsize=8)\n\
+                       add_to_ea(cap)\n";
+        assert_eq!(plot.buffer, b);
+    }
+
+    #[test]
+    fn set_title_with_loc_works() {
+        let mut plot = Plot::new();
+        plot.set_title_with_loc("Left's", "left")
+            .set_title_with_loc("Center", "center")
+            .set_title_with_loc("Right", "right");
+        let b: &str = "plt.title(r'Left’s',loc='left')\n\
+                       plt.title(r'Center',loc='center')\n\
+                       plt.title(r'Right',loc='right')\n";
+        assert_eq!(plot.buffer, b);
+    }
+
+    #[test]
+    fn set_title_with_pad_works() {
+        let mut plot = Plot::new();
+        plot.set_title_with_pad("Top's", 20.0);
+        assert_eq!(plot.buffer, "plt.gca().set_title(r'Top’s',pad=20)\n");
+    }
+
+    #[test]
+    fn set_axis_off_works() {
+        let mut plot = Plot::new();
+        plot.set_axis_off(true).set_axis_off(false);
+        assert_eq!(plot.buffer, "plt.axis('off')\nplt.axis('on')\n");
+    }
+
     #[test]
     fn set_functions_work() {
         let mut plot = Plot::new();
@@ -1542,6 +2573,253 @@ mod tests {
         assert_eq!(plot.buffer, b);
     }
 
+    #[test]
+    fn compute_bounds_works() {
+        let mut plot = Plot::new();
+        assert_eq!(plot.compute_bounds(), None);
+        let mut legend = Legend::new();
+        legend.draw();
+        plot.add(&legend);
+        assert_eq!(plot.compute_bounds(), None);
+        let mut curve = Curve::new();
+        curve.draw(&[1.0, 2.0, 3.0], &[4.0, -5.0, 6.0]);
+        plot.add(&curve);
+        assert_eq!(plot.compute_bounds(), Some((1.0, 3.0, -5.0, 6.0, 0.0, 0.0)));
+        let mut surface = Surface::new();
+        surface.draw(
+            &[[0.0, 1.0], [0.0, 1.0]],
+            &[[0.0, 0.0], [1.0, 1.0]],
+            &[[0.0, 1.0], [f64::NAN, 8.0]],
+        );
+        plot.add_with_handle(&surface);
+        assert_eq!(plot.compute_bounds(), Some((0.0, 3.0, -5.0, 6.0, 0.0, 8.0)));
+    }
+
+    #[test]
+    fn extend_bounds_works() {
+        let mut bounds = None;
+        extend_bounds(&mut bounds, f64::NAN, 1.0, 2.0);
+        let (xmin, xmax, ymin, ymax, zmin, zmax) = bounds.unwrap();
+        assert!(xmin.is_nan() && xmax.is_nan());
+        assert_eq!((ymin, ymax, zmin, zmax), (1.0, 1.0, 2.0, 2.0));
+        extend_bounds(&mut bounds, 3.0, -1.0, 2.0);
+        assert_eq!(bounds, Some((3.0, 3.0, -1.0, 1.0, 2.0, 2.0)));
+    }
+
+    #[test]
+    fn save_auto_selects_extension() {
+        let mut plot = Plot::new();
+        assert_eq!(plot.approx_size(), 0);
+        assert_eq!(plot.auto_path("/tmp/fig"), Path::new("/tmp/fig.svg"));
+        plot.set_title("hello");
+        assert_eq!(plot.approx_size(), plot.buffer.len());
+        plot.set_save_auto_threshold(5);
+        assert_eq!(plot.auto_path("/tmp/fig.svg"), Path::new("/tmp/fig.png"));
+        plot.set_save_auto_threshold(plot.approx_size());
+        assert_eq!(plot.auto_path("/tmp/fig.png"), Path::new("/tmp/fig.svg"));
+    }
+
+    #[test]
+    fn save_all_captures_errors() {
+        let plots = vec![Plot::new(), Plot::new()];
+        let mut count = 0;
+        let res = Plot::save_all(&plots, &["/tmp/fig.svg"], |_, _| count += 1);
+        assert_eq!(res.err(), Some("plots and figure_paths must have the same length"));
+        let empty: &[&str] = &[];
+        Plot::save_all(&[], empty, |_, _| count += 1).unwrap();
+        assert_eq!(count, 0);
+    }
+
+    #[test]
+    fn save_all_calls_the_callback() {
+        // "true" accepts the script and prints nothing; thus, the saving succeeds without Python
+        let mut plots = vec![Plot::new(), Plot::new(), Plot::new()];
+        for plot in &mut plots {
+            plot.set_python_exe("true");
+        }
+        let paths = [
+            format!("{}/save_all_0.svg", OUT_DIR),
+            format!("{}/save_all_1.svg", OUT_DIR),
+            format!("{}/save_all_2.svg", OUT_DIR),
+        ];
+        let mut calls = Vec::new();
+        Plot::save_all(&plots, &paths, |index, path| calls.push((index, path.to_path_buf()))).unwrap();
+        assert_eq!(
+            calls,
+            vec![
+                (0, Path::new(&paths[0]).to_path_buf()),
+                (1, Path::new(&paths[1]).to_path_buf()),
+                (2, Path::new(&paths[2]).to_path_buf()),
+            ]
+        );
+
+        // stops at the first error
+        plots[1].set_python_exe("__plotpy_missing_python__");
+        calls.clear();
+        let res = Plot::save_all(&plots, &paths, |index, path| calls.push((index, path.to_path_buf())));
+        assert_eq!(
+            res.err(),
+            Some("cannot find the python executable; check the PATH or use set_python_exe")
+        );
+        assert_eq!(calls, vec![(0, Path::new(&paths[0]).to_path_buf())]);
+    }
+
+    #[test]
+    fn save_pdf_pages_captures_errors() {
+        assert_eq!(
+            Plot::save_pdf_pages(&[], "/tmp/plotpy/unit_tests/pages.pdf").err(),
+            Some("there must be at least one plot")
+        );
+    }
+
+    #[test]
+    fn pdf_pages_commands_work() {
+        let mut first = Plot::new();
+        first.set_title("first").set_save_tight(false);
+        let mut second = Plot::new();
+        second.set_title("second");
+        let commands = Plot::pdf_pages_commands(&[&first, &second], "/tmp/pages.pdf");
+        assert_eq!(
+            commands,
+            "from matplotlib.backends.backend_pdf import PdfPages\n\
+             pdf=PdfPages(r'/tmp/pages.pdf')\n\
+             plt.figure()\n\
+             EXTRA_ARTISTS=[]\n\
+             THREE_D=dict()\n\
+             THREE_D_ACTIVE=(1,1,1)\n\
+             plt.title(r'first')\n\n\
+             pdf.savefig(plt.gcf())\n\
+             plt.close()\n\
+             plt.figure()\n\
+             EXTRA_ARTISTS=[]\n\
+             THREE_D=dict()\n\
+             THREE_D_ACTIVE=(1,1,1)\n\
+             plt.title(r'second')\n\n\
+             pdf.savefig(plt.gcf(),bbox_inches='tight',bbox_extra_artists=EXTRA_ARTISTS)\n\
+             plt.close()\n\
+             pdf.close()\n"
+        );
+    }
+
+    #[test]
+    fn figure_size_pixels_works() {
+        let mut plot = Plot::new();
+        assert_eq!(plot.figure_size_pixels(), None);
+        plot.set_figure_size_inches(4.0, 3.0);
+        assert_eq!(plot.figure_size_pixels(), Some((400.0, 300.0)));
+        plot.set_dpi(50.0);
+        assert_eq!(plot.figure_size_pixels(), Some((200.0, 150.0)));
+        plot.set_save_dpi(300);
+        assert_eq!(plot.figure_size_pixels(), Some((1200.0, 900.0)));
+        plot.set_figure_size_points(72.27 * 2.0, 72.27);
+        assert_eq!(plot.figure_size_pixels(), Some((600.0, 300.0)));
+        let b: &str = "plt.gcf().set_size_inches(4,3)\n\
+                       plt.gcf().set_dpi(50)\n\
+                       plt.gcf().set_size_inches(2,1)\n";
+        assert_eq!(plot.buffer, b);
+    }
+
+    #[test]
+    fn set_theme_works() {
+        let mut plot = Plot::new();
+        assert_eq!(plot.rc_params(), "");
+        plot.set_theme(Theme::Default);
+        assert_eq!(plot.rc_params(), "");
+        plot.set_theme(Theme::Dark);
+        assert!(plot
+            .rc_params()
+            .starts_with("plt.rcParams.update({'figure.facecolor':'#1e1e1e',"));
+        assert!(plot.rc_params().contains("'axes.prop_cycle':plt.cycler(color=["));
+        plot.set_theme(Theme::Minimal);
+        assert!(plot
+            .rc_params()
+            .contains("'axes.spines.top':False,'axes.spines.right':False,"));
+        plot.set_theme(Theme::Publication).set_default_line_width(2.0);
+        let rc = plot.rc_params();
+        assert!(rc.contains("'font.family':'serif',"));
+        assert!(rc.contains("'xtick.direction':'in','ytick.direction':'in',"));
+        assert!(rc.ends_with("'savefig.dpi':300})\nplt.rcParams['lines.linewidth']=2\n"));
+        assert_eq!(rc.lines().count(), 2);
+        let script = plot.render_python("/tmp/plotpy/unit_tests/theme.svg");
+        assert!(script.contains(&format!("{}plt.rcParams.update(", PYTHON_HEADER)));
+    }
+
+    #[test]
+    fn set_suppress_warnings_works() {
+        let mut plot = Plot::new();
+        assert!(!plot
+            .render_python("/tmp/plotpy/unit_tests/warnings.svg")
+            .contains("filterwarnings"));
+        plot.set_suppress_warnings(true).set_default_line_width(2.0);
+        assert_eq!(
+            plot.rc_params(),
+            "import warnings\n\
+             warnings.filterwarnings('ignore')\n\
+             plt.rcParams['lines.linewidth']=2\n"
+        );
+        let script = plot.render_python("/tmp/plotpy/unit_tests/warnings.svg");
+        assert!(script.contains(&format!("{}import warnings\n", PYTHON_HEADER)));
+        plot.set_suppress_warnings(false);
+        assert_eq!(plot.rc_params(), "plt.rcParams['lines.linewidth']=2\n");
+    }
+
+    #[test]
+    fn rc_params_works() {
+        let mut plot = Plot::new();
+        assert_eq!(plot.rc_params(), "");
+        plot.set_default_line_width(2.5);
+        assert_eq!(plot.rc_params(), "plt.rcParams['lines.linewidth']=2.5\n");
+        plot.set_default_marker_size(8.0);
+        assert_eq!(
+            plot.rc_params(),
+            "plt.rcParams['lines.linewidth']=2.5\n\
+             plt.rcParams['lines.markersize']=8\n"
+        );
+        assert_eq!(plot.buffer, "");
+    }
+
+    #[test]
+    fn add_with_handle_works() {
+        let mut curve = Curve::new();
+        curve.draw(&[1.0, 2.0], &[3.0, 4.0]);
+        let mut plot = Plot::new();
+        let h0 = plot.add_with_handle(&curve);
+        let h1 = plot.add_with_handle(&curve);
+        assert_eq!(h0, 0);
+        assert_eq!(h1, 1);
+        plot.set_visible(h0, false).set_visible(h1, true).set_zorder(h1, 5.0);
+        let b: &str = "h0_before=set(plt.gca().get_children())\n\
+                       x=np.array([1,2,],dtype=float)\n\
+                       y=np.array([3,4,],dtype=float)\n\
+                       plt.plot(x,y)\n\
+                       h0=[a for a in plt.gca().get_children() if a not in h0_before]\n\
+                       h1_before=set(plt.gca().get_children())\n\
+                       x=np.array([1,2,],dtype=float)\n\
+                       y=np.array([3,4,],dtype=float)\n\
+                       plt.plot(x,y)\n\
+                       h1=[a for a in plt.gca().get_children() if a not in h1_before]\n\
+                       for a in h0: a.set_visible(False)\n\
+                       for a in h1: a.set_visible(True)\n\
+                       for a in h1: a.set_zorder(5)\n";
+        assert_eq!(plot.buffer, b);
+    }
+
+    #[test]
+    fn set_gradient_background_works() {
+        let mut plot = Plot::new();
+        plot.set_gradient_background("white", "#1862ab");
+        let b: &str = "import matplotlib.colors as mcl\n\
+                       import matplotlib.image as mim\n\
+                       gcm=mcl.LinearSegmentedColormap.from_list('gradient',['#1862ab','white'])\n\
+                       gim=mim.AxesImage(plt.gca(),cmap=gcm,interpolation='bicubic',origin='lower',extent=(0,1,0,1),transform=plt.gca().transAxes,zorder=-100)\n\
+                       gim.set_data(np.linspace(0,1,256).reshape(-1,1))\n\
+                       plt.gca().add_image(gim)\n";
+        assert_eq!(plot.buffer, b);
+        let mut plot = Plot::new();
+        plot.set_gradient_background(Color::Rgb(1.0, 1.0, 1.0), Color::Rgba(0.0, 0.0, 1.0, 0.5));
+        assert!(plot.buffer.contains("from_list('gradient',[(0,0,1,0.5),(1,1,1)])"));
+    }
+
     #[test]
     fn additional_features_work() {
         let mut plot = Plot::new();
@@ -1582,6 +2860,54 @@ mod tests {
         assert_eq!(plot.buffer, b);
     }
 
+    #[test]
+    fn set_margins_works() {
+        let mut plot = Plot::new();
+        plot.set_margins(0.0, 0.1);
+        assert_eq!(plot.buffer, "plt.gca().margins(0,0.1)\n");
+    }
+
+    #[test]
+    fn set_tick_font_size_works() {
+        let mut plot = Plot::new();
+        plot.set_tick_font_size(8.5).set_label_x_fontsize(12.0);
+        let b: &str = "plt.gca().tick_params(axis='both',labelsize=8.5)\n\
+                       plt.gca().xaxis.label.set_fontsize(12)\n";
+        assert_eq!(plot.buffer, b);
+    }
+
+    #[test]
+    fn set_spine_width_works() {
+        let mut plot = Plot::new();
+        plot.set_frame_border(true, false, true, false).set_spine_width(1.5);
+        assert!(plot
+            .buffer
+            .ends_with("for s in plt.gca().spines.values():\n    if s.get_visible(): s.set_linewidth(1.5)\n"));
+    }
+
+    #[test]
+    fn set_tick_direction_works() {
+        let mut plot = Plot::new();
+        plot.set_tick_direction(Direction::In, false)
+            .set_tick_direction(Direction::Out, true)
+            .set_tick_direction(Direction::InOut, false);
+        let b: &str = "plt.gca().tick_params(axis='both',which='major',direction='in')\n\
+                       plt.gca().tick_params(axis='both',which='both',direction='out')\n\
+                       plt.gca().tick_params(axis='both',which='major',direction='inout')\n";
+        assert_eq!(plot.buffer, b);
+    }
+
+    #[test]
+    fn add_secondary_x_axis_works() {
+        let mut plot = Plot::new();
+        plot.add_secondary_x_axis("2*x", "x/2", "")
+            .add_secondary_x_axis("1239.84/x", "1239.84/x", "energy [eV]");
+        let b: &str = "sax=plt.gca().secondary_xaxis('top',functions=(lambda x: 2*x,lambda x: x/2))\n\
+                       sax=plt.gca().secondary_xaxis('top',functions=(lambda x: 1239.84/x,lambda x: 1239.84/x))\n\
+                       sax.set_xlabel(r'energy [eV]')\n";
+        assert_eq!(plot.buffer, b);
+    }
+
     #[test]
     fn set_labels_3d_works() {
         let mut plot = Plot::new();
@@ -1613,4 +2939,33 @@ mod tests {
         plot.set_python_exe("python");
         assert_eq!(plot.python_exe, "python");
     }
+
+    #[test]
+    fn buffer_is_deterministic() {
+        let build = || {
+            let mut curve = Curve::new();
+            curve
+                .set_label("curve")
+                .set_line_color("red")
+                .set_marker_style("o")
+                .draw(&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0]);
+            let mut surface = Surface::new();
+            surface.set_with_wireframe(true).set_with_colorbar(true);
+            surface.draw(
+                &[[0.0, 1.0], [0.0, 1.0]],
+                &[[0.0, 0.0], [1.0, 1.0]],
+                &[[0.0, 1.0], [1.0, 2.0]],
+            );
+            let mut legend = Legend::new();
+            legend.set_fontsize(12.0).set_num_col(2).draw();
+            let mut plot = Plot::new();
+            plot.add(&curve)
+                .add(&surface)
+                .add(&legend)
+                .set_title("title")
+                .grid_and_labels("x", "y");
+            plot.buffer
+        };
+        assert_eq!(build(), build());
+    }
 }
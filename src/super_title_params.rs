@@ -24,6 +24,9 @@ pub struct SuperTitleParams {
 
     /// The font weight of the text
     fontweight: f64,
+
+    /// The top of the subplots area in figure coordinates (reserves space for the text)
+    subplots_top: Option<f64>,
 }
 
 impl SuperTitleParams {
@@ -36,6 +39,7 @@ impl SuperTitleParams {
             align_vertical: String::new(),
             fontsize: 0.0,
             fontweight: 0.0,
+            subplots_top: None,
         }
     }
 
@@ -79,6 +83,23 @@ impl SuperTitleParams {
         self
     }
 
+    /// Sets the top of the subplots area in figure coordinates (e.g., 0.9)
+    ///
+    /// This reserves space for the super-title above the subplots, avoiding overlaps when
+    /// the y location is lowered or the text is large.
+    ///
+    /// **Note:** Matplotlib's constrained layout ignores this value (with a warning);
+    /// however, the constrained layout already reserves space for the super-title.
+    pub fn set_subplots_top(&mut self, value: f64) -> &mut Self {
+        self.subplots_top = Some(value);
+        self
+    }
+
+    /// Returns the top of the subplots area, if set
+    pub(crate) fn subplots_top(&self) -> Option<f64> {
+        self.subplots_top
+    }
+
     /// Returns options for SuperTitle
     pub(crate) fn options(&self) -> String {
         let mut opt = String::new();
@@ -119,6 +140,7 @@ mod tests {
         assert_eq!(params.align_vertical.len(), 0);
         assert_eq!(params.fontsize, 0.0);
         assert_eq!(params.fontweight, 0.0);
+        assert_eq!(params.subplots_top, None);
     }
 
     #[test]
@@ -1,6 +1,29 @@
-use super::{matrix_to_array, AsMatrix, GraphMaker, StrError};
+use super::{
+    array2num, extend_bounds, generate_list_quoted, matrix_to_array, to_f64, vector_to_array, vectors3_to_arrays,
+    ArrayFormat, AsF64, AsMatrix, Color, GraphMaker, StrError,
+};
 use crate::quote_marker;
+use std::ffi::OsStr;
 use std::fmt::Write;
+use std::fs::{self, File};
+use std::io::Write as IoWrite;
+use std::path::Path;
+
+/// Defines how the colorbar is extended with triangular arrows indicating out-of-range values
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Extend {
+    /// No extension (default)
+    Neither,
+
+    /// Extends the colorbar below the minimum value
+    Min,
+
+    /// Extends the colorbar above the maximum value
+    Max,
+
+    /// Extends the colorbar below the minimum and above the maximum values
+    Both,
+}
 
 /// Generates a 3D a surface (or wireframe, or both)
 ///
@@ -46,29 +69,51 @@ use std::fmt::Write;
 ///
 /// ![integ_surface_wireframe.svg](https://raw.githubusercontent.com/cpmech/plotpy/main/figures/integ_surface_wireframe.svg)
 pub struct Surface {
-    row_stride: usize,        // Row stride
-    col_stride: usize,        // Column stride
-    with_surface: bool,       // Generates a surface
-    with_wireframe: bool,     // Generates a wireframe
-    with_points: bool,        // Generates (a scatter of) points on the surface
-    colormap_name: String,    // Colormap name
-    with_colorbar: bool,      // Draw a colorbar
-    colorbar_label: String,   // Colorbar label
-    number_format_cb: String, // Number format for labels in colorbar
-    surf_color: String,       // Const color of surface (when not using colormap)
-    surf_line_color: String,  // Color of surface lines
-    surf_line_style: String,  // Style of surface lines
-    surf_line_width: f64,     // Width of surface lines
-    wire_line_color: String,  // Color of wireframe lines
-    wire_line_style: String,  // Style of wireframe line
-    wire_line_width: f64,     // Width of wireframe line
-    point_color: String,      // Color of markers (scatter)
-    point_void: bool,         // Draws a void marker (edge only)
-    point_line_color: String, // Edge color of markers
-    point_line_width: f64,    // Edge width of markers
-    point_size: f64,          // Size of markers
-    point_style: String,      // Style of markers, e.g., "`o`", "`+`"
-    buffer: String,           // buffer
+    row_stride: usize,                              // Row stride
+    col_stride: usize,                              // Column stride
+    with_surface: bool,                             // Generates a surface
+    with_wireframe: bool,                           // Generates a wireframe
+    with_points: bool,                              // Generates (a scatter of) points on the surface
+    with_contour_projection: bool,                  // Generates contour lines projected onto a plane
+    contour_projection_dir: String,                 // Direction normal to the projection plane: "x", "y", or "z"
+    contour_projection_offset: f64,                 // Position of the projection plane along its normal
+    colormap_name: String,                          // Colormap name
+    colormap_colors: Vec<String>,                   // Colors of a custom colormap
+    with_colorbar: bool,                            // Draw a colorbar
+    colorbar_label: String,                         // Colorbar label
+    colorbar_ticks: Vec<f64>,                       // Positions of the colorbar ticks
+    colorbar_ticklabels: Vec<String>,               // Labels of the colorbar ticks
+    number_format_cb: String,                       // Number format for labels in colorbar
+    colorbar_extend: Extend,                        // Extension of the colorbar (arrows)
+    surf_color: String,                             // Const color of surface (when not using colormap)
+    surf_color_alt: String,                         // Alternate color of surface faces (checkerboard)
+    color_by_gradient: bool,                        // Colors the surface faces by the gradient magnitude of z
+    face_color_range: Option<(f64, f64)>,           // Normalization range of the color matrix (while drawing)
+    surf_line_color: String,                        // Color of surface lines
+    surf_line_style: String,                        // Style of surface lines
+    surf_line_width: f64,                           // Width of surface lines
+    surf_alpha: f64,                                // Opacity of the surface (0, 1]. A<1e-14 => A=1.0
+    with_lighting: bool,                            // Shades the surface faces with a light source
+    light_direction: (f64, f64),                    // Azimuth and altitude (degrees) of the light source
+    wire_line_color: String,                        // Color of wireframe lines
+    wire_line_style: String,                        // Style of wireframe line
+    wire_line_width: f64,                           // Width of wireframe line
+    wire_count: Option<(usize, usize)>,             // Number of wireframe lines along rows and columns
+    point_color: String,                            // Color of markers (scatter)
+    point_void: bool,                               // Draws a void marker (edge only)
+    point_line_color: String,                       // Edge color of markers
+    point_line_width: f64,                          // Edge width of markers
+    point_size: f64,                                // Size of markers
+    point_style: String,                            // Style of markers, e.g., "`o`", "`+`"
+    annotation_color: String,                       // Color of the text and leader line of annotations
+    close_alpha: bool,                              // Makes the last α ring equal to the first one (geometry)
+    with_cap: bool,                                 // Closes hemispheres and cut superquadrics with flat caps
+    bounds: Option<(f64, f64, f64, f64, f64, f64)>, // Bounds of the drawn data
+    keep_mesh: bool,                                // Keeps a copy of the last drawn meshgrid (for write_obj)
+    mesh_size: (usize, usize),                      // Dimensions (m,n) of the last drawn meshgrid
+    mesh_points: Vec<[f64; 3]>,                     // Points of the last drawn meshgrid (row-major)
+    array_format: ArrayFormat,                      // Format of the numbers in the generated arrays
+    buffer: String,                                 // buffer
 }
 
 impl Surface {
@@ -80,23 +125,45 @@ impl Surface {
             with_surface: true,
             with_wireframe: false,
             with_points: false,
+            with_contour_projection: false,
+            contour_projection_dir: "z".to_string(),
+            contour_projection_offset: 0.0,
             colormap_name: "bwr".to_string(),
+            colormap_colors: Vec::new(),
             with_colorbar: false,
             colorbar_label: String::new(),
+            colorbar_ticks: Vec::new(),
+            colorbar_ticklabels: Vec::new(),
             number_format_cb: String::new(),
+            colorbar_extend: Extend::Neither,
             surf_color: String::new(),
+            surf_color_alt: String::new(),
+            color_by_gradient: false,
+            face_color_range: None,
             surf_line_color: String::new(),
             surf_line_style: String::new(),
             surf_line_width: 0.0,
-            wire_line_color: "black".to_string(),
+            surf_alpha: 0.0,
+            with_lighting: false,
+            light_direction: (315.0, 45.0),
+            wire_line_color: "'black'".to_string(),
             wire_line_style: String::new(),
             wire_line_width: 0.0,
+            wire_count: None,
             point_color: String::new(),
             point_void: false,
             point_line_color: String::new(),
             point_line_width: 0.0,
             point_size: 0.0,
             point_style: String::new(),
+            annotation_color: "'black'".to_string(),
+            close_alpha: false,
+            with_cap: false,
+            bounds: None,
+            keep_mesh: false,
+            mesh_size: (0, 0),
+            mesh_points: Vec::new(),
+            array_format: ArrayFormat::new(),
             buffer: String::new(),
         }
     }
@@ -119,16 +186,205 @@ impl Surface {
     /// # Notes
     ///
     /// * The type `U` of the input matrices must be a number.
+    /// * If `y` or `z` do not have the same dimensions as `x`, the commands are still written
+    ///   (and Matplotlib reports the error), but the data bounds, the meshgrid for
+    ///   [Surface::write_obj], and the gradient colors are not computed.
     ///
     pub fn draw<'a, T, U>(&mut self, x: &'a T, y: &'a T, z: &'a T)
     where
         T: AsMatrix<'a, U>,
-        U: 'a + std::fmt::Display,
+        U: 'a + std::fmt::Display + AsF64,
+    {
+        let (m, n) = x.size();
+        let same_size = y.size() == (m, n) && z.size() == (m, n);
+        self.mesh_size = (m, n);
+        self.mesh_points.clear();
+        if same_size {
+            for i in 0..m {
+                for j in 0..n {
+                    let p = [to_f64(x.at(i, j)), to_f64(y.at(i, j)), to_f64(z.at(i, j))];
+                    extend_bounds(&mut self.bounds, p[0], p[1], p[2]);
+                    if self.keep_mesh {
+                        self.mesh_points.push(p);
+                    }
+                }
+            }
+        }
+        matrix_to_array(&mut self.buffer, &self.array_format, "x", x);
+        matrix_to_array(&mut self.buffer, &self.array_format, "y", y);
+        matrix_to_array(&mut self.buffer, &self.array_format, "z", z);
+        if same_size && self.with_surface && self.color_by_gradient && self.face_color_range.is_none() {
+            let gm = gradient_magnitude(
+                m,
+                n,
+                |i, j| to_f64(x.at(i, j)),
+                |i, j| to_f64(y.at(i, j)),
+                |i, j| to_f64(z.at(i, j)),
+            );
+            matrix_to_array(&mut self.buffer, &self.array_format, "gm", &gm);
+        }
+        self.plot_xyz(m, n);
+    }
+
+    /// Draws a surface, or wireframe, or both, given the coordinates along the x and y axes
+    ///
+    /// # Input
+    ///
+    /// * `x` -- (nx) values along the x axis
+    /// * `y` -- (ny) values along the y axis
+    /// * `z` -- (ny,nx) matrix with z values; i.e., `z[i][j]` corresponds to `(x[j],y[i])`
+    ///
+    /// # Notes
+    ///
+    /// * The meshgrid is generated in the Python script using `np.meshgrid`.
+    /// * The type `U` of the input matrix must be a number.
+    pub fn draw_grid<'a, T, U>(&mut self, x: &[f64], y: &[f64], z: &'a T) -> Result<(), StrError>
+    where
+        T: AsMatrix<'a, U>,
+        U: 'a + std::fmt::Display + AsF64,
     {
-        matrix_to_array(&mut self.buffer, "x", x);
-        matrix_to_array(&mut self.buffer, "y", y);
-        matrix_to_array(&mut self.buffer, "z", z);
+        let (m, n) = z.size();
+        if m != y.len() || n != x.len() {
+            return Err("z must be a (y.len(),x.len()) matrix");
+        }
+        self.mesh_size = (m, n);
+        self.mesh_points.clear();
+        for (i, yi) in y.iter().enumerate() {
+            for (j, xj) in x.iter().enumerate() {
+                let zij = to_f64(z.at(i, j));
+                extend_bounds(&mut self.bounds, *xj, *yi, zij);
+                if self.keep_mesh {
+                    self.mesh_points.push([*xj, *yi, zij]);
+                }
+            }
+        }
+        vector_to_array(&mut self.buffer, &self.array_format, "x", &x);
+        vector_to_array(&mut self.buffer, &self.array_format, "y", &y);
+        write!(&mut self.buffer, "x,y=np.meshgrid(x,y)\n").unwrap();
+        matrix_to_array(&mut self.buffer, &self.array_format, "z", z);
+        if self.with_surface && self.color_by_gradient {
+            let gm = gradient_magnitude(m, n, |_, j| x[j], |i, _| y[i], |i, j| to_f64(z.at(i, j)));
+            matrix_to_array(&mut self.buffer, &self.array_format, "gm", &gm);
+        }
+        self.plot_xyz(m, n);
+        Ok(())
+    }
+
+    /// Writes the last drawn meshgrid to a Wavefront OBJ file
+    ///
+    /// # Input
+    ///
+    /// * `path` -- may be a String, &str, or Path
+    ///
+    /// # Notes
+    ///
+    /// * Each point of the meshgrid becomes a vertex (`v` line) and each grid cell becomes
+    ///   a quadrilateral face (`f` line) connecting the adjacent vertices.
+    /// * Cells with NaN coordinates are skipped; cells with coincident corners become
+    ///   triangles or are skipped if less than three distinct corners remain.
+    /// * Only the meshgrid given to the last call of `draw` or `draw_grid` is written.
+    /// * The meshgrid is only kept if [Surface::set_keep_mesh] is enabled before drawing.
+    pub fn write_obj<S>(&self, path: &S) -> Result<(), StrError>
+    where
+        S: AsRef<OsStr> + ?Sized,
+    {
+        let (m, n) = self.mesh_size;
+        if self.mesh_points.len() == 0 {
+            return Err("there is no meshgrid to be written");
+        }
+        let mut contents = String::new();
+        for p in &self.mesh_points {
+            write!(&mut contents, "v {} {} {}\n", p[0], p[1], p[2]).unwrap();
+        }
+        for i in 0..m.saturating_sub(1) {
+            for j in 0..n.saturating_sub(1) {
+                let corners = [i * n + j, i * n + j + 1, (i + 1) * n + j + 1, (i + 1) * n + j];
+                if corners.iter().any(|c| self.mesh_points[*c].iter().any(|v| v.is_nan())) {
+                    continue;
+                }
+                let mut face: Vec<usize> = Vec::new();
+                for c in corners {
+                    if !face.iter().any(|f| self.mesh_points[*f] == self.mesh_points[c]) {
+                        face.push(c);
+                    }
+                }
+                if face.len() < 3 {
+                    continue;
+                }
+                write!(&mut contents, "f").unwrap();
+                for c in face {
+                    write!(&mut contents, " {}", c + 1).unwrap();
+                }
+                write!(&mut contents, "\n").unwrap();
+            }
+        }
+        let path = Path::new(path);
+        if let Some(p) = path.parent() {
+            fs::create_dir_all(p).map_err(|_| "cannot create directory")?;
+        }
+        let mut file = File::create(path).map_err(|_| "cannot create file")?;
+        file.write_all(contents.as_bytes()).map_err(|_| "cannot write file")?;
+        Ok(())
+    }
+
+    /// Writes the commands to draw the features of the (m,n) surface given by x, y, z
+    fn plot_xyz(&mut self, m: usize, n: usize) {
+        self.write_custom_colormap();
         if self.with_surface {
+            if self.checkerboard() && !self.color_by_gradient && self.face_color_range.is_none() {
+                write!(&mut self.buffer, "fc=[").unwrap();
+                for i in 0..m {
+                    write!(&mut self.buffer, "[").unwrap();
+                    for j in 0..n {
+                        let color = if (i + j) % 2 == 0 {
+                            &self.surf_color
+                        } else {
+                            &self.surf_color_alt
+                        };
+                        write!(&mut self.buffer, "{},", color).unwrap();
+                    }
+                    write!(&mut self.buffer, "],").unwrap();
+                }
+                write!(&mut self.buffer, "]\n").unwrap();
+            }
+            if let Some((vmin, vmax)) = self.face_color_range {
+                let cmap = self.gradient_colormap();
+                write!(
+                    &mut self.buffer,
+                    "cnm=plt.Normalize({},{})\n\
+                     fc={}(cnm(c))\n",
+                    vmin, vmax, cmap
+                )
+                .unwrap();
+            } else if self.color_by_gradient {
+                let cmap = self.gradient_colormap();
+                write!(
+                    &mut self.buffer,
+                    "gnm=plt.Normalize(np.nanmin(gm),np.nanmax(gm))\n\
+                     fc={}(gnm(gm))\n",
+                    cmap
+                )
+                .unwrap();
+            }
+            if self.with_lighting {
+                write!(
+                    &mut self.buffer,
+                    "import matplotlib.colors as mcl\n\
+                     ls=mcl.LightSource(azdeg={},altdeg={})\n",
+                    self.light_direction.0, self.light_direction.1
+                )
+                .unwrap();
+                if self.face_colors() {
+                    write!(
+                        &mut self.buffer,
+                        "fcl=np.array([[mcl.to_rgba(c) for c in row] for row in fc])\n\
+                         fc=ls.shade_rgb(fcl[...,:3],z)\n"
+                    )
+                    .unwrap();
+                } else if let Some(cmap) = self.surface_colormap() {
+                    write!(&mut self.buffer, "fc=ls.shade(z,cmap={})\n", cmap).unwrap();
+                }
+            }
             let opt_surface = self.options_surface();
             write!(&mut self.buffer, "sf=ax3d().plot_surface(x,y,z{})\n", &opt_surface).unwrap();
         }
@@ -136,17 +392,194 @@ impl Surface {
             let opt_wireframe = self.options_wireframe();
             write!(&mut self.buffer, "ax3d().plot_wireframe(x,y,z{})\n", &opt_wireframe).unwrap();
         }
+        if self.with_contour_projection {
+            let opt_projection = self.options_contour_projection();
+            write!(&mut self.buffer, "ax3d().contour(x,y,z{})\n", &opt_projection).unwrap();
+        }
         if self.with_points {
             let opt_points = self.options_points();
             write!(&mut self.buffer, "ax3d().scatter(x,y,z{})\n", &opt_points).unwrap();
         }
+        if self.with_colorbar {
+            let opt_colorbar = self.options_colorbar();
+            let shaded_cmap = if self.with_surface && self.with_lighting {
+                self.surface_colormap()
+            } else {
+                None
+            };
+            if self.with_surface && self.face_color_range.is_some() {
+                let cmap = self.gradient_colormap();
+                write!(
+                    &mut self.buffer,
+                    "cb=plt.colorbar(plt.cm.ScalarMappable(norm=cnm,cmap={}),ax=plt.gca(){})\n",
+                    cmap, &opt_colorbar
+                )
+                .unwrap();
+            } else if self.with_surface && self.color_by_gradient {
+                let cmap = self.gradient_colormap();
+                write!(
+                    &mut self.buffer,
+                    "cb=plt.colorbar(plt.cm.ScalarMappable(norm=gnm,cmap={}),ax=plt.gca(){})\n",
+                    cmap, &opt_colorbar
+                )
+                .unwrap();
+            } else if let Some(cmap) = shaded_cmap {
+                write!(
+                    &mut self.buffer,
+                    "cb=plt.colorbar(plt.cm.ScalarMappable(norm=plt.Normalize(np.nanmin(z),np.nanmax(z)),cmap={}),ax=plt.gca(){})\n",
+                    cmap, &opt_colorbar
+                )
+                .unwrap();
+            } else {
+                write!(&mut self.buffer, "cb=plt.colorbar(sf{})\n", &opt_colorbar).unwrap();
+            }
+            self.write_colorbar_details();
+        }
+    }
+
+    /// Draws a triangulated surface from scattered (unstructured) points
+    ///
+    /// # Input
+    ///
+    /// * `x` -- x coordinates of the points
+    /// * `y` -- y coordinates of the points
+    /// * `z` -- z coordinates of the points
+    ///
+    /// # Notes
+    ///
+    /// * The triangulation (Delaunay) is computed by Matplotlib in the xy-plane.
+    /// * The colormap, color, line, and alpha options of the surface are used.
+    ///   The strides, wireframe, and points options do not apply.
+    /// * A colorbar is drawn if `set_with_colorbar` is enabled.
+    pub fn draw_trisurf(&mut self, x: &[f64], y: &[f64], z: &[f64]) -> Result<(), StrError> {
+        if y.len() != x.len() || z.len() != x.len() {
+            return Err("x, y, and z must have the same length");
+        }
+        if x.len() < 3 {
+            return Err("at least 3 points are required");
+        }
+        for i in 0..x.len() {
+            extend_bounds(&mut self.bounds, x[i], y[i], z[i]);
+        }
+        vectors3_to_arrays(&mut self.buffer, &self.array_format, "x", "y", "z", &x, &y, &z);
+        self.write_custom_colormap();
+        let opt = self.options_trisurf();
+        write!(&mut self.buffer, "sf=ax3d().plot_trisurf(x,y,z{})\n", &opt).unwrap();
         if self.with_colorbar {
             let opt_colorbar = self.options_colorbar();
             write!(&mut self.buffer, "cb=plt.colorbar(sf{})\n", &opt_colorbar).unwrap();
-            if self.colorbar_label != "" {
-                write!(&mut self.buffer, "cb.ax.set_ylabel(r'{}')\n", self.colorbar_label).unwrap();
+            self.write_colorbar_details();
+        }
+        Ok(())
+    }
+
+    /// Draws a surface with holes where the mask is true
+    ///
+    /// # Input
+    ///
+    /// * `x` -- matrix with x values
+    /// * `y` -- matrix with y values
+    /// * `z` -- matrix with z values
+    /// * `mask` -- matrix indicating the masked (hidden) points
+    ///
+    /// # Notes
+    ///
+    /// * The z values of masked points are replaced by NaN; thus, Matplotlib leaves holes there.
+    /// * All matrices must have the same dimensions.
+    /// * The type `U` of the input matrices must be a number.
+    pub fn draw_masked<'a, T, U, M>(&mut self, x: &'a T, y: &'a T, z: &'a T, mask: &'a M) -> Result<(), StrError>
+    where
+        T: AsMatrix<'a, U>,
+        U: 'a + std::fmt::Display + AsF64,
+        M: AsMatrix<'a, bool>,
+    {
+        let (m, n) = x.size();
+        if y.size() != (m, n) || z.size() != (m, n) || mask.size() != (m, n) {
+            return Err("x, y, z, and mask must have the same dimensions");
+        }
+        let mut xx = vec![vec![0.0; n]; m];
+        let mut yy = vec![vec![0.0; n]; m];
+        let mut zz = vec![vec![0.0; n]; m];
+        for i in 0..m {
+            for j in 0..n {
+                xx[i][j] = to_f64(x.at(i, j));
+                yy[i][j] = to_f64(y.at(i, j));
+                zz[i][j] = if mask.at(i, j) { f64::NAN } else { to_f64(z.at(i, j)) };
             }
         }
+        self.draw(&xx, &yy, &zz);
+        Ok(())
+    }
+
+    /// Draws a surface with faces colored by a matrix of values normalized to a given range
+    ///
+    /// # Input
+    ///
+    /// * `x` -- matrix with x values
+    /// * `y` -- matrix with y values
+    /// * `z` -- matrix with z values
+    /// * `c` -- matrix with the values mapped to colors
+    /// * `vmin` -- value corresponding to the lowest color of the colormap
+    /// * `vmax` -- value corresponding to the highest color of the colormap
+    ///
+    /// # Notes
+    ///
+    /// * Using the same range for several surfaces makes their colors comparable.
+    /// * The colormap is given by `set_colormap_name` or `set_colormap_colors`.
+    /// * The color matrix takes precedence over `set_color_by_gradient` and the checkerboard colors.
+    /// * All matrices must have the same dimensions.
+    /// * The type `U` of the input matrices must be a number.
+    pub fn draw_with_colors<'a, T, U>(
+        &mut self,
+        x: &'a T,
+        y: &'a T,
+        z: &'a T,
+        c: &'a T,
+        vmin: f64,
+        vmax: f64,
+    ) -> Result<(), StrError>
+    where
+        T: AsMatrix<'a, U>,
+        U: 'a + std::fmt::Display + AsF64,
+    {
+        let (m, n) = x.size();
+        if y.size() != (m, n) || z.size() != (m, n) || c.size() != (m, n) {
+            return Err("x, y, z, and c must have the same dimensions");
+        }
+        if vmin >= vmax || vmin.is_nan() || vmax.is_nan() {
+            return Err("vmin must be smaller than vmax");
+        }
+        matrix_to_array(&mut self.buffer, &self.array_format, "c", c);
+        self.face_color_range = Some((vmin, vmax));
+        self.draw(x, y, z);
+        self.face_color_range = None;
+        Ok(())
+    }
+
+    /// Draws a text label connected by a leader line to a point on (or near) the surface
+    ///
+    /// # Input
+    ///
+    /// * `p` -- (len = 3) the annotated point
+    /// * `offset` -- (len = 3) the position of the label relative to `p`
+    /// * `label` -- the text
+    ///
+    /// **Note:** The color is given by `set_annotation_color`.
+    pub fn draw_annotation(&mut self, p: &[f64], offset: &[f64], label: &str) -> Result<(), StrError> {
+        if p.len() != 3 || offset.len() != 3 {
+            return Err("p.len() and offset.len() must be equal to 3");
+        }
+        let q = [p[0] + offset[0], p[1] + offset[1], p[2] + offset[2]];
+        extend_bounds(&mut self.bounds, p[0], p[1], p[2]);
+        extend_bounds(&mut self.bounds, q[0], q[1], q[2]);
+        write!(
+            &mut self.buffer,
+            "ax3d().plot([{},{}],[{},{}],[{},{}],color={},linewidth=0.8)\n\
+             ax3d().text({},{},{},r'{}',color={})\n",
+            p[0], q[0], p[1], q[1], p[2], q[2], self.annotation_color, q[0], q[1], q[2], label, self.annotation_color
+        )
+        .unwrap();
+        Ok(())
     }
 
     /// Sets the row stride
@@ -168,11 +601,65 @@ impl Surface {
     }
 
     /// Enables the drawing of a wireframe representing the surface
+    ///
+    /// **Note:** If the surface is colored by a colormap, the surface lines options are ignored
+    /// and the lines are drawn by the wireframe only (using the wireframe line options).
     pub fn set_with_wireframe(&mut self, flag: bool) -> &mut Self {
         self.with_wireframe = flag;
         self
     }
 
+    /// Enables the drawing of contour lines projected onto a plane
+    ///
+    /// The plane is given by `set_contour_projection`; the default is the `z = 0` plane.
+    /// The projection is drawn even if the surface is not (see `set_with_surface`).
+    pub fn set_with_contour_projection(&mut self, flag: bool) -> &mut Self {
+        self.with_contour_projection = flag;
+        self
+    }
+
+    /// Sets the plane onto which the contour lines are projected
+    ///
+    /// # Input
+    ///
+    /// * `dir` -- the direction normal to the plane: "x", "y", or "z"
+    /// * `offset` -- the position of the plane along `dir`; e.g., below the minimum z
+    pub fn set_contour_projection(&mut self, dir: &str, offset: f64) -> &mut Self {
+        self.contour_projection_dir = String::from(dir);
+        self.contour_projection_offset = offset;
+        self
+    }
+
+    /// Makes draw_superquadric, draw_sphere, and draw_hemisphere close the surface along α
+    ///
+    /// The coordinates of the last α ring are copied from the first one, thus removing the seam
+    /// caused by floating point differences. Closing is only valid if the α span is 360 degrees;
+    /// otherwise, the drawing functions return an error.
+    pub fn set_close_alpha(&mut self, flag: bool) -> &mut Self {
+        self.close_alpha = flag;
+        self
+    }
+
+    /// Returns whether the surface should be closed along α (geometry)
+    pub(crate) fn close_alpha(&self) -> bool {
+        self.close_alpha
+    }
+
+    /// Makes draw_hemisphere and draw_superquadric close the shape with flat caps (e.g., to look solid)
+    ///
+    /// The cap of a hemisphere is the disk (or sector, if the α span is smaller than 360 degrees)
+    /// at its rim. The caps of a superquadric are drawn at θ_min > -90 and θ_max < 90 (cuts).
+    /// Each cap is drawn as an additional surface.
+    pub fn set_with_cap(&mut self, flag: bool) -> &mut Self {
+        self.with_cap = flag;
+        self
+    }
+
+    /// Returns whether the shapes should be closed with flat caps (geometry)
+    pub(crate) fn with_cap(&self) -> bool {
+        self.with_cap
+    }
+
     /// Enables the drawing of (a scatter of) points representing the surface
     pub fn set_with_points(&mut self, flag: bool) -> &mut Self {
         self.with_points = flag;
@@ -216,33 +703,127 @@ impl Surface {
         self
     }
 
+    /// Sets the colors of a custom colormap (linearly interpolated)
+    ///
+    /// The custom colormap takes precedence over the colormap name. Pass an empty slice to
+    /// use the colormap name again.
+    pub fn set_colormap_colors(&mut self, colors: &[&str]) -> &mut Self {
+        self.colormap_colors = colors.iter().map(|c| c.to_string()).collect();
+        self
+    }
+
     /// Sets option to draw a colorbar
     pub fn set_with_colorbar(&mut self, flag: bool) -> &mut Self {
         self.with_colorbar = flag;
         self
     }
 
+    /// Sets the extension of the colorbar with triangular arrows for out-of-range values
+    pub fn set_colorbar_extend(&mut self, extend: Extend) -> &mut Self {
+        self.colorbar_extend = extend;
+        self
+    }
+
     /// Sets the colorbar label
     pub fn set_colorbar_label(&mut self, label: &str) -> &mut Self {
         self.colorbar_label = String::from(label);
         self
     }
 
+    /// Sets the positions of the colorbar ticks (with the default labels)
+    pub fn set_colorbar_ticks(&mut self, positions: &[f64]) -> &mut Self {
+        self.colorbar_ticks = positions.to_vec();
+        self.colorbar_ticklabels.clear();
+        self
+    }
+
+    /// Sets the positions and the labels of the colorbar ticks (e.g., category names)
+    ///
+    /// Returns an error if the number of labels is not equal to the number of positions.
+    pub fn set_colorbar_ticks_and_labels(&mut self, positions: &[f64], labels: &[&str]) -> Result<&mut Self, StrError> {
+        if labels.len() != positions.len() {
+            return Err("the number of colorbar tick labels must be equal to the number of ticks");
+        }
+        self.colorbar_ticks = positions.to_vec();
+        self.colorbar_ticklabels = labels.iter().map(|l| l.to_string()).collect();
+        Ok(self)
+    }
+
     /// Sets the number format for the labels in the colorbar (cb)
     pub fn set_number_format_cb(&mut self, format: &str) -> &mut Self {
         self.number_format_cb = String::from(format);
         self
     }
 
+    /// Enables the shading of the surface faces by a light source (improves the depth perception)
+    ///
+    /// The light direction is given by [Surface::set_light_direction]. The colormapped, gradient,
+    /// color-range, and checkerboard colors are shaded; a constant color is shaded by Matplotlib.
+    /// This option applies to `draw` and its variants, but not to `draw_trisurf`.
+    pub fn set_with_lighting(&mut self, flag: bool) -> &mut Self {
+        self.with_lighting = flag;
+        self
+    }
+
+    /// Sets the direction of the light source used by [Surface::set_with_lighting]
+    ///
+    /// * `azimuth` -- azimuth (degrees clockwise from north) of the light source (default = 315)
+    /// * `altitude` -- altitude (degrees up from the horizontal plane) of the light source (default = 45)
+    pub fn set_light_direction(&mut self, azimuth: f64, altitude: f64) -> &mut Self {
+        self.light_direction = (azimuth, altitude);
+        self
+    }
+
     /// Sets a constant color for the surface (disables colormap)
-    pub fn set_surf_color(&mut self, color: &str) -> &mut Self {
-        self.surf_color = String::from(color);
+    ///
+    /// The color may be given by name (e.g., "red" or "#ff0000") or as a [Color]
+    pub fn set_surf_color<C>(&mut self, color: C) -> &mut Self
+    where
+        C: Into<Color>,
+    {
+        self.surf_color = color.into().to_python();
+        self
+    }
+
+    /// Sets an alternate color for the surface faces, yielding a checkerboard pattern
+    ///
+    /// **Note:** This option is only used if the (constant) color of the surface is set;
+    /// see [Surface::set_surf_color()]. The faces alternate between the two colors.
+    ///
+    /// The color may be given by name (e.g., "red" or "#ff0000") or as a [Color]
+    pub fn set_surf_color_alt<C>(&mut self, color: C) -> &mut Self
+    where
+        C: Into<Color>,
+    {
+        self.surf_color_alt = color.into().to_python();
+        self
+    }
+
+    /// Sets option to color the surface faces by the magnitude of the gradient of z (e.g., slope of terrain)
+    ///
+    /// The gradient is computed from the x, y, z matrices using finite differences and the
+    /// colors are given by the colormap. This option has priority over the surface color(s).
+    pub fn set_color_by_gradient(&mut self, flag: bool) -> &mut Self {
+        self.color_by_gradient = flag;
+        self
+    }
+
+    /// Sets the opacity of the surface (0, 1]
+    ///
+    /// Values ≤ 0 or > 1 are ignored (i.e., the surface is opaque).
+    pub fn set_surface_alpha(&mut self, alpha: f64) -> &mut Self {
+        self.surf_alpha = alpha;
         self
     }
 
     /// Sets the color of surface lines
-    pub fn set_surf_line_color(&mut self, color: &str) -> &mut Self {
-        self.surf_line_color = String::from(color);
+    ///
+    /// The color may be given by name (e.g., "red" or "#ff0000") or as a [Color]
+    pub fn set_surf_line_color<C>(&mut self, color: C) -> &mut Self
+    where
+        C: Into<Color>,
+    {
+        self.surf_line_color = color.into().to_python();
         self
     }
 
@@ -265,8 +846,13 @@ impl Surface {
     // -- wireframe ------------------------------------------------------------------------------
 
     /// Sets the color of wireframe lines
-    pub fn set_wire_line_color(&mut self, color: &str) -> &mut Self {
-        self.wire_line_color = String::from(color);
+    ///
+    /// The color may be given by name (e.g., "red" or "#ff0000") or as a [Color]
+    pub fn set_wire_line_color<C>(&mut self, color: C) -> &mut Self
+    where
+        C: Into<Color>,
+    {
+        self.wire_line_color = color.into().to_python();
         self
     }
 
@@ -286,11 +872,25 @@ impl Surface {
         self
     }
 
+    /// Sets a fixed number of wireframe lines along the rows (`n_u`) and columns (`n_v`)
+    ///
+    /// This option makes the density of the wireframe independent of the grid resolution.
+    /// The strides are not applied to the wireframe when this option is set.
+    pub fn set_wireframe_count(&mut self, n_u: usize, n_v: usize) -> &mut Self {
+        self.wire_count = Some((n_u, n_v));
+        self
+    }
+
     // -- scatter --------------------------------------------------------------------------------
 
     /// Sets the color of point markers
-    pub fn set_point_color(&mut self, color: &str) -> &mut Self {
-        self.point_color = String::from(color);
+    ///
+    /// The color may be given by name (e.g., "red" or "#ff0000") or as a [Color]
+    pub fn set_point_color<C>(&mut self, color: C) -> &mut Self
+    where
+        C: Into<Color>,
+    {
+        self.point_color = color.into().to_python();
         self.point_void = false;
         self
     }
@@ -302,8 +902,13 @@ impl Surface {
     }
 
     /// Sets the edge color of point markers
-    pub fn set_point_line_color(&mut self, color: &str) -> &mut Self {
-        self.point_line_color = String::from(color);
+    ///
+    /// The color may be given by name (e.g., "red" or "#ff0000") or as a [Color]
+    pub fn set_point_line_color<C>(&mut self, color: C) -> &mut Self
+    where
+        C: Into<Color>,
+    {
+        self.point_line_color = color.into().to_python();
         self
     }
 
@@ -330,8 +935,109 @@ impl Surface {
         self
     }
 
+    /// Sets the color of the text and leader line of annotations
+    ///
+    /// See [Surface::draw_annotation]
+    ///
+    /// The color may be given by name (e.g., "red" or "#ff0000") or as a [Color]
+    pub fn set_annotation_color<C>(&mut self, color: C) -> &mut Self
+    where
+        C: Into<Color>,
+    {
+        self.annotation_color = color.into().to_python();
+        self
+    }
+
+    /// Enables keeping a copy of the last drawn meshgrid, as required by [Surface::write_obj] (default = false)
+    pub fn set_keep_mesh(&mut self, flag: bool) -> &mut Self {
+        self.keep_mesh = flag;
+        self
+    }
+
+    /// Sets the format of the numbers in the generated lists and arrays
+    pub fn set_array_format(&mut self, format: &ArrayFormat) -> &mut Self {
+        self.array_format = format.clone();
+        self
+    }
+
     // -- options --------------------------------------------------------------------------------
 
+    /// Returns the colormap used to color the surface by the gradient magnitude or the color matrix
+    fn gradient_colormap(&self) -> String {
+        if self.colormap_colors.len() > 0 {
+            "cmap".to_string()
+        } else if self.colormap_name != "" {
+            format!("plt.get_cmap('{}')", self.colormap_name)
+        } else {
+            "plt.get_cmap('viridis')".to_string()
+        }
+    }
+
+    /// Writes the label and the ticks of the colorbar (cb)
+    fn write_colorbar_details(&mut self) {
+        if self.colorbar_label != "" {
+            write!(&mut self.buffer, "cb.ax.set_ylabel(r'{}')\n", self.colorbar_label).unwrap();
+        }
+        if self.colorbar_ticks.len() > 0 {
+            write!(
+                &mut self.buffer,
+                "cb.set_ticks({})\n",
+                array2num(&self.array_format, &self.colorbar_ticks)
+            )
+            .unwrap();
+        }
+        if self.colorbar_ticklabels.len() > 0 {
+            generate_list_quoted(
+                &mut self.buffer,
+                &self.array_format,
+                "cb_labels",
+                &self.colorbar_ticklabels,
+            );
+            write!(&mut self.buffer, "cb.set_ticklabels(cb_labels)\n").unwrap();
+        }
+    }
+
+    /// Writes the definition of the custom colormap, if any
+    fn write_custom_colormap(&mut self) {
+        if self.colormap_colors.len() > 0 {
+            write!(&mut self.buffer, "import matplotlib.colors as mcl\n").unwrap();
+            generate_list_quoted(
+                &mut self.buffer,
+                &self.array_format,
+                "cmap_colors",
+                &self.colormap_colors,
+            );
+            write!(
+                &mut self.buffer,
+                "cmap=mcl.LinearSegmentedColormap.from_list('custom',cmap_colors)\n"
+            )
+            .unwrap();
+        }
+    }
+
+    /// Returns whether the faces of the surface are given by the fc matrix (gradient, color range, or checkerboard)
+    fn face_colors(&self) -> bool {
+        self.color_by_gradient || self.face_color_range.is_some() || self.checkerboard()
+    }
+
+    /// Returns the colormap of the surface, if the surface is colormapped
+    fn surface_colormap(&self) -> Option<String> {
+        if self.face_colors() || self.surf_color != "" {
+            None
+        } else if self.colormap_colors.len() > 0 {
+            Some("cmap".to_string())
+        } else if self.colormap_name != "" {
+            Some(format!("plt.get_cmap('{}')", self.colormap_name))
+        } else {
+            None
+        }
+    }
+
+    /// Returns whether the faces of the surface alternate between two colors
+    fn checkerboard(&self) -> bool {
+        self.surf_color != "" && self.surf_color_alt != ""
+    }
+
     /// Returns options for surface
     fn options_surface(&self) -> String {
         let mut opt = String::new();
@@ -341,15 +1047,54 @@ impl Surface {
         if self.col_stride > 0 {
             write!(&mut opt, ",cstride={}", self.col_stride).unwrap();
         }
-        if self.surf_color != "" {
-            write!(&mut opt, ",color='{}'", self.surf_color).unwrap();
+        let mut colormapped = false;
+        if self.face_colors() {
+            write!(&mut opt, ",facecolors=fc").unwrap();
+        } else if let Some(cmap) = self.surface_colormap() {
+            if self.with_lighting {
+                write!(&mut opt, ",facecolors=fc").unwrap();
+            } else {
+                write!(&mut opt, ",cmap={}", cmap).unwrap();
+            }
+            colormapped = true;
         } else {
-            if self.colormap_name != "" {
-                write!(&mut opt, ",cmap=plt.get_cmap('{}')", self.colormap_name).unwrap();
+            if self.surf_color != "" {
+                write!(&mut opt, ",color={}", self.surf_color).unwrap();
             }
+            if self.with_lighting {
+                write!(&mut opt, ",shade=True,lightsource=ls").unwrap();
+            }
+        }
+        // with a colormap, the lines are drawn by the wireframe (avoids conflicting options)
+        if !(colormapped && self.with_wireframe) {
+            if self.surf_line_color != "" {
+                write!(&mut opt, ",edgecolors={}", self.surf_line_color).unwrap();
+            }
+            if self.surf_line_style != "" {
+                write!(&mut opt, ",linestyle='{}'", self.surf_line_style).unwrap();
+            }
+            if self.surf_line_width > 0.0 {
+                write!(&mut opt, ",linewidth={}", self.surf_line_width).unwrap();
+            }
+        }
+        if self.surf_alpha > 0.0 && self.surf_alpha <= 1.0 {
+            write!(&mut opt, ",alpha={}", self.surf_alpha).unwrap();
+        }
+        opt
+    }
+
+    /// Returns options for triangulated surfaces
+    fn options_trisurf(&self) -> String {
+        let mut opt = String::new();
+        if self.surf_color != "" {
+            write!(&mut opt, ",color={}", self.surf_color).unwrap();
+        } else if self.colormap_colors.len() > 0 {
+            write!(&mut opt, ",cmap=cmap").unwrap();
+        } else if self.colormap_name != "" {
+            write!(&mut opt, ",cmap=plt.get_cmap('{}')", self.colormap_name).unwrap();
         }
         if self.surf_line_color != "" {
-            write!(&mut opt, ",edgecolors='{}'", self.surf_line_color).unwrap();
+            write!(&mut opt, ",edgecolors={}", self.surf_line_color).unwrap();
         }
         if self.surf_line_style != "" {
             write!(&mut opt, ",linestyle='{}'", self.surf_line_style).unwrap();
@@ -357,20 +1102,27 @@ impl Surface {
         if self.surf_line_width > 0.0 {
             write!(&mut opt, ",linewidth={}", self.surf_line_width).unwrap();
         }
+        if self.surf_alpha > 0.0 && self.surf_alpha <= 1.0 {
+            write!(&mut opt, ",alpha={}", self.surf_alpha).unwrap();
+        }
         opt
     }
 
     /// Returns options for wireframe
     fn options_wireframe(&self) -> String {
         let mut opt = String::new();
-        if self.row_stride > 0 {
-            write!(&mut opt, ",rstride={}", self.row_stride).unwrap();
-        }
-        if self.col_stride > 0 {
-            write!(&mut opt, ",cstride={}", self.col_stride).unwrap();
+        if let Some((n_u, n_v)) = self.wire_count {
+            write!(&mut opt, ",rcount={},ccount={}", n_u, n_v).unwrap();
+        } else {
+            if self.row_stride > 0 {
+                write!(&mut opt, ",rstride={}", self.row_stride).unwrap();
+            }
+            if self.col_stride > 0 {
+                write!(&mut opt, ",cstride={}", self.col_stride).unwrap();
+            }
         }
         if self.wire_line_color != "" {
-            write!(&mut opt, ",color='{}'", self.wire_line_color).unwrap();
+            write!(&mut opt, ",color={}", self.wire_line_color).unwrap();
         }
         if self.wire_line_style != "" {
             write!(&mut opt, ",linestyle='{}'", self.wire_line_style).unwrap();
@@ -402,18 +1154,42 @@ impl Surface {
         // note: unlike contour and surface, scatter requires setting 'c=z'
         if self.point_void {
             let lc = if self.point_line_color == "" {
-                "black"
+                "'black'"
             } else {
                 self.point_line_color.as_str()
             };
-            write!(&mut opt, ",color='none',edgecolor='{}'", lc).unwrap();
+            write!(&mut opt, ",color='none',edgecolor={}", lc).unwrap();
         } else if self.point_color != "" {
-            write!(&mut opt, ",color='{}'", self.point_color).unwrap();
+            write!(&mut opt, ",color={}", self.point_color).unwrap();
+            if self.point_line_color != "" {
+                write!(&mut opt, ",edgecolor={}", self.point_line_color).unwrap();
+            }
+        } else if self.colormap_colors.len() > 0 || self.colormap_name != "" {
+            if self.colormap_colors.len() > 0 {
+                write!(&mut opt, ",c=z,cmap=cmap").unwrap();
+            } else {
+                write!(&mut opt, ",c=z,cmap=plt.get_cmap('{}')", self.colormap_name).unwrap();
+            }
             if self.point_line_color != "" {
-                write!(&mut opt, ",edgecolor='{}'", self.point_line_color).unwrap();
+                write!(&mut opt, ",edgecolors={}", self.point_line_color).unwrap();
             }
+        }
+        opt
+    }
+
+    /// Returns options for the contour projection
+    fn options_contour_projection(&self) -> String {
+        let mut opt = String::new();
+        write!(
+            &mut opt,
+            ",zdir='{}',offset={}",
+            self.contour_projection_dir, self.contour_projection_offset
+        )
+        .unwrap();
+        if self.colormap_colors.len() > 0 {
+            write!(&mut opt, ",cmap=cmap").unwrap();
         } else if self.colormap_name != "" {
-            write!(&mut opt, ",c=z,cmap=plt.get_cmap('{}')", self.colormap_name).unwrap();
+            write!(&mut opt, ",cmap=plt.get_cmap('{}')", self.colormap_name).unwrap();
         }
         opt
     }
@@ -424,6 +1200,12 @@ impl Surface {
         if self.number_format_cb != "" {
             write!(&mut opt, ",format='{}'", self.number_format_cb).unwrap();
         }
+        match self.colorbar_extend {
+            Extend::Neither => (),
+            Extend::Min => write!(&mut opt, ",extend='min'").unwrap(),
+            Extend::Max => write!(&mut opt, ",extend='max'").unwrap(),
+            Extend::Both => write!(&mut opt, ",extend='both'").unwrap(),
+        }
         opt
     }
 
@@ -466,12 +1248,46 @@ impl Surface {
     }
 }
 
+/// Computes the magnitude of the gradient of z(x,y) given on a (m,n) structured grid
+///
+/// The derivatives along the grid directions are computed using central differences
+/// (one-sided at the boundaries) and transformed to the x-y system by the chain rule.
+fn gradient_magnitude<X, Y, Z>(m: usize, n: usize, x: X, y: Y, z: Z) -> Vec<Vec<f64>>
+where
+    X: Fn(usize, usize) -> f64,
+    Y: Fn(usize, usize) -> f64,
+    Z: Fn(usize, usize) -> f64,
+{
+    let mut gm = vec![vec![0.0; n]; m];
+    for (i, row) in gm.iter_mut().enumerate() {
+        let (im, ip) = (if i > 0 { i - 1 } else { i }, usize::min(i + 1, m - 1));
+        for (j, g) in row.iter_mut().enumerate() {
+            let (jm, jp) = (if j > 0 { j - 1 } else { j }, usize::min(j + 1, n - 1));
+            let (x_i, y_i, z_i) = (x(ip, j) - x(im, j), y(ip, j) - y(im, j), z(ip, j) - z(im, j));
+            let (x_j, y_j, z_j) = (x(i, jp) - x(i, jm), y(i, jp) - y(i, jm), z(i, jp) - z(i, jm));
+            let det = x_j * y_i - y_j * x_i;
+            if det != 0.0 {
+                let dz_dx = (z_j * y_i - y_j * z_i) / det;
+                let dz_dy = (x_j * z_i - z_j * x_i) / det;
+                *g = f64::sqrt(dz_dx * dz_dx + dz_dy * dz_dy);
+            }
+        }
+    }
+    gm
+}
+
 impl GraphMaker for Surface {
     fn get_buffer<'a>(&'a self) -> &'a String {
         &self.buffer
     }
     fn clear_buffer(&mut self) {
         self.buffer.clear();
+        self.bounds = None;
+        self.mesh_size = (0, 0);
+        self.mesh_points.clear();
+    }
+    fn data_bounds(&self) -> Option<(f64, f64, f64, f64, f64, f64)> {
+        self.bounds
     }
 }
 
@@ -479,8 +1295,8 @@ impl GraphMaker for Surface {
 
 #[cfg(test)]
 mod tests {
-    use super::Surface;
-    use crate::GraphMaker;
+    use super::{gradient_magnitude, Extend, Surface};
+    use crate::{Color, GraphMaker};
 
     #[test]
     fn new_works() {
@@ -493,7 +1309,8 @@ mod tests {
         assert_eq!(surface.with_colorbar, false);
         assert_eq!(surface.colorbar_label.len(), 0);
         assert_eq!(surface.number_format_cb.len(), 0);
-        assert_eq!(surface.wire_line_color, "black".to_string());
+        assert_eq!(surface.colorbar_extend, Extend::Neither);
+        assert_eq!(surface.wire_line_color, "'black'".to_string());
         assert_eq!(surface.wire_line_style.len(), 0);
         assert_eq!(surface.wire_line_width, 0.0);
         assert_eq!(surface.buffer.len(), 0);
@@ -532,6 +1349,187 @@ mod tests {
             opt,
             ",cmap=plt.get_cmap('bwr'),edgecolors='red',linestyle='--',linewidth=2.5"
         );
+
+        surface.set_surface_alpha(0.4);
+        let opt = surface.options_surface();
+        assert!(opt.ends_with(",linewidth=2.5,alpha=0.4"));
+        surface.set_surface_alpha(1.5);
+        let opt = surface.options_surface();
+        assert!(!opt.contains("alpha"));
+    }
+
+    #[test]
+    fn colorbar_ticks_and_labels_work() {
+        let x = vec![vec![0.0, 1.0], vec![0.0, 1.0]];
+        let y = vec![vec![0.0, 0.0], vec![1.0, 1.0]];
+        let z = vec![vec![0.0, 1.0], vec![1.0, 2.0]];
+        let mut surface = Surface::new();
+        assert_eq!(
+            surface
+                .set_colorbar_ticks_and_labels(&[0.0, 1.0, 2.0], &["low", "high"])
+                .err(),
+            Some("the number of colorbar tick labels must be equal to the number of ticks")
+        );
+        surface
+            .set_with_colorbar(true)
+            .set_colorbar_ticks_and_labels(&[0.0, 1.0, 2.0], &["low", "mid", "high"])
+            .unwrap();
+        surface.draw(&x, &y, &z);
+        assert!(surface.buffer.ends_with(
+            "cb=plt.colorbar(sf)\n\
+             cb.set_ticks([0,1,2])\n\
+             cb_labels=['low','mid','high',]\n\
+             cb.set_ticklabels(cb_labels)\n"
+        ));
+
+        surface.clear_buffer();
+        surface.set_colorbar_ticks(&[0.0, 2.0]);
+        surface.draw(&x, &y, &z);
+        assert!(surface.buffer.ends_with(
+            "cb=plt.colorbar(sf)\n\
+             cb.set_ticks([0,2])\n"
+        ));
+
+        surface.clear_buffer();
+        surface.set_colorbar_ticks(&[]).set_with_colorbar(false);
+        surface.draw(&x, &y, &z);
+        assert!(!surface.buffer.contains("cb"));
+    }
+
+    #[test]
+    fn draw_trisurf_works() {
+        let mut surface = Surface::new();
+        assert_eq!(
+            surface
+                .draw_trisurf(&[0.0, 1.0, 0.0], &[0.0, 0.0], &[0.0, 1.0, 2.0])
+                .err(),
+            Some("x, y, and z must have the same length")
+        );
+        assert_eq!(
+            surface
+                .draw_trisurf(&[0.0, 1.0, 0.0], &[0.0, 0.0, 1.0], &[0.0, 1.0])
+                .err(),
+            Some("x, y, and z must have the same length")
+        );
+        assert_eq!(
+            surface.draw_trisurf(&[0.0, 1.0], &[0.0, 0.0], &[0.0, 1.0]).err(),
+            Some("at least 3 points are required")
+        );
+        surface
+            .set_row_stride(2)
+            .set_surface_alpha(0.5)
+            .set_with_colorbar(true)
+            .set_colorbar_label("height");
+        surface
+            .draw_trisurf(&[0.0, 1.0, 0.0, 1.0], &[0.0, 0.0, 1.0, 1.0], &[0.0, 1.0, 2.0, 3.0])
+            .unwrap();
+        let b: &str = "x=np.array([0,1,0,1,],dtype=float)\n\
+                       y=np.array([0,0,1,1,],dtype=float)\n\
+                       z=np.array([0,1,2,3,],dtype=float)\n\
+                       sf=ax3d().plot_trisurf(x,y,z,cmap=plt.get_cmap('bwr'),alpha=0.5)\n\
+                       cb=plt.colorbar(sf)\n\
+                       cb.ax.set_ylabel(r'height')\n";
+        assert_eq!(surface.buffer, b);
+        assert_eq!(surface.data_bounds(), Some((0.0, 1.0, 0.0, 1.0, 0.0, 3.0)));
+        surface.set_surf_color("red").set_surf_line_color("black");
+        assert_eq!(surface.options_trisurf(), ",color='red',edgecolors='black',alpha=0.5");
+    }
+
+    #[test]
+    fn draw_with_colormap_and_wireframe_works() {
+        let x = vec![vec![0.0, 1.0], vec![0.0, 1.0]];
+        let y = vec![vec![0.0, 0.0], vec![1.0, 1.0]];
+        let z = vec![vec![0.0, 2.0], vec![0.0, 2.0]];
+        let mut surface = Surface::new();
+        surface
+            .set_with_wireframe(true)
+            .set_surf_line_color("red")
+            .set_surf_line_width(2.0)
+            .set_wire_line_color("black")
+            .set_wire_line_width(0.5);
+        surface.draw(&x, &y, &z);
+        let b: &str = "x=np.array([[0,1,],[0,1,],],dtype=float)\n\
+                       y=np.array([[0,0,],[1,1,],],dtype=float)\n\
+                       z=np.array([[0,2,],[0,2,],],dtype=float)\n\
+                       sf=ax3d().plot_surface(x,y,z,cmap=plt.get_cmap('bwr'))\n\
+                       ax3d().plot_wireframe(x,y,z,color='black',linewidth=0.5)\n";
+        assert_eq!(surface.buffer, b);
+        surface.set_surf_color("blue");
+        assert_eq!(surface.options_surface(), ",color='blue',edgecolors='red',linewidth=2");
+    }
+
+    #[test]
+    fn lighting_works() {
+        let x = vec![vec![0.0, 1.0], vec![0.0, 1.0]];
+        let y = vec![vec![0.0, 0.0], vec![1.0, 1.0]];
+        let z = vec![vec![0.0, 2.0], vec![0.0, 2.0]];
+        let xyz: &str = "x=np.array([[0,1,],[0,1,],],dtype=float)\n\
+                         y=np.array([[0,0,],[1,1,],],dtype=float)\n\
+                         z=np.array([[0,2,],[0,2,],],dtype=float)\n";
+        let mut surface = Surface::new();
+        surface.set_light_direction(270.0, 30.0).draw(&x, &y, &z);
+        assert_eq!(
+            surface.buffer,
+            format!("{}sf=ax3d().plot_surface(x,y,z,cmap=plt.get_cmap('bwr'))\n", xyz)
+        );
+
+        // colormapped colors are shaded
+        surface.clear_buffer();
+        surface.set_with_lighting(true).set_with_colorbar(true).draw(&x, &y, &z);
+        let b = format!(
+            "{}import matplotlib.colors as mcl\n\
+             ls=mcl.LightSource(azdeg=270,altdeg=30)\n\
+             fc=ls.shade(z,cmap=plt.get_cmap('bwr'))\n\
+             sf=ax3d().plot_surface(x,y,z,facecolors=fc)\n\
+             cb=plt.colorbar(plt.cm.ScalarMappable(norm=plt.Normalize(np.nanmin(z),np.nanmax(z)),cmap=plt.get_cmap('bwr')),ax=plt.gca())\n",
+            xyz
+        );
+        assert_eq!(surface.buffer, b);
+
+        // constant color
+        surface.clear_buffer();
+        surface
+            .set_with_colorbar(false)
+            .set_surf_color("#ff0000")
+            .draw(&x, &y, &z);
+        let b = format!(
+            "{}import matplotlib.colors as mcl\n\
+             ls=mcl.LightSource(azdeg=270,altdeg=30)\n\
+             sf=ax3d().plot_surface(x,y,z,color='#ff0000',shade=True,lightsource=ls)\n",
+            xyz
+        );
+        assert_eq!(surface.buffer, b);
+
+        // checkerboard colors are shaded
+        surface.clear_buffer();
+        surface.set_surf_color_alt("white").draw(&x, &y, &z);
+        assert!(surface.buffer.ends_with(
+            "fc=[['#ff0000','white',],['white','#ff0000',],]\n\
+             import matplotlib.colors as mcl\n\
+             ls=mcl.LightSource(azdeg=270,altdeg=30)\n\
+             fcl=np.array([[mcl.to_rgba(c) for c in row] for row in fc])\n\
+             fc=ls.shade_rgb(fcl[...,:3],z)\n\
+             sf=ax3d().plot_surface(x,y,z,facecolors=fc)\n"
+        ));
+    }
+
+    #[test]
+    fn draw_with_checkerboard_works() {
+        let mut surface = Surface::new();
+        surface.set_surf_color_alt("white");
+        assert_eq!(surface.options_surface(), ",cmap=plt.get_cmap('bwr')");
+        surface.set_surf_color("black");
+        assert_eq!(surface.options_surface(), ",facecolors=fc");
+        let x = vec![vec![0.0, 1.0, 2.0], vec![0.0, 1.0, 2.0]];
+        let y = vec![vec![0.0, 0.0, 0.0], vec![1.0, 1.0, 1.0]];
+        let z = vec![vec![0.0, 0.0, 0.0], vec![0.0, 0.0, 0.0]];
+        surface.draw(&x, &y, &z);
+        let b: &str = "x=np.array([[0,1,2,],[0,1,2,],],dtype=float)\n\
+                       y=np.array([[0,0,0,],[1,1,1,],],dtype=float)\n\
+                       z=np.array([[0,0,0,],[0,0,0,],],dtype=float)\n\
+                       fc=[['black','white','black',],['white','black','white',],]\n\
+                       sf=ax3d().plot_surface(x,y,z,facecolors=fc)\n";
+        assert_eq!(surface.buffer, b);
     }
 
     #[test]
@@ -545,6 +1543,14 @@ mod tests {
             .set_wire_line_width(2.5);
         let opt = surface.options_wireframe();
         assert_eq!(opt, ",rstride=3,cstride=4,color='red',linestyle='--',linewidth=2.5");
+
+        surface.set_wireframe_count(10, 20);
+        let opt = surface.options_wireframe();
+        assert_eq!(opt, ",rcount=10,ccount=20,color='red',linestyle='--',linewidth=2.5");
+        assert_eq!(
+            surface.options_surface(),
+            ",rstride=3,cstride=4,cmap=plt.get_cmap('bwr')"
+        );
     }
 
     #[test]
@@ -583,6 +1589,11 @@ mod tests {
         surface.set_point_void(true).set_point_line_color("");
         let opt = surface.options_points();
         assert_eq!(opt, ",linewidths=3,s=100,marker='*',color='none',edgecolor='black'");
+        // edge of points colored by value
+        let mut surface = Surface::new();
+        surface.set_point_line_color("black").set_point_line_width(0.5);
+        let opt = surface.options_points();
+        assert_eq!(opt, ",linewidths=0.5,c=z,cmap=plt.get_cmap('bwr'),edgecolors='black'");
     }
 
     #[test]
@@ -591,6 +1602,14 @@ mod tests {
         surface.set_number_format_cb("%.3f");
         let opt = surface.options_colorbar();
         assert_eq!(opt, ",format='%.3f'");
+        surface.set_colorbar_extend(Extend::Min);
+        assert_eq!(surface.options_colorbar(), ",format='%.3f',extend='min'");
+        surface.set_colorbar_extend(Extend::Max);
+        assert_eq!(surface.options_colorbar(), ",format='%.3f',extend='max'");
+        surface.set_colorbar_extend(Extend::Both);
+        assert_eq!(surface.options_colorbar(), ",format='%.3f',extend='both'");
+        surface.set_colorbar_extend(Extend::Neither);
+        assert_eq!(surface.options_colorbar(), ",format='%.3f'");
     }
 
     #[test]
@@ -616,6 +1635,222 @@ mod tests {
         assert_eq!(surface.buffer, "");
     }
 
+    #[test]
+    fn draw_masked_works() {
+        let x = vec![vec![0.0, 1.0], vec![0.0, 1.0]];
+        let y = vec![vec![0.0, 0.0], vec![1.0, 1.0]];
+        let z = vec![vec![1.0, 2.0], vec![3.0, 4.0]];
+        let mut surface = Surface::new();
+        let res = surface.draw_masked(&x, &y, &z, &vec![vec![false, true]]);
+        assert_eq!(res.err(), Some("x, y, z, and mask must have the same dimensions"));
+        let res = surface.draw_masked(&x, &y, &vec![vec![1.0, 2.0]], &vec![vec![false, true]]);
+        assert_eq!(res.err(), Some("x, y, z, and mask must have the same dimensions"));
+        let mask = vec![vec![false, true], vec![false, false]];
+        surface.draw_masked(&x, &y, &z, &mask).unwrap();
+        let b: &str = "x=np.array([[0,1,],[0,1,],],dtype=float)\n\
+                       y=np.array([[0,0,],[1,1,],],dtype=float)\n\
+                       z=np.array([[1,np.nan,],[3,4,],],dtype=float)\n\
+                       sf=ax3d().plot_surface(x,y,z,cmap=plt.get_cmap('bwr'))\n";
+        assert_eq!(surface.buffer, b);
+    }
+
+    #[test]
+    fn gradient_magnitude_works() {
+        // z = 3x + 4y on a skewed grid
+        let x = vec![vec![0.0, 1.0, 2.0], vec![0.5, 1.5, 2.5]];
+        let y = vec![vec![0.0, 0.0, 0.0], vec![1.0, 1.0, 1.0]];
+        let gm = gradient_magnitude(
+            2,
+            3,
+            |i, j| x[i][j],
+            |i, j| y[i][j],
+            |i, j| 3.0 * x[i][j] + 4.0 * y[i][j],
+        );
+        for row in &gm {
+            for v in row {
+                assert!(f64::abs(v - 5.0) < 1e-14);
+            }
+        }
+        // degenerate grid
+        let gm = gradient_magnitude(1, 2, |_, j| j as f64, |_, _| 0.0, |_, j| j as f64);
+        assert_eq!(gm, &[[0.0, 0.0]]);
+    }
+
+    #[test]
+    fn draw_with_color_by_gradient_works() {
+        let x = vec![vec![0.0, 1.0], vec![0.0, 1.0]];
+        let y = vec![vec![0.0, 0.0], vec![1.0, 1.0]];
+        let z = vec![vec![0.0, 2.0], vec![0.0, 2.0]];
+        let mut surface = Surface::new();
+        surface.set_color_by_gradient(true).set_with_colorbar(true);
+        surface.draw(&x, &y, &z);
+        let b: &str = "x=np.array([[0,1,],[0,1,],],dtype=float)\n\
+                       y=np.array([[0,0,],[1,1,],],dtype=float)\n\
+                       z=np.array([[0,2,],[0,2,],],dtype=float)\n\
+                       gm=np.array([[2,2,],[2,2,],],dtype=float)\n\
+                       gnm=plt.Normalize(np.nanmin(gm),np.nanmax(gm))\n\
+                       fc=plt.get_cmap('bwr')(gnm(gm))\n\
+                       sf=ax3d().plot_surface(x,y,z,facecolors=fc)\n\
+                       cb=plt.colorbar(plt.cm.ScalarMappable(norm=gnm,cmap=plt.get_cmap('bwr')),ax=plt.gca())\n";
+        assert_eq!(surface.buffer, b);
+        surface.clear_buffer();
+        surface.draw_grid(&[0.0, 1.0], &[0.0, 1.0], &z).unwrap();
+        assert!(surface.buffer.contains("gm=np.array([[2,2,],[2,2,],],dtype=float)\n"));
+    }
+
+    #[test]
+    fn draw_with_colormap_colors_works() {
+        let x = vec![vec![0.0, 1.0], vec![0.0, 1.0]];
+        let y = vec![vec![0.0, 0.0], vec![1.0, 1.0]];
+        let z = vec![vec![0.0, 2.0], vec![0.0, 2.0]];
+        let mut surface = Surface::new();
+        surface
+            .set_colormap_colors(&["navy", "white", "#ff0000"])
+            .set_with_points(true);
+        surface.draw(&x, &y, &z);
+        let b: &str = "x=np.array([[0,1,],[0,1,],],dtype=float)\n\
+                       y=np.array([[0,0,],[1,1,],],dtype=float)\n\
+                       z=np.array([[0,2,],[0,2,],],dtype=float)\n\
+                       import matplotlib.colors as mcl\n\
+                       cmap_colors=['navy','white','#ff0000',]\n\
+                       cmap=mcl.LinearSegmentedColormap.from_list('custom',cmap_colors)\n\
+                       sf=ax3d().plot_surface(x,y,z,cmap=cmap)\n\
+                       ax3d().scatter(x,y,z,c=z,cmap=cmap)\n";
+        assert_eq!(surface.buffer, b);
+        surface.set_colormap_colors(&[]);
+        assert_eq!(surface.options_surface(), ",cmap=plt.get_cmap('bwr')");
+    }
+
+    #[test]
+    fn draw_with_contour_projection_works() {
+        let x = vec![vec![0.0, 1.0], vec![0.0, 1.0]];
+        let y = vec![vec![0.0, 0.0], vec![1.0, 1.0]];
+        let z = vec![vec![0.0, 2.0], vec![0.0, 2.0]];
+        let mut surface = Surface::new();
+        surface
+            .set_with_surface(false)
+            .set_with_contour_projection(true)
+            .set_contour_projection("z", -1.5);
+        surface.draw(&x, &y, &z);
+        let b: &str = "x=np.array([[0,1,],[0,1,],],dtype=float)\n\
+                       y=np.array([[0,0,],[1,1,],],dtype=float)\n\
+                       z=np.array([[0,2,],[0,2,],],dtype=float)\n\
+                       ax3d().contour(x,y,z,zdir='z',offset=-1.5,cmap=plt.get_cmap('bwr'))\n";
+        assert_eq!(surface.buffer, b);
+        surface.set_colormap_name("").set_contour_projection("x", 2.0);
+        assert_eq!(surface.options_contour_projection(), ",zdir='x',offset=2");
+    }
+
+    #[test]
+    fn draw_with_colors_works() {
+        let x = vec![vec![0.0, 1.0], vec![0.0, 1.0]];
+        let y = vec![vec![0.0, 0.0], vec![1.0, 1.0]];
+        let z = vec![vec![0.0, 2.0], vec![0.0, 2.0]];
+        let c = vec![vec![5.0, 6.0], vec![7.0, 8.0]];
+        let mut surface = Surface::new();
+        assert_eq!(
+            surface.draw_with_colors(&x, &y, &z, &vec![vec![1.0]], 0.0, 1.0).err(),
+            Some("x, y, z, and c must have the same dimensions")
+        );
+        assert_eq!(
+            surface.draw_with_colors(&x, &y, &z, &c, 1.0, 1.0).err(),
+            Some("vmin must be smaller than vmax")
+        );
+        surface.set_color_by_gradient(true).set_with_colorbar(true);
+        surface.draw_with_colors(&x, &y, &z, &c, 0.0, 10.0).unwrap();
+        let b: &str = "c=np.array([[5,6,],[7,8,],],dtype=float)\n\
+                       x=np.array([[0,1,],[0,1,],],dtype=float)\n\
+                       y=np.array([[0,0,],[1,1,],],dtype=float)\n\
+                       z=np.array([[0,2,],[0,2,],],dtype=float)\n\
+                       cnm=plt.Normalize(0,10)\n\
+                       fc=plt.get_cmap('bwr')(cnm(c))\n\
+                       sf=ax3d().plot_surface(x,y,z,facecolors=fc)\n\
+                       cb=plt.colorbar(plt.cm.ScalarMappable(norm=cnm,cmap=plt.get_cmap('bwr')),ax=plt.gca())\n";
+        assert_eq!(surface.buffer, b);
+        assert_eq!(surface.face_color_range, None);
+    }
+
+    #[test]
+    fn draw_annotation_works() {
+        let mut surface = Surface::new();
+        assert_eq!(
+            surface.draw_annotation(&[0.0, 0.0], &[1.0, 1.0, 1.0], "peak").err(),
+            Some("p.len() and offset.len() must be equal to 3")
+        );
+        surface.set_annotation_color("red");
+        surface
+            .draw_annotation(&[1.0, 2.0, 3.0], &[0.5, 0.0, 1.0], "peak")
+            .unwrap();
+        let b: &str = "ax3d().plot([1,1.5],[2,2],[3,4],color='red',linewidth=0.8)\n\
+                       ax3d().text(1.5,2,4,r'peak',color='red')\n";
+        assert_eq!(surface.buffer, b);
+        assert_eq!(surface.data_bounds(), Some((1.0, 1.5, 2.0, 2.0, 3.0, 4.0)));
+        surface.clear_buffer();
+        surface.set_annotation_color(Color::Rgb(0.0, 0.0, 1.0));
+        surface
+            .draw_annotation(&[0.0, 0.0, 0.0], &[1.0, 0.0, 0.0], "x")
+            .unwrap();
+        assert!(surface.buffer.ends_with("ax3d().text(1,0,0,r'x',color=(0,0,1))\n"));
+    }
+
+    #[test]
+    fn clear_buffer_allows_reuse() {
+        let x = vec![vec![0.0, 1.0], vec![0.0, 1.0]];
+        let y = vec![vec![0.0, 0.0], vec![1.0, 1.0]];
+        let z = vec![vec![0.0, 2.0], vec![0.0, 2.0]];
+        let mut fresh = Surface::new();
+        fresh.draw(&x, &y, &z);
+        let mut surface = Surface::new();
+        surface.set_keep_mesh(true).draw(&z, &y, &x);
+        assert_eq!(surface.mesh_points.len(), 4);
+        surface.clear_buffer();
+        assert_eq!(surface.buffer, "");
+        assert_eq!(surface.data_bounds(), None);
+        assert_eq!(surface.mesh_points.len(), 0);
+        surface.draw(&x, &y, &z);
+        assert_eq!(surface.buffer, fresh.buffer);
+        assert!(surface.buffer.starts_with("x=np.array("));
+        assert_eq!(surface.data_bounds(), fresh.data_bounds());
+    }
+
+    #[test]
+    fn draw_handles_different_sizes() {
+        let x = vec![vec![0.0, 1.0], vec![0.0, 1.0]];
+        let y = vec![vec![0.0, 0.0]];
+        let z = vec![vec![0.0, 2.0]];
+        let mut surface = Surface::new();
+        surface.set_keep_mesh(true).set_color_by_gradient(true).draw(&x, &y, &z);
+        assert!(surface.buffer.contains("y=np.array([[0,0,],],dtype=float)\n"));
+        assert!(!surface.buffer.contains("gm=np.array("));
+        assert_eq!(surface.data_bounds(), None);
+        assert_eq!(
+            surface
+                .write_obj("/tmp/plotpy/unit_tests/draw_handles_different_sizes.obj")
+                .err(),
+            Some("there is no meshgrid to be written")
+        );
+    }
+
+    #[test]
+    fn draw_grid_works() {
+        let x = &[0.0, 1.0, 2.0];
+        let y = &[0.0, 1.0];
+        let mut surface = Surface::new();
+        let res = surface.draw_grid(x, y, &vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
+        assert_eq!(res.err(), Some("z must be a (y.len(),x.len()) matrix"));
+        let res = surface.draw_grid(x, y, &vec![vec![1.0, 2.0, 3.0]]);
+        assert_eq!(res.err(), Some("z must be a (y.len(),x.len()) matrix"));
+        let z = vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]];
+        surface.draw_grid(x, y, &z).unwrap();
+        let b: &str = "x=np.array([0,1,2,],dtype=float)\n\
+                       y=np.array([0,1,],dtype=float)\n\
+                       x,y=np.meshgrid(x,y)\n\
+                       z=np.array([[1,2,3,],[4,5,6,],],dtype=float)\n\
+                       sf=ax3d().plot_surface(x,y,z,cmap=plt.get_cmap('bwr'))\n";
+        assert_eq!(surface.buffer, b);
+        assert_eq!(surface.data_bounds(), Some((0.0, 2.0, 0.0, 1.0, 1.0, 6.0)));
+    }
+
     #[test]
     fn aligned_system_fails_on_wrong_input() {
         let res = Surface::aligned_system(&[0.0, 0.0, 0.0], &[0.0, 0.0, 0.0]);
@@ -49,34 +49,167 @@ impl Surface {
         ndiv_axis: usize,
         ndiv_perimeter: usize,
     ) -> Result<(), StrError> {
-        if a.len() != 3 {
-            return Err("a.len() must equal to 3");
+        self.cylinder(a, b, radius, radius, ndiv_axis, ndiv_perimeter)
+    }
+
+    /// Draws a cylinder with an elliptical cross-section
+    ///
+    /// # Input
+    ///
+    /// * `a` -- first point on the cylinder (centered) axis
+    /// * `b` -- second point on the cylinder (centered) axis
+    /// * `r1` -- the first semi-axis of the cross-section (> 0)
+    /// * `r2` -- the second semi-axis of the cross-section (> 0)
+    /// * `ndiv_axis` -- number of divisions along the axis (≥ 1)
+    /// * `ndiv_perimeter` -- number of divisions along the cross-sectional ellipse perimeter (≥ 3)
+    ///
+    /// # Notes
+    ///
+    /// * The semi-axes are aligned with the local system computed from the a-to-b direction.
+    ///   If `r1 == r2`, the result is the same as [Surface::draw_cylinder()]
+    pub fn draw_elliptic_cylinder(
+        &mut self,
+        a: &[f64],
+        b: &[f64],
+        r1: f64,
+        r2: f64,
+        ndiv_axis: usize,
+        ndiv_perimeter: usize,
+    ) -> Result<(), StrError> {
+        if r1 <= 0.0 || r2 <= 0.0 {
+            return Err("r1 and r2 must be positive");
         }
-        if b.len() != 3 {
-            return Err("b.len() must equal to 3");
+        self.cylinder(a, b, r1, r2, ndiv_axis, ndiv_perimeter)
+    }
+
+    /// Draws a cylinder closed by two end disks
+    ///
+    /// # Input
+    ///
+    /// * `a` -- first point on the cylinder (centered) axis
+    /// * `b` -- second point on the cylinder (centered) axis
+    /// * `radius` -- the cylinder's radius
+    /// * `ndiv_axis` -- number of divisions along the axis (≥ 1)
+    /// * `ndiv_perimeter` -- number of divisions along the cross-sectional circle perimeter (≥ 3)
+    ///
+    /// # Notes
+    ///
+    /// * The lateral surface is the same as the one drawn by [Surface::draw_cylinder()];
+    ///   the disks at `a` and `b` are drawn as two additional surfaces.
+    pub fn draw_capped_cylinder(
+        &mut self,
+        a: &[f64],
+        b: &[f64],
+        radius: f64,
+        ndiv_axis: usize,
+        ndiv_perimeter: usize,
+    ) -> Result<(), StrError> {
+        self.cylinder(a, b, radius, radius, ndiv_axis, ndiv_perimeter)?;
+        let (_, e1, e2) = Surface::aligned_system(a, b)?;
+        self.disk(a, &e1, &e2, radius, radius, ndiv_perimeter);
+        self.disk(b, &e1, &e2, radius, radius, ndiv_perimeter);
+        Ok(())
+    }
+
+    /// Draws a cone or a truncated cone (frustum)
+    ///
+    /// # Input
+    ///
+    /// * `c` -- (len=3) center of the bottom cross-section
+    /// * `axis` -- (len=3) vector from the bottom center to the top center
+    /// * `r_bottom` -- radius at the bottom (> 0)
+    /// * `r_top` -- radius at the top; zero yields a sharp cone (≥ 0)
+    /// * `n_axis` -- number of divisions along the axis (≥ 1)
+    /// * `n_alpha` -- number of divisions along the cross-sectional circle perimeter (≥ 1)
+    ///
+    /// # Output
+    ///
+    /// * `x`, `y`, `z` -- the coordinates of all points as in a meshgrid
+    pub fn draw_cone(
+        &mut self,
+        c: &[f64],
+        axis: &[f64],
+        r_bottom: f64,
+        r_top: f64,
+        n_axis: usize,
+        n_alpha: usize,
+    ) -> Result<(Vec<Vec<f64>>, Vec<Vec<f64>>, Vec<Vec<f64>>), StrError> {
+        if c.len() != 3 || axis.len() != 3 {
+            return Err("c.len() and axis.len() must be equal to 3");
         }
-        if ndiv_axis < 1 {
-            return Err("ndiv_axis must be ≥ 1");
+        if r_bottom <= 0.0 || r_top < 0.0 {
+            return Err("r_bottom must be positive and r_top must be non-negative");
         }
-        if ndiv_perimeter < 3 {
-            return Err("ndiv_perimeter must be ≥ 3");
+        if n_axis < 1 || n_alpha < 1 {
+            return Err("n_axis and n_alpha must be ≥ 1");
         }
-        let (e0, e1, e2) = Surface::aligned_system(a, b)?;
-        let cylinder_height =
-            f64::sqrt((b[0] - a[0]) * (b[0] - a[0]) + (b[1] - a[1]) * (b[1] - a[1]) + (b[2] - a[2]) * (b[2] - a[2]));
-        let (n_height, n_alpha) = (ndiv_axis + 1, ndiv_perimeter + 1);
-        let mut x = vec![vec![0.0; n_height]; n_alpha];
-        let mut y = vec![vec![0.0; n_height]; n_alpha];
-        let mut z = vec![vec![0.0; n_height]; n_alpha];
-        let delta_height = cylinder_height / ((n_height - 1) as f64);
-        let delta_alpha = 2.0 * std::f64::consts::PI / ((n_alpha - 1) as f64);
+        let radii: Vec<f64> = (0..(n_axis + 1))
+            .map(|j| r_bottom + ((j as f64) / (n_axis as f64)) * (r_top - r_bottom))
+            .collect();
+        self.tube(c, axis, &radii, n_alpha)
+    }
+
+    /// Draws a tube with variable radius along its axis (e.g., a barrel or a taper)
+    ///
+    /// # Input
+    ///
+    /// * `base` -- (len=3) center of the first cross-section
+    /// * `axis` -- (len=3) vector from the first to the last cross-section center
+    /// * `radii` -- (len ≥ 2) radii of the cross-sections, evenly spaced along the axis (≥ 0)
+    /// * `n_theta` -- number of divisions along the cross-sectional circle perimeter (≥ 3)
+    ///
+    /// # Output
+    ///
+    /// * `x`, `y`, `z` -- the coordinates of all points as in a meshgrid
+    ///
+    /// # Notes
+    ///
+    /// * With two radii, the result is the same as [Surface::draw_cone()] with one axis division.
+    pub fn draw_tube(
+        &mut self,
+        base: &[f64],
+        axis: &[f64],
+        radii: &[f64],
+        n_theta: usize,
+    ) -> Result<(Vec<Vec<f64>>, Vec<Vec<f64>>, Vec<Vec<f64>>), StrError> {
+        if base.len() != 3 || axis.len() != 3 {
+            return Err("base.len() and axis.len() must be equal to 3");
+        }
+        if radii.len() < 2 {
+            return Err("radii.len() must be ≥ 2");
+        }
+        if radii.iter().any(|r| r.is_nan() || *r < 0.0) {
+            return Err("radii must be non-negative");
+        }
+        if n_theta < 3 {
+            return Err("n_theta must be ≥ 3");
+        }
+        self.tube(base, axis, radii, n_theta)
+    }
+
+    /// Draws a surface of revolution given the radii of evenly spaced cross-sections along the axis
+    fn tube(
+        &mut self,
+        c: &[f64],
+        axis: &[f64],
+        radii: &[f64],
+        n_alpha: usize,
+    ) -> Result<(Vec<Vec<f64>>, Vec<Vec<f64>>, Vec<Vec<f64>>), StrError> {
+        let b = vec![c[0] + axis[0], c[1] + axis[1], c[2] + axis[2]];
+        let (e0, e1, e2) = Surface::aligned_system(c, &b)?;
+        let height = f64::sqrt(axis[0] * axis[0] + axis[1] * axis[1] + axis[2] * axis[2]);
+        let n_axis = radii.len() - 1;
+        let mut x = vec![vec![0.0; n_axis + 1]; n_alpha + 1];
+        let mut y = vec![vec![0.0; n_axis + 1]; n_alpha + 1];
+        let mut z = vec![vec![0.0; n_axis + 1]; n_alpha + 1];
+        let delta_alpha = 2.0 * PI / (n_alpha as f64);
         let mut p = vec![0.0; 3];
-        for i in 0..n_alpha {
+        for i in 0..(n_alpha + 1) {
             let v = (i as f64) * delta_alpha;
-            for j in 0..n_height {
-                let u = (j as f64) * delta_height;
+            for (j, r) in radii.iter().enumerate() {
+                let t = (j as f64) / (n_axis as f64);
                 for k in 0..3 {
-                    p[k] = a[k] + u * e0[k] + radius * f64::sin(v) * e1[k] + radius * f64::cos(v) * e2[k];
+                    p[k] = c[k] + t * height * e0[k] + r * f64::sin(v) * e1[k] + r * f64::cos(v) * e2[k];
                 }
                 x[i][j] = p[0];
                 y[i][j] = p[1];
@@ -84,7 +217,7 @@ impl Surface {
             }
         }
         self.draw(&x, &y, &z);
-        Ok(())
+        Ok((x, y, z))
     }
 
     /// Draws a plane that has a normal vector with a non-zero z (nzz) component
@@ -162,6 +295,138 @@ impl Surface {
         Ok((x, y, z))
     }
 
+    /// Draws a plane that has a normal vector with a non-zero x (nxx) component
+    ///
+    /// The plane may be perpendicular to x if n = (1,0,0)
+    ///
+    /// # Input
+    ///
+    /// * `p` -- (len=3) point on plane
+    /// * `n` -- (len=3) normal vector
+    /// * `ymin` and `ymax` -- limits along y
+    /// * `zmin` and `zmax` -- limits along z
+    /// * `ny` -- number of divisions along y (must be ≥ 2)
+    /// * `nz` -- number of divisions along z (must be ≥ 2)
+    ///
+    /// # Output
+    ///
+    /// * `x`, `y`, `z` -- the coordinates of all points as in a meshgrid
+    pub fn draw_plane_nxx(
+        &mut self,
+        p: &[f64],
+        n: &[f64],
+        ymin: f64,
+        ymax: f64,
+        zmin: f64,
+        zmax: f64,
+        ny: usize,
+        nz: usize,
+    ) -> Result<(Vec<Vec<f64>>, Vec<Vec<f64>>, Vec<Vec<f64>>), StrError> {
+        if p.len() != 3 || n.len() != 3 {
+            return Err("p.len() and n.len() must be equal to 3");
+        }
+        if f64::abs(n[0]) < 1e-10 {
+            return Err("the x-component of the normal vector cannot be zero");
+        }
+        if ny < 2 || nz < 2 {
+            return Err("ny and nz must be ≥ 2");
+        }
+        let d = -n[0] * p[0] - n[1] * p[1] - n[2] * p[2];
+        let (y, z, x) = generate3d(ymin, ymax, zmin, zmax, ny + 1, nz + 1, |y, z| {
+            (-d - n[1] * y - n[2] * z) / n[0]
+        });
+        self.draw(&x, &y, &z);
+        Ok((x, y, z))
+    }
+
+    /// Draws a plane that has a normal vector with a non-zero y (nyy) component
+    ///
+    /// The plane may be perpendicular to y if n = (0,1,0)
+    ///
+    /// # Input
+    ///
+    /// * `p` -- (len=3) point on plane
+    /// * `n` -- (len=3) normal vector
+    /// * `xmin` and `xmax` -- limits along x
+    /// * `zmin` and `zmax` -- limits along z
+    /// * `nx` -- number of divisions along x (must be ≥ 2)
+    /// * `nz` -- number of divisions along z (must be ≥ 2)
+    ///
+    /// # Output
+    ///
+    /// * `x`, `y`, `z` -- the coordinates of all points as in a meshgrid
+    pub fn draw_plane_nyy(
+        &mut self,
+        p: &[f64],
+        n: &[f64],
+        xmin: f64,
+        xmax: f64,
+        zmin: f64,
+        zmax: f64,
+        nx: usize,
+        nz: usize,
+    ) -> Result<(Vec<Vec<f64>>, Vec<Vec<f64>>, Vec<Vec<f64>>), StrError> {
+        if p.len() != 3 || n.len() != 3 {
+            return Err("p.len() and n.len() must be equal to 3");
+        }
+        if f64::abs(n[1]) < 1e-10 {
+            return Err("the y-component of the normal vector cannot be zero");
+        }
+        if nx < 2 || nz < 2 {
+            return Err("nx and nz must be ≥ 2");
+        }
+        let d = -n[0] * p[0] - n[1] * p[1] - n[2] * p[2];
+        let (x, z, y) = generate3d(xmin, xmax, zmin, zmax, nx + 1, nz + 1, |x, z| {
+            (-d - n[0] * x - n[2] * z) / n[1]
+        });
+        self.draw(&x, &y, &z);
+        Ok((x, y, z))
+    }
+
+    /// Draws a plane with arbitrary normal vector
+    ///
+    /// The plane is drawn by [Surface::draw_plane_nxx()], [Surface::draw_plane_nyy()], or
+    /// [Surface::draw_plane_nzz()], according to the largest component of the normal vector.
+    ///
+    /// # Input
+    ///
+    /// * `p` -- (len=3) point on plane
+    /// * `n` -- (len=3) normal vector
+    /// * `amin` and `amax` -- limits along the first free coordinate (y if nxx; x otherwise)
+    /// * `bmin` and `bmax` -- limits along the second free coordinate (y if nzz; z otherwise)
+    /// * `na` -- number of divisions along the first free coordinate (must be ≥ 2)
+    /// * `nb` -- number of divisions along the second free coordinate (must be ≥ 2)
+    ///
+    /// # Output
+    ///
+    /// * `x`, `y`, `z` -- the coordinates of all points as in a meshgrid
+    pub fn draw_plane(
+        &mut self,
+        p: &[f64],
+        n: &[f64],
+        amin: f64,
+        amax: f64,
+        bmin: f64,
+        bmax: f64,
+        na: usize,
+        nb: usize,
+    ) -> Result<(Vec<Vec<f64>>, Vec<Vec<f64>>, Vec<Vec<f64>>), StrError> {
+        if p.len() != 3 || n.len() != 3 {
+            return Err("p.len() and n.len() must be equal to 3");
+        }
+        let (ax, ay, az) = (f64::abs(n[0]), f64::abs(n[1]), f64::abs(n[2]));
+        if ax < 1e-10 && ay < 1e-10 && az < 1e-10 {
+            return Err("the normal vector cannot be zero");
+        }
+        if az >= ax && az >= ay {
+            self.draw_plane_nzz(p, n, amin, amax, bmin, bmax, na, nb)
+        } else if ax >= ay {
+            self.draw_plane_nxx(p, n, amin, amax, bmin, bmax, na, nb)
+        } else {
+            self.draw_plane_nyy(p, n, amin, amax, bmin, bmax, na, nb)
+        }
+    }
+
     /// Draws a hemisphere
     ///
     /// # Input
@@ -174,6 +439,8 @@ impl Surface {
     /// * `n_theta` -- number of divisions along θ (must be ≥ 2)
     /// * `cup` -- upside-down; like a cup
     ///
+    /// See [Surface::set_with_cap] to close the rim with a flat disk.
+    ///
     /// # Output
     ///
     /// * `x`, `y`, `z` -- the coordinates of all points as in a meshgrid
@@ -229,6 +496,9 @@ impl Surface {
         if n_alpha < 2 || n_theta < 2 {
             return Err("n_alpha and n_theta must be ≥ 2");
         }
+        if self.close_alpha() && f64::abs(alpha_max - alpha_min - 360.0) > 1e-10 {
+            return Err("close_alpha requires alpha_max - alpha_min = 360");
+        }
         let a_min = alpha_min * PI / 180.0;
         let a_max = alpha_max * PI / 180.0;
         let d_alpha = (a_max - a_min) / (n_alpha as f64);
@@ -251,7 +521,15 @@ impl Surface {
                 }
             }
         }
+        if self.close_alpha() {
+            x[n_alpha] = x[0].clone();
+            y[n_alpha] = y[0].clone();
+            z[n_alpha] = z[0].clone();
+        }
         self.draw(&x, &y, &z);
+        if self.with_cap() {
+            self.cap(c, &x, &y, &z, n_theta);
+        }
         Ok((x, y, z))
     }
 
@@ -269,6 +547,9 @@ impl Surface {
     /// * `n_alpha` -- number of divisions along α (must be ≥ 2)
     /// * `n_theta` -- number of divisions along θ (must be ≥ 2)
     ///
+    /// An error is returned if the angles are out of range or if `min ≥ max`.
+    /// See [Surface::set_with_cap] to close the cuts along θ with flat caps.
+    ///
     /// # Output
     ///
     /// * `x`, `y`, `z` -- the coordinates of all points as in a meshgrid
@@ -323,9 +604,26 @@ impl Surface {
         if n_alpha < 2 || n_theta < 2 {
             return Err("n_alpha and n_theta must be ≥ 2");
         }
+        if self.close_alpha() && f64::abs(alpha_max - alpha_min - 360.0) > 1e-10 {
+            return Err("close_alpha requires alpha_max - alpha_min = 360");
+        }
         if k[0] < 0.0 || k[1] < 0.0 || k[2] < 0.0 {
             return Err("exponents k must be greater than zero");
         }
+        let alpha_range = -180.0..=180.0;
+        if !alpha_range.contains(&alpha_min) || !alpha_range.contains(&alpha_max) {
+            return Err("alpha_min and alpha_max must be in [-180, 180]");
+        }
+        if alpha_min >= alpha_max {
+            return Err("alpha_min must be smaller than alpha_max");
+        }
+        let theta_range = -90.0..=90.0;
+        if !theta_range.contains(&theta_min) || !theta_range.contains(&theta_max) {
+            return Err("theta_min and theta_max must be in [-90, 90]");
+        }
+        if theta_min >= theta_max {
+            return Err("theta_min must be smaller than theta_max");
+        }
         let (aa, bb, cc) = (2.0 / k[0], 2.0 / k[1], 2.0 / k[2]);
         let a_min = alpha_min * PI / 180.0;
         let a_max = alpha_max * PI / 180.0;
@@ -345,7 +643,20 @@ impl Surface {
                 z[i][j] = c[2] + r[2] * suq_sin(theta, cc);
             }
         }
+        if self.close_alpha() {
+            x[n_alpha] = x[0].clone();
+            y[n_alpha] = y[0].clone();
+            z[n_alpha] = z[0].clone();
+        }
         self.draw(&x, &y, &z);
+        if self.with_cap() {
+            if theta_min > -90.0 {
+                self.cap(&[c[0], c[1], z[0][0]], &x, &y, &z, 0);
+            }
+            if theta_max < 90.0 {
+                self.cap(&[c[0], c[1], z[0][n_theta]], &x, &y, &z, n_theta);
+            }
+        }
         Ok((x, y, z))
     }
 
@@ -417,6 +728,234 @@ impl Surface {
             n_theta,
         )
     }
+
+    /// Draws a superquadric and returns the outward unit normals at the grid vertices
+    ///
+    /// The input is the same as in [Surface::draw_superquadric].
+    ///
+    /// # Output
+    ///
+    /// * `x`, `y`, `z` -- the coordinates of all points as in a meshgrid
+    /// * `nx`, `ny`, `nz` -- the components of the unit normal vectors at all points
+    ///
+    /// # Notes
+    ///
+    /// * The normals are computed from the gradient of the implicit function
+    ///   `|x/r₀|^k₀ + |y/r₁|^k₁ + |z/r₂|^k₂ = 1`.
+    /// * The normal is zero where the gradient is undefined or null (e.g., at the cusps if `k < 1`).
+    pub fn draw_superquadric_with_normals(
+        &mut self,
+        c: &[f64],
+        r: &[f64],
+        k: &[f64],
+        alpha_min: f64,
+        alpha_max: f64,
+        theta_min: f64,
+        theta_max: f64,
+        n_alpha: usize,
+        n_theta: usize,
+    ) -> Result<
+        (
+            Vec<Vec<f64>>,
+            Vec<Vec<f64>>,
+            Vec<Vec<f64>>,
+            Vec<Vec<f64>>,
+            Vec<Vec<f64>>,
+            Vec<Vec<f64>>,
+        ),
+        StrError,
+    > {
+        let (x, y, z) =
+            self.draw_superquadric(c, r, k, alpha_min, alpha_max, theta_min, theta_max, n_alpha, n_theta)?;
+        let mut nx = vec![vec![0.0; n_theta + 1]; n_alpha + 1];
+        let mut ny = vec![vec![0.0; n_theta + 1]; n_alpha + 1];
+        let mut nz = vec![vec![0.0; n_theta + 1]; n_alpha + 1];
+        let grad = |u: f64, r: f64, k: f64| {
+            let v = u / r;
+            if v == 0.0 {
+                0.0
+            } else {
+                k * f64::powf(f64::abs(v), k - 1.0) * f64::signum(v) / r
+            }
+        };
+        for i in 0..n_alpha + 1 {
+            for j in 0..n_theta + 1 {
+                let gx = grad(x[i][j] - c[0], r[0], k[0]);
+                let gy = grad(y[i][j] - c[1], r[1], k[1]);
+                let gz = grad(z[i][j] - c[2], r[2], k[2]);
+                let norm = f64::sqrt(gx * gx + gy * gy + gz * gz);
+                if norm > 0.0 && norm.is_finite() {
+                    nx[i][j] = gx / norm;
+                    ny[i][j] = gy / norm;
+                    nz[i][j] = gz / norm;
+                }
+            }
+        }
+        Ok((x, y, z, nx, ny, nz))
+    }
+
+    /// Draws a sphere and returns the outward unit normals at the grid vertices
+    ///
+    /// The input is the same as in [Surface::draw_sphere].
+    ///
+    /// # Output
+    ///
+    /// * `x`, `y`, `z` -- the coordinates of all points as in a meshgrid
+    /// * `nx`, `ny`, `nz` -- the components of the unit normal vectors, i.e., `(p - c) / r`
+    pub fn draw_sphere_with_normals(
+        &mut self,
+        c: &[f64],
+        r: f64,
+        n_alpha: usize,
+        n_theta: usize,
+    ) -> Result<
+        (
+            Vec<Vec<f64>>,
+            Vec<Vec<f64>>,
+            Vec<Vec<f64>>,
+            Vec<Vec<f64>>,
+            Vec<Vec<f64>>,
+            Vec<Vec<f64>>,
+        ),
+        StrError,
+    > {
+        let (x, y, z) = self.draw_sphere(c, r, n_alpha, n_theta)?;
+        let unit = |m: &Vec<Vec<f64>>, c: f64| -> Vec<Vec<f64>> {
+            m.iter().map(|row| row.iter().map(|v| (v - c) / r).collect()).collect()
+        };
+        let (nx, ny, nz) = (unit(&x, c[0]), unit(&y, c[1]), unit(&z, c[2]));
+        Ok((x, y, z, nx, ny, nz))
+    }
+
+    /// Draws a torus with its axis parallel to z
+    ///
+    /// # Input
+    ///
+    /// * `c` -- (len=3) center of the torus
+    /// * `r_major` -- distance from the center to the center of the tube (> 0)
+    /// * `r_minor` -- radius of the tube (> 0); usually smaller than `r_major`
+    /// * `n_u` -- number of divisions around the axis (must be ≥ 1)
+    /// * `n_v` -- number of divisions around the tube (must be ≥ 1)
+    ///
+    /// # Output
+    ///
+    /// * `x`, `y`, `z` -- the coordinates of all points as in a meshgrid
+    ///
+    /// # Notes
+    ///
+    /// * The surface self-intersects if `r_minor ≥ r_major`.
+    pub fn draw_torus(
+        &mut self,
+        c: &[f64],
+        r_major: f64,
+        r_minor: f64,
+        n_u: usize,
+        n_v: usize,
+    ) -> Result<(Vec<Vec<f64>>, Vec<Vec<f64>>, Vec<Vec<f64>>), StrError> {
+        if c.len() != 3 {
+            return Err("c.len() must be equal to 3");
+        }
+        if r_major <= 0.0 || r_minor <= 0.0 {
+            return Err("r_major and r_minor must be positive");
+        }
+        if n_u < 1 || n_v < 1 {
+            return Err("n_u and n_v must be ≥ 1");
+        }
+        let d_u = 2.0 * PI / (n_u as f64);
+        let d_v = 2.0 * PI / (n_v as f64);
+        let mut x = vec![vec![0.0; n_v + 1]; n_u + 1];
+        let mut y = vec![vec![0.0; n_v + 1]; n_u + 1];
+        let mut z = vec![vec![0.0; n_v + 1]; n_u + 1];
+        for i in 0..n_u + 1 {
+            let u = (i as f64) * d_u;
+            for j in 0..n_v + 1 {
+                let v = (j as f64) * d_v;
+                x[i][j] = c[0] + (r_major + r_minor * f64::cos(v)) * f64::cos(u);
+                y[i][j] = c[1] + (r_major + r_minor * f64::cos(v)) * f64::sin(u);
+                z[i][j] = c[2] + r_minor * f64::sin(v);
+            }
+        }
+        self.draw(&x, &y, &z);
+        Ok((x, y, z))
+    }
+
+    /// Draws a cylinder with an elliptical (or circular) cross-section
+    fn cylinder(
+        &mut self,
+        a: &[f64],
+        b: &[f64],
+        r1: f64,
+        r2: f64,
+        ndiv_axis: usize,
+        ndiv_perimeter: usize,
+    ) -> Result<(), StrError> {
+        if a.len() != 3 {
+            return Err("a.len() must equal to 3");
+        }
+        if b.len() != 3 {
+            return Err("b.len() must equal to 3");
+        }
+        if ndiv_axis < 1 {
+            return Err("ndiv_axis must be ≥ 1");
+        }
+        if ndiv_perimeter < 3 {
+            return Err("ndiv_perimeter must be ≥ 3");
+        }
+        let (e0, e1, e2) = Surface::aligned_system(a, b)?;
+        let cylinder_height =
+            f64::sqrt((b[0] - a[0]) * (b[0] - a[0]) + (b[1] - a[1]) * (b[1] - a[1]) + (b[2] - a[2]) * (b[2] - a[2]));
+        let (n_height, n_alpha) = (ndiv_axis + 1, ndiv_perimeter + 1);
+        let mut x = vec![vec![0.0; n_height]; n_alpha];
+        let mut y = vec![vec![0.0; n_height]; n_alpha];
+        let mut z = vec![vec![0.0; n_height]; n_alpha];
+        let delta_height = cylinder_height / ((n_height - 1) as f64);
+        let delta_alpha = 2.0 * std::f64::consts::PI / ((n_alpha - 1) as f64);
+        let mut p = vec![0.0; 3];
+        for i in 0..n_alpha {
+            let v = (i as f64) * delta_alpha;
+            for j in 0..n_height {
+                let u = (j as f64) * delta_height;
+                for k in 0..3 {
+                    p[k] = a[k] + u * e0[k] + r1 * f64::sin(v) * e1[k] + r2 * f64::cos(v) * e2[k];
+                }
+                x[i][j] = p[0];
+                y[i][j] = p[1];
+                z[i][j] = p[2];
+            }
+        }
+        self.draw(&x, &y, &z);
+        Ok(())
+    }
+
+    /// Draws an elliptical (or circular) disk centered at c and spanned by the unit vectors e1 and e2
+    fn disk(&mut self, c: &[f64], e1: &[f64], e2: &[f64], r1: f64, r2: f64, ndiv_perimeter: usize) {
+        let n_alpha = ndiv_perimeter + 1;
+        let mut x = vec![vec![0.0; 2]; n_alpha];
+        let mut y = vec![vec![0.0; 2]; n_alpha];
+        let mut z = vec![vec![0.0; 2]; n_alpha];
+        let delta_alpha = 2.0 * std::f64::consts::PI / ((n_alpha - 1) as f64);
+        for i in 0..n_alpha {
+            let v = (i as f64) * delta_alpha;
+            x[i][1] = c[0] + r1 * f64::sin(v) * e1[0] + r2 * f64::cos(v) * e2[0];
+            y[i][1] = c[1] + r1 * f64::sin(v) * e1[1] + r2 * f64::cos(v) * e2[1];
+            z[i][1] = c[2] + r1 * f64::sin(v) * e1[2] + r2 * f64::cos(v) * e2[2];
+        }
+        self.cap(c, &x, &y, &z, 1);
+    }
+
+    /// Draws a flat cap (fan) connecting the center c to the points of the column j of a meshgrid
+    fn cap(&mut self, c: &[f64], x: &Vec<Vec<f64>>, y: &Vec<Vec<f64>>, z: &Vec<Vec<f64>>, j: usize) {
+        let n = x.len();
+        let mut xx = vec![vec![c[0]; 2]; n];
+        let mut yy = vec![vec![c[1]; 2]; n];
+        let mut zz = vec![vec![c[2]; 2]; n];
+        for i in 0..n {
+            xx[i][1] = x[i][j];
+            yy[i][1] = y[i][j];
+            zz[i][1] = z[i][j];
+        }
+        self.draw(&xx, &yy, &zz);
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -453,6 +992,52 @@ mod tests {
         assert!(surf.get_buffer().len() > 0);
     }
 
+    #[test]
+    fn draw_elliptic_cylinder_fails_on_wrong_input() {
+        let mut surf = Surface::new();
+        let res = surf.draw_elliptic_cylinder(&[0.0, 0.0, 0.0], &[1.0, 1.0, 1.0], 0.0, 1.0, 1, 3);
+        assert_eq!(res.err(), Some("r1 and r2 must be positive"));
+        let res = surf.draw_elliptic_cylinder(&[0.0, 0.0, 0.0], &[1.0, 1.0, 1.0], 1.0, -1.0, 1, 3);
+        assert_eq!(res.err(), Some("r1 and r2 must be positive"));
+        let res = surf.draw_elliptic_cylinder(&[0.0, 0.0], &[1.0, 1.0, 1.0], 1.0, 1.0, 1, 3);
+        assert_eq!(res.err(), Some("a.len() must equal to 3"));
+        let res = surf.draw_elliptic_cylinder(&[0.0, 0.0, 0.0], &[1.0, 1.0, 1.0], 1.0, 1.0, 1, 2);
+        assert_eq!(res.err(), Some("ndiv_perimeter must be ≥ 3"));
+    }
+
+    #[test]
+    fn draw_elliptic_cylinder_works() {
+        let mut surf = Surface::new();
+        surf.draw_elliptic_cylinder(&[0.0, 0.0, 0.0], &[1.0, 0.0, 0.0], 2.0, 0.5, 2, 3)
+            .unwrap();
+        assert!(surf.get_buffer().len() > 0);
+
+        // same as the circular cylinder when r1 == r2
+        let mut circular = Surface::new();
+        circular
+            .draw_cylinder(&[0.0, 0.0, 0.0], &[1.0, 0.0, 0.0], 1.0, 2, 3)
+            .unwrap();
+        let mut elliptic = Surface::new();
+        elliptic
+            .draw_elliptic_cylinder(&[0.0, 0.0, 0.0], &[1.0, 0.0, 0.0], 1.0, 1.0, 2, 3)
+            .unwrap();
+        assert_eq!(circular.get_buffer(), elliptic.get_buffer());
+    }
+
+    #[test]
+    fn draw_capped_cylinder_works() {
+        let mut surf = Surface::new();
+        let res = surf.draw_capped_cylinder(&[0.0, 0.0, 0.0], &[1.0, 1.0, 1.0], 1.0, 0, 3);
+        assert_eq!(res.err(), Some("ndiv_axis must be ≥ 1"));
+        surf.clear_buffer();
+        surf.draw_capped_cylinder(&[0.0, 0.0, 0.0], &[0.0, 0.0, 1.0], 1.0, 1, 4)
+            .unwrap();
+        assert_eq!(surf.get_buffer().matches("plot_surface").count(), 3);
+        let b = surf.get_buffer();
+        assert!(b.contains("x=np.array([[0,0,],[0,"));
+        assert!(b.contains("z=np.array([[1,1,],[1,1,],"));
+    }
+
     #[test]
     fn draw_plane_nzz_fails_on_wrong_input() {
         let mut surf = Surface::new();
@@ -478,6 +1063,66 @@ mod tests {
         assert!(surf.get_buffer().len() > 0);
     }
 
+    #[test]
+    fn draw_plane_nxx_and_nyy_fail_on_wrong_input() {
+        let mut surf = Surface::new();
+        let res = surf.draw_plane_nxx(&[0.0, 0.0], &[1.0, 0.0, 0.0], 0.0, 1.0, 0.0, 1.0, 2, 2);
+        assert_eq!(res.err(), Some("p.len() and n.len() must be equal to 3"));
+        let res = surf.draw_plane_nxx(&[0.0, 0.0, 0.0], &[0.0, 1.0, 0.0], 0.0, 1.0, 0.0, 1.0, 2, 2);
+        assert_eq!(res.err(), Some("the x-component of the normal vector cannot be zero"));
+        let res = surf.draw_plane_nxx(&[0.0, 0.0, 0.0], &[1.0, 0.0, 0.0], 0.0, 1.0, 0.0, 1.0, 1, 2);
+        assert_eq!(res.err(), Some("ny and nz must be ≥ 2"));
+        let res = surf.draw_plane_nyy(&[0.0, 0.0, 0.0], &[0.0, 1.0], 0.0, 1.0, 0.0, 1.0, 2, 2);
+        assert_eq!(res.err(), Some("p.len() and n.len() must be equal to 3"));
+        let res = surf.draw_plane_nyy(&[0.0, 0.0, 0.0], &[1.0, 0.0, 0.0], 0.0, 1.0, 0.0, 1.0, 2, 2);
+        assert_eq!(res.err(), Some("the y-component of the normal vector cannot be zero"));
+        let res = surf.draw_plane_nyy(&[0.0, 0.0, 0.0], &[0.0, 1.0, 0.0], 0.0, 1.0, 0.0, 1.0, 2, 1);
+        assert_eq!(res.err(), Some("nx and nz must be ≥ 2"));
+        let res = surf.draw_plane(&[0.0, 0.0, 0.0], &[0.0, 0.0, 0.0], 0.0, 1.0, 0.0, 1.0, 2, 2);
+        assert_eq!(res.err(), Some("the normal vector cannot be zero"));
+    }
+
+    #[test]
+    fn draw_plane_nxx_works() {
+        let mut surf = Surface::new();
+        let (x, y, z) = surf
+            .draw_plane_nxx(&[2.0, 0.0, 0.0], &[1.0, 0.0, 0.0], 0.0, 1.0, -1.0, 1.0, 2, 2)
+            .unwrap();
+        assert_eq!(x, &[[2.0, 2.0, 2.0], [2.0, 2.0, 2.0], [2.0, 2.0, 2.0]]);
+        assert_eq!(y, &[[0.0, 0.5, 1.0], [0.0, 0.5, 1.0], [0.0, 0.5, 1.0]]);
+        assert_eq!(z, &[[-1.0, -1.0, -1.0], [0.0, 0.0, 0.0], [1.0, 1.0, 1.0]]);
+        assert!(surf.get_buffer().len() > 0);
+    }
+
+    #[test]
+    fn draw_plane_nyy_works() {
+        let mut surf = Surface::new();
+        let (x, y, z) = surf
+            .draw_plane_nyy(&[0.0, -3.0, 0.0], &[0.0, 1.0, 0.0], 0.0, 1.0, -1.0, 1.0, 2, 2)
+            .unwrap();
+        assert_eq!(x, &[[0.0, 0.5, 1.0], [0.0, 0.5, 1.0], [0.0, 0.5, 1.0]]);
+        assert_eq!(y, &[[-3.0, -3.0, -3.0], [-3.0, -3.0, -3.0], [-3.0, -3.0, -3.0]]);
+        assert_eq!(z, &[[-1.0, -1.0, -1.0], [0.0, 0.0, 0.0], [1.0, 1.0, 1.0]]);
+        assert!(surf.get_buffer().len() > 0);
+    }
+
+    #[test]
+    fn draw_plane_selects_dominant_component() {
+        let mut surf = Surface::new();
+        let (x, _, _) = surf
+            .draw_plane(&[2.0, 0.0, 0.0], &[1.0, 0.0, 0.0], 0.0, 1.0, 0.0, 1.0, 2, 2)
+            .unwrap();
+        assert_eq!(x[0], &[2.0, 2.0, 2.0]);
+        let (_, y, _) = surf
+            .draw_plane(&[0.0, 1.0, 0.0], &[0.1, -1.0, 0.0], 0.0, 0.0, 0.0, 1.0, 2, 2)
+            .unwrap();
+        assert_eq!(y[0], &[1.0, 1.0, 1.0]);
+        let (_, _, z) = surf
+            .draw_plane(&[0.0, 0.0, 5.0], &[0.0, 0.0, 1.0], 0.0, 1.0, 0.0, 1.0, 2, 2)
+            .unwrap();
+        assert_eq!(z[0], &[5.0, 5.0, 5.0]);
+    }
+
     #[test]
     fn draw_hemisphere_fails_on_wrong_input() {
         let mut surf = Surface::new();
@@ -501,6 +1146,49 @@ mod tests {
         assert!(surf.get_buffer().len() > 0);
     }
 
+    #[test]
+    fn with_cap_works() {
+        let mut surf = Surface::new();
+        surf.draw_hemisphere(&[0.0, 0.0, 1.0], 2.0, -180.0, 180.0, 4, 2, false)
+            .unwrap();
+        assert_eq!(surf.get_buffer().matches("plot_surface").count(), 1);
+
+        surf.clear_buffer();
+        surf.set_with_cap(true);
+        surf.draw_hemisphere(&[0.0, 0.0, 1.0], 2.0, -180.0, 180.0, 4, 2, false)
+            .unwrap();
+        assert_eq!(surf.get_buffer().matches("plot_surface").count(), 2);
+        let b = surf.get_buffer();
+        assert!(b.contains("sf=ax3d().plot_surface(x,y,z,cmap=plt.get_cmap('bwr'))\nx=np.array([[0,-2,],[0,"));
+        assert!(b.ends_with(
+            "z=np.array([[1,1.0000000000000002,],[1,1.0000000000000002,],[1,1.0000000000000002,],\
+             [1,1.0000000000000002,],[1,1.0000000000000002,],],dtype=float)\n\
+             sf=ax3d().plot_surface(x,y,z,cmap=plt.get_cmap('bwr'))\n"
+        ));
+
+        // the full sphere has no cuts
+        surf.clear_buffer();
+        surf.draw_sphere(&[0.0, 0.0, 0.0], 1.0, 4, 4).unwrap();
+        assert_eq!(surf.get_buffer().matches("plot_surface").count(), 1);
+
+        // the cuts of the superquadric are closed
+        surf.clear_buffer();
+        let d3 = &[1.0, 1.0, 1.0];
+        let k = &[2.0, 2.0, 2.0];
+        surf.draw_superquadric(d3, d3, k, -180.0, 180.0, -90.0, 0.0, 4, 4)
+            .unwrap();
+        assert_eq!(surf.get_buffer().matches("plot_surface").count(), 2);
+        assert!(surf
+            .get_buffer()
+            .ends_with("dtype=float)\nsf=ax3d().plot_surface(x,y,z,cmap=plt.get_cmap('bwr'))\n"));
+        assert!(surf.get_buffer().contains("z=np.array([[1,1,],[1,1,],"));
+        surf.clear_buffer();
+        surf.draw_superquadric(d3, d3, k, -180.0, 180.0, -30.0, 0.0, 4, 4)
+            .unwrap();
+        assert_eq!(surf.get_buffer().matches("plot_surface").count(), 3);
+        assert!(surf.get_buffer().contains("z=np.array([[1,1,],[1,1,],"));
+    }
+
     #[test]
     fn draw_superquadric_fails_on_wrong_input() {
         let d2 = &[0.0, 0.0];
@@ -526,6 +1214,27 @@ mod tests {
         assert_eq!(res.err(), Some("exponents k must be greater than zero"));
         let res = surf.draw_superquadric(d3, d3, kc, 0.0, 180.0, 0.0, 180.0, 2, 2);
         assert_eq!(res.err(), Some("exponents k must be greater than zero"));
+
+        let msg_alpha_range = Some("alpha_min and alpha_max must be in [-180, 180]");
+        let res = surf.draw_superquadric(d3, d3, d3, -181.0, 180.0, -90.0, 90.0, 2, 2);
+        assert_eq!(res.err(), msg_alpha_range);
+        let res = surf.draw_superquadric(d3, d3, d3, 0.0, 360.0, -90.0, 90.0, 2, 2);
+        assert_eq!(res.err(), msg_alpha_range);
+        let res = surf.draw_superquadric(d3, d3, d3, f64::NAN, 180.0, -90.0, 90.0, 2, 2);
+        assert_eq!(res.err(), msg_alpha_range);
+        let res = surf.draw_superquadric(d3, d3, d3, 90.0, -90.0, -90.0, 90.0, 2, 2);
+        assert_eq!(res.err(), Some("alpha_min must be smaller than alpha_max"));
+        let res = surf.draw_superquadric(d3, d3, d3, 90.0, 90.0, -90.0, 90.0, 2, 2);
+        assert_eq!(res.err(), Some("alpha_min must be smaller than alpha_max"));
+
+        let msg_theta_range = Some("theta_min and theta_max must be in [-90, 90]");
+        let res = surf.draw_superquadric(d3, d3, d3, -180.0, 180.0, -91.0, 90.0, 2, 2);
+        assert_eq!(res.err(), msg_theta_range);
+        let res = surf.draw_superquadric(d3, d3, d3, -180.0, 180.0, 0.0, 180.0, 2, 2);
+        assert_eq!(res.err(), msg_theta_range);
+        let res = surf.draw_superquadric(d3, d3, d3, -180.0, 180.0, 45.0, -45.0, 2, 2);
+        assert_eq!(res.err(), Some("theta_min must be smaller than theta_max"));
+        assert_eq!(surf.get_buffer().len(), 0);
     }
 
     #[test]
@@ -537,8 +1246,8 @@ mod tests {
             &[2.0, 2.0, 2.0],
             0.0,
             180.0,
-            0.0,
-            180.0,
+            -90.0,
+            90.0,
             2,
             2,
         )
@@ -546,6 +1255,29 @@ mod tests {
         assert!(surf.get_buffer().len() > 0);
     }
 
+    #[test]
+    fn close_alpha_works() {
+        let mut surf = Surface::new();
+        surf.set_close_alpha(true);
+        let d3 = &[1.0, 1.0, 1.0];
+        let res = surf.draw_superquadric(d3, d3, d3, 0.0, 180.0, -90.0, 90.0, 2, 2);
+        assert_eq!(res.err(), Some("close_alpha requires alpha_max - alpha_min = 360"));
+        let res = surf.draw_hemisphere(d3, 1.0, 0.0, 180.0, 2, 2, false);
+        assert_eq!(res.err(), Some("close_alpha requires alpha_max - alpha_min = 360"));
+
+        let (x, y, z) = surf.draw_sphere(&[0.3, 0.2, 0.1], 1.0, 7, 5).unwrap();
+        assert_eq!(x[7], x[0]);
+        assert_eq!(y[7], y[0]);
+        assert_eq!(z[7], z[0]);
+
+        let (x, y, z) = surf
+            .draw_hemisphere(&[0.3, 0.2, 0.1], 1.0, -180.0, 180.0, 7, 5, true)
+            .unwrap();
+        assert_eq!(x[7], x[0]);
+        assert_eq!(y[7], y[0]);
+        assert_eq!(z[7], z[0]);
+    }
+
     #[test]
     fn draw_sphere_fails_on_wrong_input() {
         let mut surf = Surface::new();
@@ -564,4 +1296,199 @@ mod tests {
         surf.draw_sphere(&[0.0, 0.0, 0.0], 1.0, 2, 2).unwrap();
         assert!(surf.get_buffer().len() > 0);
     }
+
+    #[test]
+    fn draw_sphere_with_normals_works() {
+        let mut surf = Surface::new();
+        let (x, y, z, nx, ny, nz) = surf.draw_sphere_with_normals(&[0.0, 0.0, 0.0], 1.0, 8, 6).unwrap();
+        for (i, j) in [(0, 0), (1, 2), (3, 3), (5, 1), (8, 6)] {
+            assert_eq!((nx[i][j], ny[i][j], nz[i][j]), (x[i][j], y[i][j], z[i][j]));
+        }
+
+        let c = &[1.0, 2.0, 3.0];
+        let (x, y, z, nx, ny, nz) = surf.draw_sphere_with_normals(c, 2.0, 8, 6).unwrap();
+        for (i, j) in [(0, 0), (1, 2), (3, 3), (5, 1), (8, 6)] {
+            let len = f64::sqrt(nx[i][j] * nx[i][j] + ny[i][j] * ny[i][j] + nz[i][j] * nz[i][j]);
+            assert!(f64::abs(len - 1.0) < 1e-15);
+            assert!(f64::abs(nx[i][j] - (x[i][j] - 1.0) / 2.0) < 1e-15);
+            assert!(f64::abs(ny[i][j] - (y[i][j] - 2.0) / 2.0) < 1e-15);
+            assert!(f64::abs(nz[i][j] - (z[i][j] - 3.0) / 2.0) < 1e-15);
+        }
+    }
+
+    #[test]
+    fn draw_superquadric_with_normals_works() {
+        let mut surf = Surface::new();
+        let (c, k) = (&[0.0, 0.0, 0.0], &[2.0, 2.0, 2.0]);
+        let res = surf.draw_superquadric_with_normals(c, &[1.0, 1.0], k, -180.0, 180.0, -90.0, 90.0, 8, 6);
+        assert_eq!(res.err(), Some("c.len(), r.len(), and k.len() must be equal to 3"));
+
+        // unit sphere: the normal equals the normalized position
+        let (x, y, z, nx, ny, nz) = surf
+            .draw_superquadric_with_normals(c, &[1.0, 1.0, 1.0], k, -180.0, 180.0, -90.0, 90.0, 8, 6)
+            .unwrap();
+        for (i, j) in [(0, 0), (1, 2), (3, 3), (5, 1), (8, 6)] {
+            let len = f64::sqrt(x[i][j] * x[i][j] + y[i][j] * y[i][j] + z[i][j] * z[i][j]);
+            assert!(f64::abs(nx[i][j] - x[i][j] / len) < 1e-14);
+            assert!(f64::abs(ny[i][j] - y[i][j] / len) < 1e-14);
+            assert!(f64::abs(nz[i][j] - z[i][j] / len) < 1e-14);
+        }
+
+        // super-ellipsoid: unit normals orthogonal to the grid lines
+        let (x, y, z, nx, ny, nz) = surf
+            .draw_superquadric_with_normals(
+                c,
+                &[1.0, 2.0, 3.0],
+                &[4.0, 4.0, 4.0],
+                -180.0,
+                180.0,
+                -60.0,
+                60.0,
+                40,
+                40,
+            )
+            .unwrap();
+        let (i, j) = (7, 23);
+        let len = f64::sqrt(nx[i][j] * nx[i][j] + ny[i][j] * ny[i][j] + nz[i][j] * nz[i][j]);
+        assert!(f64::abs(len - 1.0) < 1e-14);
+        let (dx, dy, dz) = (
+            x[i + 1][j] - x[i - 1][j],
+            y[i + 1][j] - y[i - 1][j],
+            z[i + 1][j] - z[i - 1][j],
+        );
+        let dot = (nx[i][j] * dx + ny[i][j] * dy + nz[i][j] * dz) / f64::sqrt(dx * dx + dy * dy + dz * dz);
+        assert!(f64::abs(dot) < 1e-2);
+    }
+
+    #[test]
+    fn draw_cone_fails_on_wrong_input() {
+        let mut surf = Surface::new();
+        let res = surf.draw_cone(&[0.0, 0.0], &[0.0, 0.0, 1.0], 1.0, 0.0, 1, 3);
+        assert_eq!(res.err(), Some("c.len() and axis.len() must be equal to 3"));
+        let res = surf.draw_cone(&[0.0, 0.0, 0.0], &[0.0, 1.0], 1.0, 0.0, 1, 3);
+        assert_eq!(res.err(), Some("c.len() and axis.len() must be equal to 3"));
+        let res = surf.draw_cone(&[0.0, 0.0, 0.0], &[0.0, 0.0, 1.0], 0.0, 0.0, 1, 3);
+        assert_eq!(
+            res.err(),
+            Some("r_bottom must be positive and r_top must be non-negative")
+        );
+        let res = surf.draw_cone(&[0.0, 0.0, 0.0], &[0.0, 0.0, 1.0], 1.0, -1.0, 1, 3);
+        assert_eq!(
+            res.err(),
+            Some("r_bottom must be positive and r_top must be non-negative")
+        );
+        let res = surf.draw_cone(&[0.0, 0.0, 0.0], &[0.0, 0.0, 1.0], 1.0, 0.0, 0, 3);
+        assert_eq!(res.err(), Some("n_axis and n_alpha must be ≥ 1"));
+        let res = surf.draw_cone(&[0.0, 0.0, 0.0], &[0.0, 0.0, 1.0], 1.0, 0.0, 1, 0);
+        assert_eq!(res.err(), Some("n_axis and n_alpha must be ≥ 1"));
+        let res = surf.draw_cone(&[0.0, 0.0, 0.0], &[0.0, 0.0, 0.0], 1.0, 0.0, 1, 3);
+        assert_eq!(res.err(), Some("a-to-b segment is too short"));
+    }
+
+    #[test]
+    fn draw_cone_works() {
+        let mut surf = Surface::new();
+        let (x, y, z) = surf
+            .draw_cone(&[1.0, 2.0, 3.0], &[0.0, 0.0, 2.0], 1.0, 0.0, 2, 4)
+            .unwrap();
+        assert_eq!(x.len(), 5);
+        assert_eq!(x[0].len(), 3);
+        for i in 0..5 {
+            // bottom circle
+            let r = f64::sqrt((x[i][0] - 1.0) * (x[i][0] - 1.0) + (y[i][0] - 2.0) * (y[i][0] - 2.0));
+            assert!(f64::abs(r - 1.0) < 1e-15);
+            assert!(f64::abs(z[i][0] - 3.0) < 1e-15);
+            // middle circle
+            let r = f64::sqrt((x[i][1] - 1.0) * (x[i][1] - 1.0) + (y[i][1] - 2.0) * (y[i][1] - 2.0));
+            assert!(f64::abs(r - 0.5) < 1e-15);
+            assert!(f64::abs(z[i][1] - 4.0) < 1e-15);
+            // apex
+            assert!(f64::abs(x[i][2] - 1.0) < 1e-15);
+            assert!(f64::abs(y[i][2] - 2.0) < 1e-15);
+            assert!(f64::abs(z[i][2] - 5.0) < 1e-15);
+        }
+        assert!(surf.get_buffer().len() > 0);
+    }
+
+    #[test]
+    fn draw_tube_fails_on_wrong_input() {
+        let mut surf = Surface::new();
+        let res = surf.draw_tube(&[0.0, 0.0], &[0.0, 0.0, 1.0], &[1.0, 1.0], 3);
+        assert_eq!(res.err(), Some("base.len() and axis.len() must be equal to 3"));
+        let res = surf.draw_tube(&[0.0, 0.0, 0.0], &[0.0, 1.0], &[1.0, 1.0], 3);
+        assert_eq!(res.err(), Some("base.len() and axis.len() must be equal to 3"));
+        let res = surf.draw_tube(&[0.0, 0.0, 0.0], &[0.0, 0.0, 1.0], &[1.0], 3);
+        assert_eq!(res.err(), Some("radii.len() must be ≥ 2"));
+        let res = surf.draw_tube(&[0.0, 0.0, 0.0], &[0.0, 0.0, 1.0], &[1.0, -1.0], 3);
+        assert_eq!(res.err(), Some("radii must be non-negative"));
+        let res = surf.draw_tube(&[0.0, 0.0, 0.0], &[0.0, 0.0, 1.0], &[1.0, f64::NAN], 3);
+        assert_eq!(res.err(), Some("radii must be non-negative"));
+        let res = surf.draw_tube(&[0.0, 0.0, 0.0], &[0.0, 0.0, 1.0], &[1.0, 1.0], 2);
+        assert_eq!(res.err(), Some("n_theta must be ≥ 3"));
+        let res = surf.draw_tube(&[0.0, 0.0, 0.0], &[0.0, 0.0, 0.0], &[1.0, 1.0], 3);
+        assert_eq!(res.err(), Some("a-to-b segment is too short"));
+    }
+
+    #[test]
+    fn draw_tube_works() {
+        let mut surf = Surface::new();
+        let radii = &[1.0, 1.5, 1.0];
+        let (x, y, z) = surf.draw_tube(&[1.0, 2.0, 3.0], &[0.0, 0.0, 2.0], radii, 4).unwrap();
+        assert_eq!(x.len(), 5);
+        assert_eq!(x[0].len(), 3);
+        for i in 0..5 {
+            for j in 0..3 {
+                let r = f64::sqrt((x[i][j] - 1.0) * (x[i][j] - 1.0) + (y[i][j] - 2.0) * (y[i][j] - 2.0));
+                assert!(f64::abs(r - radii[j]) < 1e-15);
+                assert!(f64::abs(z[i][j] - (3.0 + (j as f64))) < 1e-15);
+            }
+        }
+
+        // same as the cone with one axis division
+        let mut cone = Surface::new();
+        let res = cone
+            .draw_cone(&[1.0, 2.0, 3.0], &[0.0, 0.0, 2.0], 1.0, 0.5, 1, 4)
+            .unwrap();
+        let mut tube = Surface::new();
+        let (x, y, z) = tube
+            .draw_tube(&[1.0, 2.0, 3.0], &[0.0, 0.0, 2.0], &[1.0, 0.5], 4)
+            .unwrap();
+        assert_eq!((x, y, z), res);
+        assert_eq!(tube.get_buffer(), cone.get_buffer());
+    }
+
+    #[test]
+    fn draw_torus_fails_on_wrong_input() {
+        let mut surf = Surface::new();
+        let res = surf.draw_torus(&[0.0, 0.0], 1.0, 0.5, 2, 2);
+        assert_eq!(res.err(), Some("c.len() must be equal to 3"));
+        let res = surf.draw_torus(&[0.0, 0.0, 0.0], 0.0, 0.5, 2, 2);
+        assert_eq!(res.err(), Some("r_major and r_minor must be positive"));
+        let res = surf.draw_torus(&[0.0, 0.0, 0.0], 1.0, -0.5, 2, 2);
+        assert_eq!(res.err(), Some("r_major and r_minor must be positive"));
+        let res = surf.draw_torus(&[0.0, 0.0, 0.0], 1.0, 0.5, 0, 2);
+        assert_eq!(res.err(), Some("n_u and n_v must be ≥ 1"));
+        let res = surf.draw_torus(&[0.0, 0.0, 0.0], 1.0, 0.5, 2, 0);
+        assert_eq!(res.err(), Some("n_u and n_v must be ≥ 1"));
+    }
+
+    #[test]
+    fn draw_torus_works() {
+        let mut surf = Surface::new();
+        let (x, y, z) = surf.draw_torus(&[1.0, 2.0, 3.0], 2.0, 0.5, 4, 4).unwrap();
+        assert_eq!(x.len(), 5);
+        assert_eq!(x[0].len(), 5);
+        // u = 0, v = 0 (outer equator)
+        assert!(f64::abs(x[0][0] - 3.5) < 1e-15);
+        assert!(f64::abs(y[0][0] - 2.0) < 1e-15);
+        assert!(f64::abs(z[0][0] - 3.0) < 1e-15);
+        // u = π/2, v = π (inner equator)
+        assert!(f64::abs(x[1][2] - 1.0) < 1e-15);
+        assert!(f64::abs(y[1][2] - 3.5) < 1e-15);
+        assert!(f64::abs(z[1][2] - 3.0) < 1e-15);
+        // u = 0, v = π/2 (top)
+        assert!(f64::abs(x[0][1] - 3.0) < 1e-15);
+        assert!(f64::abs(z[0][1] - 3.5) < 1e-15);
+        assert!(surf.get_buffer().len() > 0);
+    }
 }
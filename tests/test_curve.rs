@@ -1,4 +1,4 @@
-use plotpy::{Curve, Plot, RayEndpoint, StrError};
+use plotpy::{Curve, GraphMaker, Plot, RayEndpoint, StrError};
 use std::f64::consts::PI;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
@@ -217,3 +217,67 @@ fn test_curve_3d() -> Result<(), StrError> {
     assert!(n_lines > 800 && n_lines < 900);
     Ok(())
 }
+
+#[test]
+fn test_curve_scatter_3d() -> Result<(), StrError> {
+    // curve object and options
+    let mut curve = Curve::new();
+    curve
+        .set_marker_color("#1862ab")
+        .set_marker_line_color("#cda500")
+        .set_marker_size(8.0)
+        .set_marker_style("o");
+
+    // draw points
+    let x = &[1.0, 2.0, 3.0, 4.0, 5.0];
+    let y = &[1.0, 4.0, 9.0, 16.0, 25.0];
+    let z = &[0.0, 0.0, 0.0, 1.0, 1.0];
+    curve.draw_scatter_3d(x, y, z);
+
+    // add curve to plot
+    let mut plot = Plot::new();
+    plot.add(&curve);
+
+    // save figure
+    let path = Path::new(OUT_DIR).join("integ_curve_scatter_3d.svg");
+    plot.save(&path)?;
+
+    // check number of lines
+    let file = File::open(path).map_err(|_| "cannot open file")?;
+    let buffered = BufReader::new(file);
+    let lines_iter = buffered.lines();
+    let n_lines = lines_iter.count();
+    assert!(n_lines > 500);
+    Ok(())
+}
+
+#[test]
+fn test_curve_by_category() -> Result<(), StrError> {
+    // two species with overlapping ranges
+    let x = &[1.0, 1.5, 2.0, 2.5, 3.0, 3.5];
+    let y = &[2.0, 1.0, 3.0, 2.5, 4.0, 3.0];
+    let species = &["setosa", "virginica", "setosa", "virginica", "setosa", "virginica"];
+    let mut curve = Curve::new();
+    curve.set_marker_size(8.0).draw_by_category(x, y, species)?;
+
+    // two scatter commands with distinct labels (first appearance order)
+    let buffer = curve.get_buffer();
+    assert_eq!(buffer.matches("plt.scatter(").count(), 2);
+    assert!(buffer.contains("color='C0',label=r'setosa'"));
+    assert!(buffer.contains("color='C1',label=r'virginica'"));
+
+    // add curve to plot (the legend lists the categories)
+    let mut plot = Plot::new();
+    plot.add(&curve).grid_labels_legend("x", "y");
+
+    // save figure
+    let path = Path::new(OUT_DIR).join("integ_curve_by_category.svg");
+    plot.save(&path)?;
+
+    // check number of lines
+    let file = File::open(path).map_err(|_| "cannot open file")?;
+    let buffered = BufReader::new(file);
+    let lines_iter = buffered.lines();
+    assert!(lines_iter.count() > 400);
+    Ok(())
+}
@@ -159,6 +159,41 @@ fn test_plot_3d() -> Result<(), StrError> {
     Ok(())
 }
 
+#[test]
+fn test_plot_colormap_preview() -> Result<(), StrError> {
+    let path = Path::new(OUT_DIR).join("integ_plot_colormap_preview.svg");
+    Plot::save_colormap_preview("viridis", &path)?;
+
+    // check number of lines
+    let file = File::open(path).map_err(|_| "cannot open file")?;
+    let buffered = BufReader::new(file);
+    let lines_iter = buffered.lines();
+    assert!(lines_iter.count() > 30);
+    Ok(())
+}
+
+#[test]
+fn test_plot_stream() -> Result<(), StrError> {
+    let path = Path::new(OUT_DIR).join("integ_plot_stream.svg");
+    let mut plot = Plot::new();
+    plot.begin_stream(&path)?;
+    for k in 0..3 {
+        let x = linspace(0.0, 1.0, 1000);
+        let y: Vec<f64> = x.iter().map(|v| f64::sin((k + 1) as f64 * PI * v)).collect();
+        let mut curve = Curve::new();
+        curve.draw(&x, &y);
+        plot.add(&curve); // the curve may be dropped now
+    }
+    plot.grid_and_labels("x", "y").end_stream()?;
+
+    // check number of lines
+    let file = File::open(path).map_err(|_| "cannot open file")?;
+    let buffered = BufReader::new(file);
+    let lines_iter = buffered.lines();
+    assert!(lines_iter.count() > 500);
+    Ok(())
+}
+
 #[test]
 fn test_plot_error() {
     let plot = Plot::new();
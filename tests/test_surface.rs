@@ -215,3 +215,77 @@ fn test_surface_points_void() -> Result<(), StrError> {
     assert!(n_lines > 800 && n_lines < 900);
     Ok(())
 }
+
+#[test]
+fn test_surface_trisurf() -> Result<(), StrError> {
+    // scattered points on a spiral
+    let n = 60;
+    let (mut x, mut y, mut z) = (Vec::new(), Vec::new(), Vec::new());
+    for i in 0..n {
+        let t = (i as f64) * 0.3;
+        let r = 0.1 + (i as f64) / (n as f64);
+        x.push(r * f64::cos(t));
+        y.push(r * f64::sin(t));
+        z.push(r * r);
+    }
+
+    // draw triangulated surface
+    let mut surface = Surface::new();
+    surface
+        .set_colormap_name("viridis")
+        .set_with_colorbar(true)
+        .draw_trisurf(&x, &y, &z)?;
+
+    // add surface to plot
+    let mut plot = Plot::new();
+    plot.add(&surface);
+
+    // save figure
+    let path = Path::new(OUT_DIR).join("integ_surface_trisurf.svg");
+    plot.save(&path)?;
+
+    // check number of lines
+    let file = File::open(path).map_err(|_| "cannot open file")?;
+    let buffered = BufReader::new(file);
+    let lines_iter = buffered.lines();
+    assert!(lines_iter.count() > 500);
+    Ok(())
+}
+
+#[test]
+fn test_surface_write_obj() -> Result<(), StrError> {
+    let mut surface = Surface::new();
+    assert_eq!(
+        surface
+            .write_obj("/tmp/plotpy/integ_tests/integ_surface_empty.obj")
+            .err(),
+        Some("there is no meshgrid to be written")
+    );
+
+    // 3 x 3 grid with a coincident corner (triangle) and a NaN (skipped cell)
+    let x = vec![vec![0.0, 1.0, 2.0], vec![0.0, 1.0, 2.0], vec![1.0, 1.0, 2.0]];
+    let y = vec![vec![0.0, 0.0, 0.0], vec![1.0, 1.0, 1.0], vec![2.0, 2.0, 2.0]];
+    let z = vec![vec![0.0, 0.0, f64::NAN], vec![0.0, 0.0, 0.0], vec![0.0, 0.0, 0.0]];
+    surface.draw(&x, &y, &z);
+    assert_eq!(
+        surface
+            .write_obj("/tmp/plotpy/integ_tests/integ_surface_empty.obj")
+            .err(),
+        Some("there is no meshgrid to be written")
+    );
+    surface.set_keep_mesh(true).draw(&x, &y, &z);
+
+    // write file
+    let path = Path::new(OUT_DIR).join("integ_surface_write_obj.obj");
+    surface.write_obj(&path)?;
+
+    // check file
+    let file = File::open(path).map_err(|_| "cannot open file")?;
+    let buffered = BufReader::new(file);
+    let lines: Vec<String> = buffered.lines().map(|l| l.unwrap()).collect();
+    assert_eq!(lines.iter().filter(|l| l.starts_with("v ")).count(), 9);
+    assert_eq!(lines[0], "v 0 0 0");
+    assert_eq!(lines[2], "v 2 0 NaN");
+    assert_eq!(&lines[9..], &["f 1 2 5 4", "f 4 5 8", "f 5 6 9 8"]);
+    Ok(())
+}
@@ -136,3 +136,51 @@ fn test_surface_superquadric() -> Result<(), StrError> {
     assert!(lines_iter.count() > 24780);
     Ok(())
 }
+
+#[test]
+fn test_surface_cone() -> Result<(), StrError> {
+    let mut surface = Surface::new();
+    surface.set_surf_color("red");
+    surface.draw_cone(&[0.0, 0.0, 0.0], &[0.0, 0.0, 2.0], 1.0, 0.0, 1, 20)?;
+    surface.set_surf_color("blue");
+    surface.draw_cone(&[3.0, 0.0, 0.0], &[0.0, 0.0, 2.0], 1.0, 0.5, 2, 20)?;
+
+    // add surface to plot
+    let mut plot = Plot::new();
+    plot.add(&surface);
+
+    // save figure
+    let path = Path::new(OUT_DIR).join("integ_surface_cone.svg");
+    plot.set_range_3d(-1.0, 4.0, -2.5, 2.5, 0.0, 2.0).set_equal_axes(true);
+    plot.save(&path)?;
+
+    // check number of lines
+    let file = File::open(path).map_err(|_| "cannot open file")?;
+    let buffered = BufReader::new(file);
+    let lines_iter = buffered.lines();
+    assert!(lines_iter.count() > 900);
+    Ok(())
+}
+
+#[test]
+fn test_surface_torus() -> Result<(), StrError> {
+    let mut surface = Surface::new();
+    surface.set_surf_color("#e5b630");
+    surface.draw_torus(&[0.0, 0.0, 0.0], 1.0, 0.3, 40, 20)?;
+
+    // add surface to plot
+    let mut plot = Plot::new();
+    plot.add(&surface);
+
+    // save figure
+    let path = Path::new(OUT_DIR).join("integ_surface_torus.svg");
+    plot.set_range_3d(-1.5, 1.5, -1.5, 1.5, -1.5, 1.5).set_equal_axes(true);
+    plot.save(&path)?;
+
+    // check number of lines
+    let file = File::open(path).map_err(|_| "cannot open file")?;
+    let buffered = BufReader::new(file);
+    let lines_iter = buffered.lines();
+    assert!(lines_iter.count() > 900);
+    Ok(())
+}